@@ -3,23 +3,30 @@ use super::TreeOperation;
 use crate::app::configs::deserializers;
 use crate::app::structs::direction::Direction;
 use crate::app::structs::orientation::Orientation;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
 
-#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+/// Settings for the "golden_ratio" tiling strategy
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
 #[serde(default)]
 pub struct GoldenRatio {
+    /// Ratio of the first split
     #[serde(deserialize_with = "deserializers::to_u8_minmax::<10,90,_>")]
+    #[schemars(range(min = 10, max = 90), example = 65)]
     ratio: u8,
+    /// Places the windows clockwise (true) or counterclockwise (false)
     clockwise: bool,
     #[serde(skip)]
     current_direction: Direction,
+    /// If true, the first split will be vertical
     #[serde(
         rename = "vertical",
         deserialize_with = "deserialize_first_split",
         serialize_with = "serialize_first_split"
     )]
+    #[schemars(with = "bool")]
     first_split: Orientation,
     #[serde(skip)]
     count: u8,
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::app::structs::{direction::Direction, orientation::Orientation};
@@ -67,9 +68,11 @@ impl Default for MonoAxis {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+/// Settings for the "vertical" tiling strategy
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
 #[serde(default)]
 pub struct MonoAxisVertical {
+    /// If true, new windows will be placed on the bottom side of the screen
     grow_down: bool,
     #[serde(skip)]
     layout: MonoAxis,
@@ -107,9 +110,11 @@ impl Default for MonoAxisVertical {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+/// Settings for the "horizontal" tiling strategy
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
 #[serde(default)]
 pub struct MonoAxisHorizontal {
+    /// If true, new windows will be placed on the right side of the screen
     grow_right: bool,
     #[serde(skip)]
     layout: MonoAxis,
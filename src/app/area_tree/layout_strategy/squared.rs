@@ -1,10 +1,12 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::app::structs::{direction::Direction, orientation::Orientation};
 
 use super::{LayoutStrategy, TreeOperation};
 
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+/// Settings for the "squared" tiling strategy
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
 #[serde(default)]
 pub struct Squared {
     #[serde(skip)]
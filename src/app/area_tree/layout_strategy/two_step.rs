@@ -3,17 +3,23 @@ use super::TreeOperation;
 use crate::app::configs::deserializers;
 use crate::app::structs::direction::Direction;
 use crate::app::structs::orientation::Orientation;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+/// Settings for the "twostep" tiling strategy
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
 #[serde(default)]
 pub struct TwoStep {
+    /// First insertion direction
     #[serde(rename = "first_step")]
     first_dir: Direction,
+    /// Second insertion direction
     #[serde(rename = "second_step")]
     second_dir: Direction,
+    /// Ratio of the first split
     #[serde(deserialize_with = "deserializers::to_u8_minmax::<10,90,_>")]
+    #[schemars(range(min = 10, max = 90), example = 65)]
     ratio: u8,
     #[serde(skip)]
     current_dir: Direction,
@@ -19,6 +19,7 @@ pub struct AreaTree<T: Copy + Clone + Eq + Hash> {
     area: Area,
     paddings: Paddings,
     strategy: LayoutStrategyEnum,
+    base_strategy: LayoutStrategyEnum,
     ids_map: std::collections::HashMap<T, AreaLeaf<T>>,
 }
 
@@ -29,6 +30,7 @@ impl<T: Copy + Clone + Eq + Hash + Debug> AreaTree<T> {
             base_area,
             area: base_area.with_paddings(paddings),
             paddings,
+            base_strategy: strategy.clone(),
             strategy,
             ids_map: std::collections::HashMap::new(),
         }
@@ -142,6 +144,21 @@ impl<T: Copy + Clone + Eq + Hash + Debug> AreaTree<T> {
         }
     }
 
+    /// Sets the share (in percent) of the split taken by the leaf with the given id
+    pub(crate) fn set_leaf_ratio(&mut self, id: T, ratio: u8) {
+        let center = match self.ids_map.get(&id) {
+            Some(leaf) => leaf.viewbox.get_center(),
+            None => return,
+        };
+
+        if let Some(parent) = self.root.find_parent_mut(center, self.area) {
+            let is_left = parent.left.as_ref().is_some_and(|l| l.id == Some(id));
+            let ratio = ratio.min(100);
+            parent.ratio = if is_left { ratio } else { 100 - ratio };
+            self.update_map();
+        }
+    }
+
     pub(crate) fn swap_ids(&mut self, id1: T, id2: T) {
         let (p1, p2) = (self.ids_map.get(&id1), self.ids_map.get(&id2));
         if let (Some(l1), Some(l2)) = (p1, p2) {
@@ -191,6 +208,27 @@ impl<T: Copy + Clone + Eq + Hash + Debug> AreaTree<T> {
         self.update_map();
     }
 
+    pub fn set_paddings(&mut self, paddings: Paddings) {
+        self.paddings = paddings;
+        self.area = self.base_area.with_paddings(paddings);
+        self.update_map();
+    }
+
+    /// Sets the layout strategy, rebuilding the tree by reinserting its leaves in order
+    /// The tree is left untouched if the strategy did not change, keeping its current layout
+    pub fn set_strategy(&mut self, strategy: LayoutStrategyEnum) {
+        // INFO: `strategy` holds the state of the last operation, so the configured one is compared
+        if self.base_strategy == strategy {
+            return;
+        }
+
+        let ids: Vec<T> = self.leaves(None).iter().map(|l| l.id).collect();
+        self.base_strategy = strategy.clone();
+        self.strategy = strategy;
+        self.clear();
+        ids.into_iter().for_each(|id| self.insert(id));
+    }
+
     pub fn replace_root(&mut self, other: AreaTree<T>) {
         self.root = other.root;
         self.update_map();
@@ -229,3 +267,57 @@ impl<T: Debug + Copy + Eq + Hash> Debug for AreaTree<T> {
         write!(f, "{:#?}", self.root)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AreaTree;
+    use crate::app::area_tree::layout_strategy::squared::Squared;
+    use crate::app::area_tree::layout_strategy::LayoutStrategyEnum;
+    use crate::app::structs::area::Area;
+    use crate::app::structs::paddings::Paddings;
+
+    fn leaf_area(tree: &AreaTree<u8>, id: u8) -> u32 {
+        tree.find_leaf(id, 0).unwrap().viewbox.calc_area()
+    }
+
+    #[test]
+    fn test_set_leaf_ratio() {
+        let mut tree = AreaTree::new(
+            Area::new(0, 0, 1000, 500),
+            LayoutStrategyEnum::default(),
+            Paddings::default(),
+        );
+        tree.insert(1);
+        tree.insert(2);
+
+        tree.set_leaf_ratio(2, 70);
+        assert_eq!(leaf_area(&tree, 2), 350_000);
+        assert_eq!(leaf_area(&tree, 1), 150_000);
+
+        tree.set_leaf_ratio(1, 70);
+        assert_eq!(leaf_area(&tree, 1), 350_000);
+        assert_eq!(leaf_area(&tree, 2), 150_000);
+
+        tree.set_leaf_ratio(3, 20);
+        assert_eq!(leaf_area(&tree, 1), 350_000);
+    }
+
+    #[test]
+    fn test_set_strategy() {
+        let mut tree = AreaTree::new(
+            Area::new(0, 0, 1000, 500),
+            LayoutStrategyEnum::default(),
+            Paddings::default(),
+        );
+        tree.insert(1);
+        tree.insert(2);
+        tree.set_leaf_ratio(2, 70);
+
+        tree.set_strategy(LayoutStrategyEnum::default());
+        assert_eq!(leaf_area(&tree, 2), 350_000);
+
+        tree.set_strategy(LayoutStrategyEnum::Squared(Squared::default()));
+        assert_eq!(leaf_area(&tree, 1), 250_000);
+        assert_eq!(leaf_area(&tree, 2), 250_000);
+    }
+}
@@ -1,4 +1,10 @@
+use crate::app::configs::loader;
+use crate::app::rules_tester::parse_hex;
 use clap::Parser;
+use clap::Subcommand;
+use std::path::PathBuf;
+
+const CONFIG_ENV_VAR: &str = "MONDRIAN_CONFIG";
 
 fn less_than_2(l: &str) -> Result<u8, String> {
     const ERROR_MSG: &str = "LOG_TYPE must be 0 (no log file), 1 (error log file) or 2 (all log files)";
@@ -38,6 +44,109 @@ pub struct CliArgs {
 
     #[arg(long = "healthcheck", help = "Enable health check", default_value_t = false)]
     pub health_check: bool,
+
+    #[arg(
+        long = "config",
+        value_name = "PATH",
+        global = true,
+        help = "Path of the configuration file (overrides the MONDRIAN_CONFIG environment variable)"
+    )]
+    config_path: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    #[command(about = "Print the JSON schema of the configuration file")]
+    Schema,
+
+    #[command(about = "Print the default configuration file")]
+    DefaultConfig,
+
+    #[command(about = "Print which rules match a window (by default, the focused one) and why")]
+    ExplainWindow {
+        #[arg(
+            long = "hwnd",
+            help = "Handle of the window (e.g. as shown by the dumpstateinfo action)",
+            conflicts_with = "filter"
+        )]
+        hwnd: Option<isize>,
+
+        #[arg(
+            long = "filter",
+            value_name = "FILTER",
+            help = "Filter matching the window, as a TOML inline table (e.g. \"{ exename = 'app.exe' }\")"
+        )]
+        filter: Option<String>,
+    },
+
+    #[command(about = "Print how the rules apply to a window with the given properties, without opening it")]
+    TestRules {
+        #[arg(long = "exe", help = "Executable name of the window")]
+        exe: Option<String>,
+
+        #[arg(long = "path", help = "Full path of the executable of the window")]
+        path: Option<String>,
+
+        #[arg(long = "title", help = "Title of the window")]
+        title: Option<String>,
+
+        #[arg(long = "class", help = "Class name of the window")]
+        class: Option<String>,
+
+        #[arg(
+            long = "style",
+            help = "Style of the window, in hexadecimal (e.g. 16cf0000)",
+            value_parser = parse_hex
+        )]
+        style: Option<u32>,
+
+        #[arg(
+            long = "exstyle",
+            help = "Extended style of the window, in hexadecimal",
+            value_parser = parse_hex
+        )]
+        exstyle: Option<u32>,
+
+        #[arg(long = "monitor", help = "Monitor the window opens on (e.g. MONITOR1)")]
+        monitor: Option<String>,
+
+        #[arg(
+            long = "size",
+            value_name = "WIDTHxHEIGHT",
+            help = "Size of the window (e.g. 800x600)",
+            value_parser = parse_size
+        )]
+        size: Option<(u16, u16)>,
+
+        #[arg(long = "has-owner", help = "The window has an owner window", default_value_t = false)]
+        has_owner: bool,
+
+        #[arg(
+            long = "has-parent",
+            help = "The window has a parent window",
+            default_value_t = false
+        )]
+        has_parent: bool,
+
+        #[arg(
+            long = "batch",
+            value_name = "FILE",
+            help = "JSON file with the list of windows (and their expected outcomes) to test",
+            conflicts_with_all = [
+                "exe", "path", "title", "class", "style", "exstyle", "monitor", "size", "has_owner", "has_parent"
+            ]
+        )]
+        batch: Option<PathBuf>,
+    },
+}
+
+fn parse_size(s: &str) -> Result<(u16, u16), String> {
+    const ERROR_MSG: &str = "SIZE must be in the WIDTHxHEIGHT format (e.g. 800x600)";
+    let (w, h) = s.split_once('x').ok_or(ERROR_MSG)?;
+    Ok((w.parse().map_err(|_| ERROR_MSG)?, h.parse().map_err(|_| ERROR_MSG)?))
 }
 
 impl CliArgs {
@@ -60,4 +169,25 @@ impl CliArgs {
             _ => log::LevelFilter::Info,
         }
     }
+
+    /// Returns the configuration file path, in order of precedence: the `--config` argument, the
+    /// `MONDRIAN_CONFIG` environment variable and the default `~/.config/mondrian/mondrian.{toml,yaml,yml,json}`
+    pub fn get_config_path(&self) -> PathBuf {
+        let path = self
+            .config_path
+            .clone()
+            .or_else(|| {
+                std::env::var_os(CONFIG_ENV_VAR)
+                    .filter(|v| !v.is_empty())
+                    .map(PathBuf::from)
+            })
+            .unwrap_or_else(|| {
+                let config_dir = dirs::home_dir()
+                    .expect("Failed to get home dir")
+                    .join(".config/mondrian");
+                loader::find_config_file(&config_dir)
+            });
+
+        std::path::absolute(&path).unwrap_or(path)
+    }
 }
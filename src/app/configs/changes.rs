@@ -0,0 +1,215 @@
+use super::modules::Modules;
+use super::AppConfig;
+use std::collections::HashSet;
+
+/// Sections of the [`AppConfig`] that can change independently on reload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfigSection {
+    General,
+    Animations,
+    FloatingWins,
+    Rules,
+    Layout,
+    Monitors,
+    Workspaces,
+    Keybindings,
+    Overlays,
+}
+
+/// Sections changed between two configurations
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigChanges(HashSet<ConfigSection>);
+
+impl ConfigChanges {
+    pub fn diff(old: &AppConfig, new: &AppConfig) -> ConfigChanges {
+        // INFO: the destructuring is exhaustive, so that a new field can't be left out of the sections
+        let AppConfig {
+            history_based_navigation,
+            move_cursor_on_focus,
+            auto_reload_configs,
+            detect_maximized_windows,
+            insert_in_monitor,
+            free_move_in_monitor,
+            allow_focus_on_empty_monitor,
+            animations,
+            floating_wins_config,
+            default_workspace,
+            fallback_monitor,
+            events_coalescing,
+            max_batch_latency,
+            ignore_filter,
+            delayed_filter,
+            rules,
+            title_change_filters,
+            tiles_pad,
+            borders_pads,
+            half_focalized_tiles_pad,
+            half_focalized_borders_pads,
+            focalized_pads,
+            scale_with_dpi,
+            layout_strategy,
+            monitors_config,
+            monitor_aliases,
+            workspaces_config,
+            modules: Modules { keybindings, overlays },
+            profiles: _, // INFO: the profiles are already applied to the other fields
+        } = new;
+
+        let general = (
+            history_based_navigation,
+            move_cursor_on_focus,
+            auto_reload_configs,
+            detect_maximized_windows,
+            insert_in_monitor,
+            free_move_in_monitor,
+            allow_focus_on_empty_monitor,
+            default_workspace,
+            fallback_monitor,
+            events_coalescing,
+            max_batch_latency,
+        ) != (
+            &old.history_based_navigation,
+            &old.move_cursor_on_focus,
+            &old.auto_reload_configs,
+            &old.detect_maximized_windows,
+            &old.insert_in_monitor,
+            &old.free_move_in_monitor,
+            &old.allow_focus_on_empty_monitor,
+            &old.default_workspace,
+            &old.fallback_monitor,
+            &old.events_coalescing,
+            &old.max_batch_latency,
+        );
+        let rules = (ignore_filter, delayed_filter, rules, title_change_filters)
+            != (
+                &old.ignore_filter,
+                &old.delayed_filter,
+                &old.rules,
+                &old.title_change_filters,
+            );
+        let layout = (
+            tiles_pad,
+            borders_pads,
+            half_focalized_tiles_pad,
+            half_focalized_borders_pads,
+            focalized_pads,
+            scale_with_dpi,
+            layout_strategy,
+        ) != (
+            &old.tiles_pad,
+            &old.borders_pads,
+            &old.half_focalized_tiles_pad,
+            &old.half_focalized_borders_pads,
+            &old.focalized_pads,
+            &old.scale_with_dpi,
+            &old.layout_strategy,
+        );
+        let monitors = (monitors_config, monitor_aliases) != (&old.monitors_config, &old.monitor_aliases);
+
+        let sections = [
+            (ConfigSection::General, general),
+            (ConfigSection::Animations, *animations != old.animations),
+            (
+                ConfigSection::FloatingWins,
+                *floating_wins_config != old.floating_wins_config,
+            ),
+            (ConfigSection::Rules, rules),
+            (ConfigSection::Layout, layout),
+            (ConfigSection::Monitors, monitors),
+            (ConfigSection::Workspaces, *workspaces_config != old.workspaces_config),
+            (ConfigSection::Keybindings, *keybindings != old.modules.keybindings),
+            (ConfigSection::Overlays, *overlays != old.modules.overlays),
+        ];
+
+        ConfigChanges(
+            sections
+                .into_iter()
+                .filter(|(_, changed)| *changed)
+                .map(|(s, _)| s)
+                .collect(),
+        )
+    }
+
+    pub fn contains(&self, section: ConfigSection) -> bool {
+        self.0.contains(&section)
+    }
+
+    /// Returns true if at least one of the given sections changed
+    pub fn any(&self, sections: &[ConfigSection]) -> bool {
+        sections.iter().any(|s| self.0.contains(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigChanges;
+    use super::ConfigSection;
+    use crate::app::configs::AppConfig;
+
+    fn sections(changes: &ConfigChanges) -> Vec<ConfigSection> {
+        let mut sections: Vec<ConfigSection> = changes.0.iter().copied().collect();
+        sections.sort_by_key(|s| *s as u8);
+        sections
+    }
+
+    #[test]
+    fn test_diff_same_config() {
+        let config = AppConfig::default();
+        assert_eq!(ConfigChanges::diff(&config, &config.clone()), ConfigChanges::default());
+    }
+
+    #[test]
+    fn test_diff_flags_changed_sections() {
+        let old = AppConfig::default();
+
+        let mut new = old.clone();
+        new.events_coalescing += 10;
+        assert_eq!(sections(&ConfigChanges::diff(&old, &new)), [ConfigSection::General]);
+
+        let mut new = old.clone();
+        new.animations.framerate += 1;
+        assert_eq!(sections(&ConfigChanges::diff(&old, &new)), [ConfigSection::Animations]);
+
+        let mut new = old.clone();
+        new.title_change_filters.push(old.ignore_filter.clone());
+        assert_eq!(sections(&ConfigChanges::diff(&old, &new)), [ConfigSection::Rules]);
+
+        let mut new = old.clone();
+        new.tiles_pad += 1;
+        assert_eq!(sections(&ConfigChanges::diff(&old, &new)), [ConfigSection::Layout]);
+
+        let mut new = old.clone();
+        new.monitor_aliases.insert("main".to_string(), "primary".to_string());
+        assert_eq!(sections(&ConfigChanges::diff(&old, &new)), [ConfigSection::Monitors]);
+
+        let mut new = old.clone();
+        new.modules.keybindings.enabled = !old.modules.keybindings.enabled;
+        new.modules.overlays.enabled = !old.modules.overlays.enabled;
+        assert_eq!(
+            sections(&ConfigChanges::diff(&old, &new)),
+            [ConfigSection::Keybindings, ConfigSection::Overlays]
+        );
+    }
+
+    #[test]
+    fn test_diff_restarted_modules() {
+        // INFO: the events monitor restarts on general and rules changes, the other modules on their own section
+        let events_monitor = [ConfigSection::General, ConfigSection::Rules];
+        let old = AppConfig::default();
+
+        let mut new = old.clone();
+        new.title_change_filters.push(old.ignore_filter.clone());
+        let changes = ConfigChanges::diff(&old, &new);
+        assert!(changes.any(&events_monitor));
+        assert!(!changes.contains(ConfigSection::Keybindings));
+        assert!(!changes.contains(ConfigSection::Overlays));
+
+        let mut new = old.clone();
+        new.tiles_pad += 1;
+        new.modules.overlays.enabled = !old.modules.overlays.enabled;
+        let changes = ConfigChanges::diff(&old, &new);
+        assert!(!changes.any(&events_monitor));
+        assert!(!changes.contains(ConfigSection::Keybindings));
+        assert!(changes.contains(ConfigSection::Overlays));
+    }
+}
@@ -4,6 +4,8 @@ use serde::Deserializer;
 
 use crate::app::structs::paddings::Paddings;
 
+pub const TILING_STRATEGIES: [&str; 5] = ["golden_ratio", "horizontal", "vertical", "twostep", "squared"];
+
 pub fn to_u8_max<'de, const MAX: u8, D>(deserializer: D) -> Result<u8, D::Error>
 where
     D: Deserializer<'de>,
@@ -37,6 +39,36 @@ where
     }
 }
 
+pub fn to_opt_u32_minmax<'de, const MIN: u32, const MAX: u32, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let v: u32 = match Option::deserialize(deserializer)? {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    match v >= MIN && v <= MAX {
+        true => Ok(Some(v)),
+        false => Err(D::Error::custom(format!(
+            "value must be between {MIN} and {MAX} (inclusive)"
+        ))),
+    }
+}
+
+pub fn to_u16_max<'de, const MAX: u16, D>(deserializer: D) -> Result<u16, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let v: u16 = u16::deserialize(deserializer)?;
+    match v <= MAX {
+        true => Ok(v),
+        false => Err(D::Error::custom(format!(
+            "value must be between 0 and {MAX} (inclusive)"
+        ))),
+    }
+}
+
 pub fn to_opt_u8_max<'de, const MAX: u8, D>(deserializer: D) -> Result<Option<u8>, D::Error>
 where
     D: Deserializer<'de>,
@@ -69,7 +101,7 @@ where
     get_tiling_strategy(&s).map_err(D::Error::custom)
 }
 
-pub fn to_opt_paddings_max<'de, const MAX: u8, D>(deserializer: D) -> Result<Option<Paddings>, D::Error>
+pub fn to_opt_paddings_max<'de, const MAX: i16, D>(deserializer: D) -> Result<Option<Paddings>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -77,7 +109,7 @@ where
     get_paddings(&value, MAX).map(Some).map_err(D::Error::custom)
 }
 
-pub fn to_paddings_max<'de, const MAX: u8, D>(deserializer: D) -> Result<Paddings, D::Error>
+pub fn to_paddings_max<'de, const MAX: i16, D>(deserializer: D) -> Result<Paddings, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -85,24 +117,22 @@ where
     get_paddings(&value, MAX).map_err(D::Error::custom)
 }
 
-fn get_paddings(value: &toml::Value, max: u8) -> Result<Paddings, String> {
+fn get_paddings(value: &toml::Value, max: i16) -> Result<Paddings, String> {
+    let in_range = |n: i64| i16::try_from(n).ok().filter(|n| (0..=max).contains(n));
     match value {
         toml::Value::Integer(n) => {
-            if *n < 0 || *n > max as i64 {
-                return Err(format!("value must be between 0 and {max} (inclusive)"));
-            }
-            Ok(Paddings::full(*n as u8))
+            let n = in_range(*n).ok_or(format!("value must be between 0 and {max} (inclusive)"))?;
+            Ok(Paddings::full(n))
         }
 
         toml::Value::Array(arr) => {
-            let values: Vec<u8> = arr.iter().filter_map(|x| x.as_integer().map(|x| x as u8)).collect();
-            for v in arr.iter() {
-                let v = v.as_integer().ok_or("Invalid value".to_string())?;
-                let v = u8::try_from(v).map_err(|e| e.to_string())?;
-                if v > max {
-                    return Err(format!("values must be between 0 and {max} (inclusive)"));
-                }
-            }
+            let values = arr
+                .iter()
+                .map(|v| {
+                    let v = v.as_integer().ok_or("Invalid value".to_string())?;
+                    in_range(v).ok_or(format!("values must be between 0 and {max} (inclusive)"))
+                })
+                .collect::<Result<Vec<i16>, String>>()?;
 
             match values.len() {
                 2 => Ok(Paddings::new(values[0], values[1], values[0], values[1])),
@@ -152,13 +182,36 @@ where
 }
 
 fn get_tiling_strategy(s: &str) -> Result<String, String> {
-    let valid = ["golden_ratio", "horizontal", "vertical", "twostep", "squared"];
-    match valid.contains(&s.to_lowercase().as_str()) {
+    match TILING_STRATEGIES.contains(&s.to_lowercase().as_str()) {
         true => Ok(s.to_lowercase()),
         false => Err(format!(
             "Invalid tiling strategy: {}, valid options are {}",
             s,
-            valid.join(", ")
+            TILING_STRATEGIES.join(", ")
+        )),
+    }
+}
+
+pub fn to_resolutions<'de, D>(deserializer: D) -> Result<Vec<(i32, i32)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values: Vec<String> = Vec::deserialize(deserializer)?;
+    values
+        .iter()
+        .map(|v| get_resolution(v).map_err(D::Error::custom))
+        .collect()
+}
+
+fn get_resolution(s: &str) -> Result<(i32, i32), String> {
+    let size = s
+        .to_lowercase()
+        .split_once('x')
+        .map(|(w, h)| (w.trim().parse::<i32>(), h.trim().parse::<i32>()));
+    match size {
+        Some((Ok(w), Ok(h))) if w > 0 && h > 0 => Ok((w, h)),
+        _ => Err(format!(
+            "Invalid resolution: {s}, expected <width>x<height> (e.g. 1920x1080)"
         )),
     }
 }
@@ -1,14 +1,36 @@
 use super::general::FloatingWinsSizeStrategyLabel;
+use crate::app::configs::schemas;
 use crate::app::structs::win_matcher::WinMatcher;
+use schemars::JsonSchema;
 use serde::de;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+/// Core configuration settings
+#[derive(Deserialize, Serialize, JsonSchema, Default, Debug, Clone)]
 pub struct Core {
+    /// Rules to exclude windows from being managed
     #[serde(default)]
+    #[schemars(example = serde_json::json!([
+        { "exename": "OpenWith.exe" },
+        { "exename": "StartMenuExperienceHost.exe" },
+        { "classname": "OperationStatusWindow" },
+        { "title": "/[Pp]icture.in.[Pp]icture/", "classname": "/Chrome_WidgetWin_1|MozillaDialogClass/" },
+    ]))]
+    #[schemars(extend("x-item-comments" = {
+        "0": ["\"Open with\" dialog"],
+        "1": ["Manages the start menu"],
+        "2": ["Explorer operation status"],
+        "3": ["PIP Firefox/Chrome"]
+    }))]
+    #[serde(deserialize_with = "deserialize_ignore_rules")]
     pub ignore_rules: Vec<WinMatcher>,
+    /// Rules to control the behavior of specific windows
     #[serde(default, deserialize_with = "deserialize_rules")]
+    #[schemars(with = "Vec<WindowRuleExternal>")]
+    #[schemars(extend("x-commented-example" = r#"rules = [
+    { filter = { classname = "#32770" }, behavior.float = { size = "preserve" } },
+]"#))]
     pub rules: Vec<WindowRule>,
 }
 
@@ -17,35 +39,76 @@ pub struct Core {
 pub struct WindowRule {
     pub filter: WinMatcher,
     pub behavior: WindowBehavior,
+    #[serde(default)]
+    pub reevaluate_on_title_change: bool,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub stop: bool,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum WindowBehaviorRaw {
-    Shortcut(String),
+    Shortcut(#[schemars(schema_with = "schemas::behavior_shortcut")] String),
     Full(WindowBehavior),
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
 #[serde(deny_unknown_fields, rename_all = "lowercase")]
 pub enum WindowBehavior {
+    /// Don't manage the window
     Ignore,
+    /// Delay the insertion of the window
     DelayInsert {
+        /// Delay (in milliseconds)
+        #[schemars(range(min = 10, max = 20000))]
         delay: u32,
+        /// If true, the window is inserted once its area has not changed for `delay` milliseconds
+        #[serde(default)]
+        until_stable: bool,
     },
+    /// Make the window floating
     Float {
+        /// If true, the window is always on top of other windows
         topmost: Option<bool>,
+        /// If true, the window is centered on the monitor when released
         centered: Option<bool>,
         size: Option<FloatingWinsSizeStrategyLabel>,
+        /// Ratio of the window size relative to the monitor, as [width, height]
+        #[schemars(schema_with = "schemas::size_ratio")]
         size_ratio: Option<(f32, f32)>,
+        /// Fixed size of the window (in pixels), as [width, height]
+        #[schemars(schema_with = "schemas::size_fixed")]
         size_fixed: Option<(u16, u16)>,
+        /// If true, the window is visible on all the workspaces instead of the one it was floated in
+        #[serde(default)]
+        global: bool,
     },
+    /// Insert the window in the given monitor or workspace
     Insert {
+        /// Monitor to insert the window in
         monitor: Option<String>,
+        /// Workspace to insert the window in
         workspace: Option<String>,
+        /// If false, the corresponding workspace will be focused
         #[serde(default)]
         silent: bool,
     },
+    /// Focalize the window when it is opened
+    Focalize,
+    /// Half-focalize the window when it is opened
+    HalfFocalize,
+    /// Share of the split taken by the window when it is inserted in the layout
+    Ratio(#[schemars(range(min = 0.1, max = 0.9))] f32),
+    /// Padding of the window in the layout (in pixels), overrides `layout.paddings.tiles`
+    Padding(#[schemars(range(max = 140))] u8),
+    /// If false, no overlay is drawn around the window
+    Overlay(bool),
+    /// Minimize the window when it is opened
+    StartMinimized,
+    /// If the window is tiled, it is always on top of other windows
+    Topmost,
 }
 
 impl TryFrom<WindowBehaviorRaw> for WindowBehavior {
@@ -54,14 +117,22 @@ impl TryFrom<WindowBehaviorRaw> for WindowBehavior {
         match value {
             WindowBehaviorRaw::Shortcut(s) => match s.as_str() {
                 "ignore" => Ok(WindowBehavior::Ignore),
-                "delayinsert" => Ok(WindowBehavior::DelayInsert { delay: 500 }),
+                "delayinsert" => Ok(WindowBehavior::DelayInsert {
+                    delay: 500,
+                    until_stable: false,
+                }),
                 "float" => Ok(WindowBehavior::Float {
                     topmost: None,
                     size: None,
                     centered: None,
                     size_ratio: None,
                     size_fixed: None,
+                    global: false,
                 }),
+                "focalize" => Ok(WindowBehavior::Focalize),
+                "halffocalize" => Ok(WindowBehavior::HalfFocalize),
+                "startminimized" => Ok(WindowBehavior::StartMinimized),
+                "topmost" => Ok(WindowBehavior::Topmost),
                 _ => Err(format!("Unknown behavior: {}", s)),
             },
             WindowBehaviorRaw::Full(w) => match w {
@@ -79,9 +150,15 @@ impl TryFrom<WindowBehaviorRaw> for WindowBehavior {
                 WindowBehavior::Insert { monitor, workspace, .. } if monitor.is_none() && workspace.is_none() => {
                     Err("A monitor or a workspace must be specified".to_string())
                 }
-                WindowBehavior::DelayInsert { delay } if !(10..=20000).contains(&delay) => {
+                WindowBehavior::DelayInsert { delay, .. } if !(10..=20000).contains(&delay) => {
                     Err("Delay must be between 10 and 20000 milliseconds".to_string())
                 }
+                WindowBehavior::Ratio(ratio) if !(0.1..=0.9).contains(&ratio) => {
+                    Err("Ratio must be between 0.1 and 0.9".to_string())
+                }
+                WindowBehavior::Padding(padding) if padding > 140 => {
+                    Err("Padding must be between 0 and 140 pixels".to_string())
+                }
                 _ => Ok(w),
             },
         }
@@ -90,11 +167,19 @@ impl TryFrom<WindowBehaviorRaw> for WindowBehavior {
 
 impl WindowRule {
     pub fn new(filter: WinMatcher, behavior: WindowBehavior) -> Self {
-        WindowRule { filter, behavior }
+        WindowRule {
+            filter,
+            behavior,
+            reevaluate_on_title_change: false,
+            priority: 0,
+            stop: false,
+        }
     }
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+/// Rule composed by a `filter`, used to match windows, and a `behavior` (or `behaviors`)
+/// applied to the matched windows
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
 #[serde(deny_unknown_fields)]
 struct WindowRuleExternal {
     pub filter: WinMatcher,
@@ -103,42 +188,90 @@ struct WindowRuleExternal {
         skip_serializing_if = "Option::is_none",
         deserialize_with = "deserialize_behavior"
     )]
+    #[schemars(with = "Option<WindowBehaviorRaw>")]
     pub behavior: Option<WindowBehavior>,
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
         deserialize_with = "deserialize_behaviors"
     )]
+    #[schemars(with = "Option<Vec<WindowBehaviorRaw>>")]
     pub behaviors: Option<Vec<WindowBehavior>>,
+    /// If true, the rule is evaluated again each time the title of a window changes. When the
+    /// window starts or stops matching the rule, all the rules are applied to it again.
+    #[serde(default)]
+    pub reevaluate_on_title_change: bool,
+    /// Rules with a higher priority override the options set by the ones with a lower priority.
+    /// Rules with the same priority are evaluated in the order they are defined, so the last one wins.
+    #[serde(default)]
+    pub priority: i32,
+    /// If true and the rule matches a window, the rules evaluated before it (i.e. with a lower
+    /// priority or defined before it) are not applied. Not allowed on `ignore` and `delayinsert` rules.
+    #[serde(default)]
+    pub stop: bool,
 }
 
 fn deserialize_rules<'de, D>(deserializer: D) -> Result<Vec<WindowRule>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
-    let value: Vec<WindowRuleExternal> = serde::Deserialize::deserialize(deserializer)?;
+    let value: Vec<toml::Value> = serde::Deserialize::deserialize(deserializer)?;
     let mut rules_config: Vec<WindowRule> = Vec::new();
-    for rule in &value {
+    for (i, rule) in value.into_iter().enumerate() {
+        let rule =
+            WindowRuleExternal::deserialize(rule).map_err(|e| de::Error::custom(format!("Rule #{}: {}", i + 1, e)))?;
         if rule.behavior.is_none() && rule.behaviors.is_none() {
-            return Err(de::Error::custom("A rule must have at least one action"));
+            return Err(de::Error::custom(format!(
+                "Rule #{}: a rule must have at least one action",
+                i + 1
+            )));
         }
         if rule.behavior.is_some() && rule.behaviors.is_some() {
-            return Err(de::Error::custom("A rule cannot have both action and actions fields"));
+            return Err(de::Error::custom(format!(
+                "Rule #{}: a rule cannot have both action and actions fields",
+                i + 1
+            )));
         }
 
-        if let Some(behaviors) = rule.behaviors.as_ref() {
-            let new_rules = behaviors
-                .iter()
-                .map(|b| WindowRule::new(rule.filter.clone(), b.clone()));
-            rules_config.extend(new_rules);
-        } else if let Some(behavior) = rule.behavior.as_ref() {
-            rules_config.push(WindowRule::new(rule.filter.clone(), behavior.clone()));
+        // INFO: each behavior becomes a rule. Only the first one applied when a window is added
+        // (i.e. not `ignore` or `delayinsert`) stops the evaluation, so that the others are applied.
+        let behaviors = rule.behaviors.clone().or(rule.behavior.clone().map(|b| vec![b]));
+        let behaviors = behaviors.unwrap_or_default();
+        let first = behaviors
+            .iter()
+            .position(|b| !matches!(b, WindowBehavior::Ignore | WindowBehavior::DelayInsert { .. }));
+        if rule.stop && first.is_none() {
+            return Err(de::Error::custom(format!(
+                "Rule #{}: `stop` can't be used with only the ignore and delayinsert actions",
+                i + 1
+            )));
         }
+        rules_config.extend(behaviors.into_iter().enumerate().map(|(i, behavior)| WindowRule {
+            filter: rule.filter.clone(),
+            behavior,
+            reevaluate_on_title_change: rule.reevaluate_on_title_change,
+            priority: rule.priority,
+            stop: rule.stop && first == Some(i),
+        }));
     }
 
     Ok(rules_config)
 }
 
+fn deserialize_ignore_rules<'de, D>(deserializer: D) -> Result<Vec<WinMatcher>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Vec<toml::Value> = serde::Deserialize::deserialize(deserializer)?;
+    value
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| {
+            WinMatcher::deserialize(v).map_err(|e| de::Error::custom(format!("Ignore rule #{}: {}", i + 1, e)))
+        })
+        .collect()
+}
+
 fn deserialize_behavior<'de, D>(deserializer: D) -> Result<Option<WindowBehavior>, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -1,39 +1,126 @@
 use crate::app::configs::deserializers;
+use crate::app::configs::schemas;
 use crate::modules::tiles_manager::lib::window_animation_player::WindowAnimation;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+/// General configuration options
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone)]
 #[serde(default, deny_unknown_fields)]
 pub struct General {
+    /// If true, navigation will prioritize the most recently focused window in the given direction
+    #[schemars(example = true)]
     pub history_based_navigation: bool,
+    /// Moves the mouse cursor to the center of the focused window when focus is changed
     pub move_cursor_on_focus: bool,
+    /// The `focus` action will also consider empty monitors
     pub allow_focus_on_empty_monitor: bool,
+    /// Reloads the configurations on config file changes
     pub auto_reload_configs: bool,
+    /// Prevents maximized windows from being managed
     pub detect_maximized_windows: bool,
+    /// If true, moving the window to a new monitor inserts it rather than swapping
     pub insert_in_monitor: bool,
+    /// If true, free moving the window to a new monitor is enabled by default
     pub free_move_in_monitor: bool,
     pub animations: AnimationsConfig,
     pub floating_wins: FloatingWinsConfig,
+    /// Active workspace on startup
+    #[schemars(schema_with = "schemas::workspace_id")]
     pub default_workspace: String,
+    /// Monitor receiving the windows of a disconnected monitor, as a monitor id, alias or positional selector
+    pub fallback_monitor: String,
+    /// Maximum time window (in milliseconds) in which bursts of window events are coalesced (0 disables it)
+    #[serde(deserialize_with = "deserializers::to_u32_minmax::<0,200,_>")]
+    #[schemars(range(min = 0, max = 200))]
+    pub events_coalescing: u32,
+    /// Maximum time (in milliseconds) spent handling the pending commands before updating the layout, 0 for no limit
+    #[serde(deserialize_with = "deserializers::to_u32_minmax::<0,1000,_>")]
+    #[schemars(range(min = 0, max = 1000))]
+    pub max_batch_latency: u32,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+/// Settings for the windows animations
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone)]
 #[serde(default, deny_unknown_fields)]
 pub struct AnimationsConfig {
+    /// Enables or disables the animations
     pub enabled: bool,
 
+    /// Duration of the animations (in milliseconds)
     #[serde(deserialize_with = "deserializers::to_u32_minmax::<100,10000,_>")]
+    #[schemars(range(min = 100, max = 10000))]
     pub duration: u32,
 
+    /// Framerate of the animations
     #[serde(deserialize_with = "deserializers::to_u8_minmax::<10,240,_>")]
+    #[schemars(range(min = 10, max = 240))]
     pub framerate: u8,
 
+    /// Type of animation, can be "linear", any of the easing functions from https://easings.net/ (in snake_case), "cubic_bezier(x1, y1, x2, y2)" or "spring(stiffness, damping)"
     #[serde(rename = "type")]
+    #[schemars(example = WindowAnimation::EaseOutBack)]
     pub animation_type: WindowAnimation,
+
+    /// Transition of the windows when switching workspace, using the settings of `workspace_switch`
+    pub workspace_transition: WorkspaceTransition,
+
+    /// Overrides the animations of the windows being opened or restored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open: Option<AnimationOverride>,
+
+    /// Overrides the animations of the windows left after a window is closed or minimized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close: Option<AnimationOverride>,
+
+    /// Overrides the animations of the windows being moved or swapped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap: Option<AnimationOverride>,
+
+    /// Overrides the animations of the windows being resized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resize: Option<AnimationOverride>,
+
+    /// Overrides the animations played when switching workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_switch: Option<AnimationOverride>,
+
+    /// Overrides the animations of the windows being focalized or half-focalized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focalize: Option<AnimationOverride>,
+}
+
+/// Settings overriding the ones of the animations for a kind of change
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnimationOverride {
+    /// Duration of the animations (in milliseconds)
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserializers::to_opt_u32_minmax::<100,10000,_>"
+    )]
+    #[schemars(range(min = 100, max = 10000))]
+    pub duration: Option<u32>,
+
+    /// Type of animation
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub animation_type: Option<WindowAnimation>,
+}
+
+/// How the windows enter and leave the monitor when switching workspace
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum WorkspaceTransition {
+    #[default]
+    None,
+    SlideHorizontal,
+    SlideVertical,
+    Fade,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+/// How floating windows are resized
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, Copy, PartialEq)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum FloatingWinsSizeStrategyLabel {
     Preserve,
@@ -41,15 +128,22 @@ pub enum FloatingWinsSizeStrategyLabel {
     Relative,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+/// Settings for the floating windows
+#[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, Copy, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct FloatingWinsConfig {
+    /// If true, floating windows are always on top of other windows
     pub topmost: bool,
+    /// If true, floating windows are centered on the monitor when released
     pub centered: bool,
     pub size: FloatingWinsSizeStrategyLabel,
+    /// Ratio of the floating windows size relative to the monitor, as [width, height]
     #[serde(deserialize_with = "deserializers::deserialize_size_ratio")]
+    #[schemars(schema_with = "schemas::size_ratio")]
     pub size_ratio: (f32, f32),
+    /// Fixed size of the floating windows (in pixels), as [width, height]
     #[serde(deserialize_with = "deserializers::deserialize_size_fixed")]
+    #[schemars(schema_with = "schemas::size_fixed")]
     pub size_fixed: (u16, u16),
 }
 
@@ -66,6 +160,9 @@ impl Default for General {
             animations: AnimationsConfig::default(),
             floating_wins: FloatingWinsConfig::default(),
             default_workspace: "1".into(),
+            fallback_monitor: "primary".into(),
+            events_coalescing: 0,
+            max_batch_latency: 0,
         }
     }
 }
@@ -77,6 +174,13 @@ impl Default for AnimationsConfig {
             duration: 300,
             framerate: 60,
             animation_type: WindowAnimation::default(),
+            workspace_transition: WorkspaceTransition::None,
+            open: None,
+            close: None,
+            swap: None,
+            resize: None,
+            workspace_switch: None,
+            focalize: None,
         }
     }
 }
@@ -1,27 +1,45 @@
 use crate::app::area_tree::layout_strategy;
 use crate::app::configs::deserializers;
+use crate::app::configs::schemas;
 use crate::app::structs::paddings::Paddings;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+/// Configuration settings for the tiling layout
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
 #[serde(default, deny_unknown_fields)]
 pub struct Layout {
+    /// Tiling strategy
     #[serde(deserialize_with = "deserializers::to_tiling_strategy")]
+    #[schemars(schema_with = "schemas::tiling_strategy")]
     pub tiling_strategy: String,
+    /// Paddings of the windows in the layout
+    #[schemars(example = PaddingsConfigs { tiles: 8, borders: Paddings::full(16) })]
     pub paddings: PaddingsConfigs,
+    /// Paddings of the half-focalized windows
     pub half_focalized_paddings: PaddingsConfigs,
+    /// Padding between the monitor borders and the focalized window (in pixels), as a single value,
+    /// [vertical, horizontal] or [top, right, bottom, left]
     #[serde(deserialize_with = "deserializers::to_paddings_max::<140,_>")]
+    #[schemars(schema_with = "schemas::paddings::<140>")]
     pub focalized_padding: Paddings,
+    /// Scales the paddings by the DPI of the monitor (e.g. doubled with a 200% scaling)
+    pub scale_with_dpi: bool,
     pub strategy: StrategyConfigs,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
 #[serde(default, deny_unknown_fields)]
 pub struct PaddingsConfigs {
+    /// Padding between the windows (in pixels)
     #[serde(deserialize_with = "deserializers::to_u8_max::<140,_>")]
+    #[schemars(range(max = 140))]
     pub tiles: u8,
+    /// Padding between the monitor borders and the windows (in pixels), as a single value,
+    /// [vertical, horizontal] or [top, right, bottom, left]
     #[serde(deserialize_with = "deserializers::to_paddings_max::<140,_>")]
+    #[schemars(schema_with = "schemas::paddings::<140>")]
     pub borders: Paddings,
 }
 
@@ -34,7 +52,8 @@ impl Default for PaddingsConfigs {
     }
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+/// Settings of the single tiling strategies
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct StrategyConfigs {
     pub golden_ratio: layout_strategy::golden_ratio::GoldenRatio,
@@ -51,6 +70,7 @@ impl Default for Layout {
             paddings: PaddingsConfigs::default(),
             half_focalized_paddings: PaddingsConfigs::default(),
             focalized_padding: Paddings::full(8),
+            scale_with_dpi: false,
             strategy: StrategyConfigs::default(),
         }
     }
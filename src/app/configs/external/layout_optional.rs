@@ -1,24 +1,41 @@
 use crate::app::configs::deserializers;
+use crate::app::configs::schemas;
 use crate::app::structs::paddings::Paddings;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+/// Layout settings, overriding the `layout` ones
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default, PartialEq)]
 #[serde(deny_unknown_fields, default)]
 pub struct LayoutOptional {
+    /// Tiling strategy
     #[serde(deserialize_with = "deserializers::to_opt_tiling_strategy")]
+    #[schemars(schema_with = "schemas::tiling_strategy")]
     pub tiling_strategy: Option<String>,
+    /// Paddings of the windows in the layout
     pub paddings: PaddingsOptionalConfigs,
+    /// Paddings of the half-focalized windows
     pub half_focalized_paddings: PaddingsOptionalConfigs,
+    /// Padding between the monitor borders and the focalized window (in pixels), as a single value,
+    /// [vertical, horizontal] or [top, right, bottom, left]
     #[serde(deserialize_with = "deserializers::to_opt_paddings_max::<140,_>")]
+    #[schemars(schema_with = "schemas::paddings::<140>")]
     pub focalized_padding: Option<Paddings>,
+    /// Scales the paddings by the DPI of the monitor (e.g. doubled with a 200% scaling)
+    pub scale_with_dpi: Option<bool>,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default, PartialEq)]
 #[serde(deny_unknown_fields, default)]
 pub struct PaddingsOptionalConfigs {
+    /// Padding between the windows (in pixels)
     #[serde(deserialize_with = "deserializers::to_opt_u8_max::<140,_>")]
+    #[schemars(range(max = 140))]
     pub tiles: Option<u8>,
+    /// Padding between the monitor borders and the windows (in pixels), as a single value,
+    /// [vertical, horizontal] or [top, right, bottom, left]
     #[serde(deserialize_with = "deserializers::to_opt_paddings_max::<140,_>")]
+    #[schemars(schema_with = "schemas::paddings::<140>")]
     pub borders: Option<Paddings>,
 }
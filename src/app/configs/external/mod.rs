@@ -3,6 +3,7 @@ pub(super) mod general;
 pub(super) mod layout;
 pub(super) mod layout_optional;
 pub(super) mod monitors;
+pub(super) mod profiles;
 pub(super) mod workspaces;
 
 use super::modules::Modules;
@@ -10,17 +11,47 @@ use core::Core;
 use general::General;
 use layout::Layout;
 use monitors::MonitorConfig;
+use profiles::Profile;
+use schemars::JsonSchema;
+use schemars::Schema;
 use serde::Deserialize;
 use std::collections::HashMap;
 use workspaces::WorkspaceConfig;
 
-#[derive(Deserialize, Debug, Clone, Default)]
+/// Mondrian configuration file
+#[derive(Deserialize, JsonSchema, Debug, Clone, Default)]
 #[serde(default, deny_unknown_fields)]
+#[schemars(title = "Mondrian", transform = add_loader_keys)]
 pub(super) struct AppConfigExternal {
     pub general: General,
     pub layout: Layout,
     pub core: Core,
     pub modules: Modules,
+    /// Monitors specific settings, by monitor name. Monitor names can be monitor ids, aliases or
+    /// positional selectors ("primary", "leftmost", "rightmost", "index:<N>", "portrait" or "landscape")
+    #[schemars(extend("x-commented-example" = "[monitors.\"MONITOR1\"]\nlayout.tiling_strategy = \"horizontal\""))]
     pub monitors: HashMap<String, MonitorConfig>,
+    /// Monitor aliases, mapping a friendly name to a monitor id or a positional selector
+    pub monitor_aliases: HashMap<String, String>,
+    /// Workspaces specific settings, by workspace id
+    #[schemars(extend("x-commented-example" = "[workspaces.\"1\"]\nlayout.tiling_strategy = \"horizontal\""))]
     pub workspaces: HashMap<String, WorkspaceConfig>,
+    /// Configuration profiles, by profile name
+    pub profiles: HashMap<String, Profile>,
+}
+
+/// Adds the keys handled by the loader (see [`super::loader`]) before the deserialization
+fn add_loader_keys(schema: &mut Schema) {
+    let Some(props) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) else {
+        return;
+    };
+
+    let include = serde_json::json!({
+        "type": "array",
+        "description": "Configuration files to include, relative to this file. The settings of this file override the included ones",
+        "items": { "type": "string" }
+    });
+    let schema_url = serde_json::json!({ "type": "string", "description": "JSON schema of the file" });
+    props.insert("include".to_string(), include);
+    props.insert("$schema".to_string(), schema_url);
 }
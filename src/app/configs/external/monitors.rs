@@ -1,11 +1,19 @@
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
 use super::layout_optional::LayoutOptional;
+use crate::app::configs::schemas;
+use crate::app::structs::reserved_area::ReservedArea;
 
-#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+/// Monitor configuration settings
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default, PartialEq)]
 #[serde(deny_unknown_fields, default)]
 pub struct MonitorConfig {
+    /// Active workspace of the monitor on startup
+    #[schemars(schema_with = "schemas::workspace_id")]
     pub default_workspace: Option<String>,
     pub layout: LayoutOptional,
+    /// Space reserved to external bars (in pixels), excluded from the tiling area
+    pub reserved: ReservedArea,
 }
@@ -0,0 +1,37 @@
+use super::layout_optional::LayoutOptional;
+use super::monitors::MonitorConfig;
+use super::workspaces::WorkspaceConfig;
+use crate::app::configs::deserializers;
+use crate::app::configs::schemas;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Configuration profile, overriding the layout, monitors and workspaces settings
+#[derive(Deserialize, JsonSchema, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// Monitors setup that activates the profile automatically. Profiles without it can only be
+    /// activated with the `set-profile` action
+    pub monitors_set: Option<MonitorSet>,
+    pub layout: LayoutOptional,
+    /// Monitors specific settings, replacing the `monitors` ones with the same name
+    pub monitors: HashMap<String, MonitorConfig>,
+    /// Workspaces specific settings, replacing the `workspaces` ones with the same id
+    pub workspaces: HashMap<String, WorkspaceConfig>,
+}
+
+/// Connected monitors. All the specified conditions must be satisfied
+#[derive(Deserialize, JsonSchema, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct MonitorSet {
+    /// Names of the monitors that must be connected
+    pub ids: Vec<String>,
+    /// Number of connected monitors
+    #[schemars(range(min = 1))]
+    pub count: Option<u8>,
+    /// Resolutions of the monitors that must be connected (e.g. "1920x1080")
+    #[serde(deserialize_with = "deserializers::to_resolutions")]
+    #[schemars(schema_with = "schemas::resolutions")]
+    pub resolutions: Vec<(i32, i32)>,
+}
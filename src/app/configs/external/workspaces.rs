@@ -1,12 +1,16 @@
 use super::layout_optional::LayoutOptional;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
 
-#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+/// Workspace configuration settings
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, Default, PartialEq)]
 #[serde(deny_unknown_fields, default)]
 pub struct WorkspaceConfig {
+    /// Monitor to bind the workspace to
     pub bind_to_monitor: Option<String>,
     pub layout: LayoutOptional,
+    /// Layout settings of the workspace for specific monitors
     pub monitors: HashMap<String, LayoutOptional>,
 }
@@ -12,6 +12,8 @@ pub struct FloatingWinsConfig {
     pub topmost: bool,
     pub centered: bool,
     pub strategy: FloatingWinsSizeStrategy,
+    /// If true, the window is visible on all the workspaces
+    pub global: bool,
 }
 
 impl Default for FloatingWinsConfig {
@@ -20,6 +22,7 @@ impl Default for FloatingWinsConfig {
             topmost: true,
             centered: true,
             strategy: FloatingWinsSizeStrategy::Relative { w: 0.5, h: 0.5 },
+            global: false,
         }
     }
 }
@@ -40,6 +43,7 @@ impl From<external::general::FloatingWinsConfig> for FloatingWinsConfig {
                     h: value.size_ratio.1,
                 },
             },
+            global: false,
         }
     }
 }
@@ -0,0 +1,274 @@
+use super::external::AppConfigExternal;
+use schemars::generate::SchemaSettings;
+use serde_json::Map;
+use serde_json::Value;
+
+const SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/policastro/mondrian/refs/heads/main/assets/configs/mondrian.schema.json";
+
+/// Maximum number of enum values listed in the comments of the default configuration
+const MAX_ENUM_HINT: usize = 10;
+
+/// Schema key of the comments written before the items of an array, by item index
+const ITEM_COMMENTS_KEY: &str = "x-item-comments";
+
+/// Schema key of the (TOML) example written as a comment when the property has no default value
+const COMMENTED_EXAMPLE_KEY: &str = "x-commented-example";
+
+/// Returns the JSON schema of the configuration file
+pub fn json_schema() -> String {
+    let schema = serde_json::to_string_pretty(&schema()).expect("Failed to serialize the schema");
+    format!("{schema}\n")
+}
+
+/// Returns the default configuration file (TOML). Each value is taken from the first example of
+/// the corresponding schema property, falling back to its default.
+pub fn default_config() -> String {
+    let schema = schema();
+    let value = node_value(&schema, &schema);
+    let mut config = format!("#:schema {SCHEMA_URL}\n");
+    write_table(&mut config, &schema, &schema, &value, &[]);
+    config
+}
+
+fn schema() -> Value {
+    let generator = SchemaSettings::draft07().into_generator();
+    generator.into_root_schema_for::<AppConfigExternal>().to_value()
+}
+
+/// Follows the `$ref` of the node, if any (including the ones wrapped in a single `allOf`)
+fn resolve<'a>(root: &'a Value, node: &'a Value) -> &'a Value {
+    if let Some(reference) = node.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/definitions/");
+        return resolve(root, &root["definitions"][name]);
+    }
+
+    match node.get("allOf").and_then(Value::as_array) {
+        Some(all) if all.len() == 1 => resolve(root, &all[0]),
+        _ => node,
+    }
+}
+
+fn get<'a>(root: &'a Value, node: &'a Value, key: &str) -> Option<&'a Value> {
+    node.get(key).or_else(|| resolve(root, node).get(key))
+}
+
+fn properties<'a>(root: &'a Value, node: &'a Value) -> Option<&'a Map<String, Value>> {
+    resolve(root, node).get("properties").and_then(Value::as_object)
+}
+
+fn node_value(root: &Value, node: &Value) -> Value {
+    let mut value = get(root, node, "default").cloned().unwrap_or(Value::Null);
+
+    if let Some(props) = properties(root, node) {
+        let children = props
+            .iter()
+            .map(|(k, p)| (k.clone(), node_value(root, p)))
+            .filter(|(_, v)| !v.is_null())
+            .collect();
+        merge(&mut value, Value::Object(children));
+    }
+
+    if let Some(example) = get(root, node, "examples").and_then(|e| e.get(0)) {
+        merge(&mut value, example.clone());
+    }
+
+    value
+}
+
+fn merge(base: &mut Value, other: Value) {
+    match (base, other) {
+        (Value::Object(base), Value::Object(other)) => other.into_iter().for_each(|(k, v)| match base.get_mut(&k) {
+            Some(b) => merge(b, v),
+            None => drop(base.insert(k, v)),
+        }),
+        (_, Value::Null) => {}
+        (base, other) => *base = other,
+    }
+}
+
+/// Writes the table at `path`. Its nested tables are written as dotted keys if they only contain
+/// values and the table has values of its own, otherwise as separate tables.
+fn write_table(config: &mut String, root: &Value, node: &Value, value: &Value, path: &[&str]) {
+    let entries = table_entries(root, node, value);
+    let has_values = !path.is_empty() && entries.iter().any(|(_, p, v)| !is_table(root, p, v));
+
+    let mut lines = vec![];
+    let mut subtables = vec![];
+    for (key, prop, value) in entries {
+        if !is_table(root, prop, value) {
+            write_entry(&mut lines, root, &toml_key(key), prop, value);
+            continue;
+        }
+
+        let children = table_entries(root, prop, value);
+        match has_values && !children.iter().any(|(_, p, v)| is_table(root, p, v)) {
+            true => children.iter().for_each(|(k, p, v)| {
+                write_entry(&mut lines, root, &format!("{}.{}", toml_key(key), toml_key(k)), p, v)
+            }),
+            false => subtables.push((key, prop, value)),
+        }
+    }
+
+    if !lines.is_empty() {
+        if !path.is_empty() {
+            let header = path.iter().map(|k| toml_key(k)).collect::<Vec<_>>().join(".");
+            match comment(root, node) {
+                c if c.is_empty() => config.push_str(&format!("\n[{header}]\n")),
+                c => config.push_str(&format!("\n[{header}] # {c}\n")),
+            }
+        }
+        lines.iter().for_each(|l| config.push_str(&format!("{l}\n")));
+    }
+
+    for (key, prop, value) in subtables {
+        let path = [path, &[key]].concat();
+        write_table(config, root, prop, value, &path);
+    }
+
+    write_commented_examples(config, root, node, value);
+}
+
+/// Writes the commented examples of the properties without a value
+fn write_commented_examples(config: &mut String, root: &Value, node: &Value, value: &Value) {
+    let props = properties(root, node).into_iter().flatten();
+    for (key, prop) in props.filter(|(k, _)| value.get(k.as_str()).is_none_or(is_empty)) {
+        let Some(example) = get(root, prop, COMMENTED_EXAMPLE_KEY).and_then(Value::as_str) else {
+            continue;
+        };
+        match comment(root, prop) {
+            c if c.is_empty() => config.push_str(&format!("\n# {key}\n")),
+            c => config.push_str(&format!("\n# {c}\n")),
+        }
+        example.lines().for_each(|l| config.push_str(&format!("#{l}\n")));
+    }
+}
+
+fn table_entries<'a>(root: &'a Value, node: &'a Value, value: &'a Value) -> Vec<(&'a str, &'a Value, &'a Value)> {
+    properties(root, node)
+        .into_iter()
+        .flatten()
+        .filter_map(|(k, p)| value.get(k).filter(|v| !is_empty(v)).map(|v| (k.as_str(), p, v)))
+        .collect()
+}
+
+fn write_entry(lines: &mut Vec<String>, root: &Value, key: &str, prop: &Value, value: &Value) {
+    let comment = comment(root, prop);
+    match value.as_array().filter(|a| a.iter().any(Value::is_object)) {
+        Some(items) => {
+            if !comment.is_empty() {
+                lines.push(format!("# {comment}"));
+            }
+            lines.push(format!("{key} = ["));
+            let item_comments = get(root, prop, ITEM_COMMENTS_KEY);
+            for (i, item) in items.iter().enumerate() {
+                let comments = item_comments
+                    .and_then(|c| c.get(i.to_string()))
+                    .and_then(Value::as_array);
+                comments
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .for_each(|c| lines.push(format!("    # {c}")));
+                lines.push(format!("    {},", toml_value(item)));
+            }
+            lines.push("]".to_string());
+        }
+        None if comment.is_empty() => lines.push(format!("{key} = {}", toml_value(value))),
+        None => lines.push(format!("{key} = {} # {comment}", toml_value(value))),
+    }
+}
+
+fn comment(root: &Value, node: &Value) -> String {
+    let description = get(root, node, "description")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .replace('\n', " ");
+
+    let resolved = resolve(root, node);
+    let hint = match (resolved.get("minimum"), resolved.get("maximum"), resolved.get("enum")) {
+        (Some(min), Some(max), _) => format!("between {min} and {max}"),
+        (_, _, Some(Value::Array(values))) if values.len() <= MAX_ENUM_HINT => {
+            let values: Vec<String> = values.iter().map(Value::to_string).collect();
+            format!("one of {}", values.join(", "))
+        }
+        _ => String::new(),
+    };
+
+    match (description.is_empty(), hint.is_empty()) {
+        (_, true) => description,
+        (true, false) => hint,
+        (false, false) => format!("{description}, {hint}"),
+    }
+}
+
+fn is_table(root: &Value, node: &Value, value: &Value) -> bool {
+    value.is_object() && properties(root, node).is_some()
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(a) => a.is_empty(),
+        Value::Object(o) => o.values().all(is_empty),
+        _ => false,
+    }
+}
+
+fn toml_key(key: &str) -> String {
+    match key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        true => key.to_string(),
+        false => toml::Value::String(key.to_string()).to_string(),
+    }
+}
+
+fn toml_value(value: &Value) -> String {
+    toml::Value::try_from(value).map(|v| v.to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::default_config;
+    use super::json_schema;
+    use crate::app::configs::AppConfig;
+
+    #[test]
+    fn shipped_schema_is_up_to_date() {
+        let shipped = include_str!("../../../assets/configs/mondrian.schema.json").replace("\r\n", "\n");
+        assert!(
+            shipped == json_schema(),
+            "Run `mondrian schema` to update the shipped schema"
+        );
+    }
+
+    #[test]
+    fn shipped_default_config_is_up_to_date() {
+        let shipped = include_str!("../../../assets/configs/mondrian.toml").replace("\r\n", "\n");
+        assert!(
+            shipped == default_config(),
+            "Run `mondrian default-config` to update the shipped configuration"
+        );
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        toml::from_str::<AppConfig>(&default_config()).unwrap();
+    }
+
+    #[test]
+    fn commented_examples_are_valid() {
+        // INFO: the commented entries start with `#` followed by a key or an indented item, or they are
+        // commented array items, while the other comments start with `# `
+        let uncomment = |l: &str| match (l.trim_start().strip_prefix("# {"), l.strip_prefix('#')) {
+            (Some(item), _) => format!("{{{item}"),
+            (None, Some(line)) if !line.starts_with([' ', ':']) || line.starts_with("    ") => line.to_string(),
+            _ => l.to_string(),
+        };
+        let config: String = default_config().lines().map(|l| uncomment(l) + "\n").collect();
+        assert_ne!(config, default_config());
+
+        let config = toml::from_str::<AppConfig>(&config).unwrap();
+        assert!(!config.rules.is_empty());
+        assert!(!config.monitors_config.is_empty() && !config.workspaces_config.is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+use super::AppConfig;
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+const INCLUDE_KEY: &str = "include";
+const SCHEMA_KEY: &str = "$schema";
+
+/// Supported configuration files, in order of precedence
+pub const CONFIG_FILE_NAMES: [&str; 4] = ["mondrian.toml", "mondrian.yaml", "mondrian.yml", "mondrian.json"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ConfigFormat {
+    pub fn from_path(path: &Path) -> ConfigFormat {
+        let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+        match ext.as_deref() {
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            Some("json") => ConfigFormat::Json,
+            _ => ConfigFormat::Toml,
+        }
+    }
+
+    fn parse<T: DeserializeOwned>(&self, content: &str) -> Result<T, String> {
+        match self {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => from_value(serde_yaml::from_str(content).map_err(|e| e.to_string())?),
+            ConfigFormat::Json => from_value(serde_json::from_str(content).map_err(|e| e.to_string())?),
+        }
+    }
+}
+
+/// TOML has no null, so the null values (e.g. `null` in JSON or `~` in YAML) are treated as absent keys
+fn from_value<T: DeserializeOwned>(mut value: serde_json::Value) -> Result<T, String> {
+    remove_nulls(&mut value);
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+fn remove_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(remove_nulls);
+        }
+        serde_json::Value::Array(array) => array.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
+}
+
+/// Returns the first existing configuration file in `dir` (see [`CONFIG_FILE_NAMES`]).
+/// If none of them exists, the TOML one is returned.
+pub fn find_config_file(dir: &Path) -> PathBuf {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|n| dir.join(n))
+        .find(|p| p.exists())
+        .unwrap_or(dir.join(CONFIG_FILE_NAMES[0]))
+}
+
+/// Reads the configuration file, merging all the files it includes (if any).
+pub fn read_config(path: &Path) -> Result<AppConfig, String> {
+    let format = ConfigFormat::from_path(path);
+    let content = read_file(path)?;
+    let table = format.parse::<Table>(&content)?;
+
+    // INFO: without includes, the file is parsed directly to keep the errors locations
+    if !table.contains_key(INCLUDE_KEY) && !table.contains_key(SCHEMA_KEY) {
+        return format.parse::<AppConfig>(&content);
+    }
+
+    let merged = read_layers(path, &mut vec![], &mut vec![])?;
+    Value::Table(merged).try_into::<AppConfig>().map_err(|e| e.to_string())
+}
+
+/// Returns the configuration file along with all the files it includes, directly or not.
+/// Unreadable or invalid files are still returned, so that they can be watched for changes.
+pub fn get_config_files(path: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let _ = read_layers(path, &mut vec![], &mut files);
+    files
+}
+
+fn read_layers(path: &Path, stack: &mut Vec<PathBuf>, files: &mut Vec<PathBuf>) -> Result<Table, String> {
+    let path = std::path::absolute(path).unwrap_or(path.to_path_buf());
+    if stack.contains(&path) {
+        return Err(format!("Circular include of '{}'", path.display()));
+    }
+
+    if !files.contains(&path) {
+        files.push(path.clone());
+    }
+
+    let mut table = ConfigFormat::from_path(&path)
+        .parse::<Table>(&read_file(&path)?)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    table.remove(SCHEMA_KEY);
+
+    let includes = match table.remove(INCLUDE_KEY) {
+        Some(Value::Array(includes)) => includes,
+        Some(_) => return Err(format!("{}: '{INCLUDE_KEY}' must be an array of paths", path.display())),
+        None => vec![],
+    };
+
+    let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut merged = Table::new();
+    stack.push(path.clone());
+    for include in includes {
+        let include = match include {
+            Value::String(include) => base_dir.join(include),
+            _ => return Err(format!("{}: '{INCLUDE_KEY}' must be an array of paths", path.display())),
+        };
+        merge_tables(&mut merged, read_layers(&include, stack, files)?);
+    }
+    stack.pop();
+
+    merge_tables(&mut merged, table);
+    Ok(merged)
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Can't read '{}': {}", path.display(), e))
+}
+
+/// Merges `other` into `base`. Tables are merged recursively, arrays of tables (e.g. rules or
+/// keybindings) are concatenated and any other value in `other` replaces the one in `base`.
+fn merge_tables(base: &mut Table, other: Table) {
+    for (key, value) in other {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_table)), Value::Table(table)) => merge_tables(base_table, table),
+            (Some(Value::Array(base_array)), Value::Array(array)) if is_tables_array(base_array, &array) => {
+                base_array.extend(array)
+            }
+            (_, value) => drop(base.insert(key, value)),
+        }
+    }
+}
+
+fn is_tables_array(a: &[Value], b: &[Value]) -> bool {
+    a.iter().chain(b.iter()).all(Value::is_table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_config_files, merge_tables, read_config, read_layers};
+    use std::path::{Path, PathBuf};
+    use toml::{Table, Value};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mondrian-loader-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_include_merge() {
+        let dir = temp_dir("merge");
+        write(
+            &dir,
+            "base.toml",
+            "[general]\nhistory_based_navigation = true\ndefault_workspace = \"2\"\n",
+        );
+        let path = write(
+            &dir,
+            "mondrian.toml",
+            "include = [\"base.toml\"]\n[general]\ndefault_workspace = \"3\"\n",
+        );
+
+        let config = read_config(&path).unwrap();
+        assert!(config.history_based_navigation);
+        assert_eq!(config.default_workspace, "3");
+        assert_eq!(get_config_files(&path).len(), 2);
+    }
+
+    #[test]
+    fn test_circular_include() {
+        let dir = temp_dir("circular");
+        write(&dir, "a.toml", "include = [\"b.toml\"]\n");
+        let path = write(&dir, "b.toml", "include = [\"a.toml\"]\n");
+
+        let error = read_config(&path).unwrap_err();
+        assert!(error.starts_with("Circular include"), "{error}");
+        assert_eq!(get_config_files(&path).len(), 2);
+    }
+
+    #[test]
+    fn test_rules_concatenated() {
+        let dir = temp_dir("rules");
+        write(
+            &dir,
+            "base.toml",
+            "[[core.rules]]\nfilter = { exename = \"a.exe\" }\nbehavior = \"float\"\n",
+        );
+        let path = write(
+            &dir,
+            "mondrian.toml",
+            "include = [\"base.toml\"]\n[[core.rules]]\nfilter = { exename = \"b.exe\" }\nbehavior = \"float\"\n",
+        );
+
+        let merged = read_layers(&path, &mut vec![], &mut vec![]).unwrap();
+        let rules = merged["core"]["rules"].as_array().unwrap();
+        let exenames: Vec<&str> = rules.iter().map(|r| r["filter"]["exename"].as_str().unwrap()).collect();
+        assert_eq!(exenames, ["a.exe", "b.exe"]);
+        assert!(read_config(&path).is_ok());
+    }
+
+    #[test]
+    fn test_rules_priority() {
+        let dir = temp_dir("priority");
+        let rule = |exe: &str, extra: &str| format!("[[core.rules]]\nfilter = {{ exename = \"{exe}\" }}\n{extra}\n");
+        let content = [
+            rule("a.exe", "behavior = \"float\"\npriority = 10"),
+            rule("b.exe", "behavior = \"float\""),
+            rule("c.exe", "behavior = \"focalize\"\npriority = 10"),
+        ];
+        let path = write(&dir, "mondrian.toml", &content.concat());
+
+        let config = read_config(&path).unwrap();
+        let exenames: Vec<String> = config.rules.iter().map(|r| r.filter.to_string()).collect();
+        assert_eq!(
+            exenames,
+            ["exename = \"b.exe\"", "exename = \"a.exe\"", "exename = \"c.exe\""]
+        );
+
+        let path = write(&dir, "stop.toml", &rule("a.exe", "behavior = \"ignore\"\nstop = true"));
+        let error = read_config(&path).unwrap_err();
+        assert!(error.contains("`stop` can't be used"), "{error}");
+    }
+
+    #[test]
+    fn test_yaml_json_include() {
+        let dir = temp_dir("formats");
+        write(
+            &dir,
+            "base.json",
+            r#"{ "general": { "history_based_navigation": true, "default_workspace": "2" } }"#,
+        );
+        let path = write(
+            &dir,
+            "mondrian.yaml",
+            "include: [base.json]\ngeneral:\n  default_workspace: \"3\"\n",
+        );
+
+        let config = read_config(&path).unwrap();
+        assert!(config.history_based_navigation);
+        assert_eq!(config.default_workspace, "3");
+        assert_eq!(get_config_files(&path).len(), 2);
+    }
+
+    #[test]
+    fn test_null_values() {
+        let dir = temp_dir("nulls");
+        let json = write(
+            &dir,
+            "mondrian.json",
+            r#"{ "general": { "history_based_navigation": null, "default_workspace": "2" } }"#,
+        );
+        let yaml = write(
+            &dir,
+            "mondrian.yaml",
+            "general:\n  history_based_navigation: ~\n  default_workspace: \"2\"\n",
+        );
+
+        for path in [json, yaml] {
+            let config = read_config(&path).unwrap();
+            assert!(!config.history_based_navigation);
+            assert_eq!(config.default_workspace, "2");
+        }
+    }
+
+    #[test]
+    fn test_merge_tables() {
+        let mut base: Table = toml::from_str("a = [1, 2]\nb = 1\n[t]\nx = 1\ny = 1\n").unwrap();
+        let other: Table = toml::from_str("a = [3]\n[t]\ny = 2\n").unwrap();
+        merge_tables(&mut base, other);
+
+        assert_eq!(base["a"], Value::Array(vec![Value::Integer(3)]));
+        assert_eq!(base["b"], Value::Integer(1));
+        assert_eq!(base["t"]["x"], Value::Integer(1));
+        assert_eq!(base["t"]["y"], Value::Integer(2));
+    }
+}
@@ -1,27 +1,57 @@
+pub(crate) mod changes;
 pub(crate) mod deserializers;
 mod external;
 pub(crate) mod floating;
+pub(crate) mod generator;
+pub(crate) mod loader;
 mod modules;
+pub(crate) mod profiles;
 pub(crate) mod rules;
+pub(crate) mod schemas;
 mod utils;
 
 use super::area_tree::layout_strategy::LayoutStrategyEnum;
+use super::structs::monitor_selector::MonitorSelector;
 use super::structs::paddings::Paddings;
+use super::structs::reserved_area::ReservedArea;
 use super::structs::win_matcher::WinMatcher;
+use crate::modules::tiles_manager::lib::window_animation_player::AnimationKind;
+use crate::modules::tiles_manager::lib::window_animation_player::AnimationSettings;
 use crate::modules::tiles_manager::lib::window_animation_player::WindowAnimation;
+use crate::win32::api::monitor::Monitor;
+use external::general::WorkspaceTransition;
 use external::AppConfigExternal;
 use floating::FloatingWinsConfig;
 use modules::Modules;
+use profiles::ConfigProfile;
 use rules::extract_rules;
+use rules::extract_title_change_filters;
+use rules::InsertDelay;
 use rules::WindowRule;
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct AnimationsConfig {
     pub duration: u32,
     pub framerate: u8,
     pub animation_type: Option<WindowAnimation>,
+    pub workspace_transition: WorkspaceTransition,
+    /// Duration and type of the animations overridden for specific kinds of changes
+    pub overrides: HashMap<AnimationKind, (Option<u32>, Option<WindowAnimation>)>,
+}
+
+impl AnimationsConfig {
+    /// Returns the settings of the animations for the given kind of change, or `None` if the
+    /// animations are disabled
+    pub fn get_settings(&self, kind: AnimationKind) -> Option<AnimationSettings> {
+        let (duration, animation_type) = self.overrides.get(&kind).copied().unwrap_or_default();
+        let animation = self.animation_type.map(|a| animation_type.unwrap_or(a))?;
+        let duration = Duration::from_millis(duration.unwrap_or(self.duration).into());
+        Some(AnimationSettings { animation, duration })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -32,12 +62,14 @@ pub struct LayoutConfig {
     pub focalized_padding: Paddings,
     pub half_focalized_borders_pad: Paddings,
     pub half_focalized_tiles_pad: u8,
+    pub scale_with_dpi: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct MonitorConfig {
     pub default_workspace: String,
     pub layout: LayoutConfig,
+    pub reserved: ReservedArea,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -60,18 +92,59 @@ pub struct AppConfig {
     pub animations: AnimationsConfig,
     pub floating_wins_config: FloatingWinsConfig,
     pub default_workspace: String,
+    pub fallback_monitor: String,
+    pub events_coalescing: u32,
+    pub max_batch_latency: u32,
     pub ignore_filter: WinMatcher,
-    pub delayed_filter: Vec<(WinMatcher, u32)>,
+    pub delayed_filter: Vec<(WinMatcher, InsertDelay)>,
     pub rules: Vec<WindowRule>,
+    pub title_change_filters: Vec<WinMatcher>,
     pub tiles_pad: u8,
     pub borders_pads: Paddings,
     pub half_focalized_tiles_pad: u8,
     pub half_focalized_borders_pads: Paddings,
     pub focalized_pads: Paddings,
+    pub scale_with_dpi: bool,
     pub layout_strategy: LayoutStrategyEnum,
     pub monitors_config: HashMap<String, MonitorConfig>,
+    pub monitor_aliases: HashMap<String, String>,
     pub workspaces_config: HashMap<String, WorkspaceConfig>,
     pub modules: Modules,
+    pub profiles: HashMap<String, ConfigProfile>,
+}
+
+impl AppConfig {
+    /// Returns the name of the first profile (in alphabetical order) matching the connected monitors
+    pub fn find_profile(&self, monitors: &[Monitor]) -> Option<String> {
+        let mut names: Vec<&String> = self.profiles.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .find(|n| {
+                self.profiles[*n]
+                    .monitors_set
+                    .as_ref()
+                    .is_some_and(|s| s.matches(monitors, &self.monitor_aliases))
+            })
+            .cloned()
+    }
+
+    /// Returns the configuration with the settings of the given profile, if it exists
+    pub fn with_profile(&self, name: &str) -> Option<AppConfig> {
+        let profile = self.profiles.get(name)?;
+        Some(AppConfig {
+            tiles_pad: profile.layout.tiles_padding,
+            borders_pads: profile.layout.borders_padding,
+            half_focalized_tiles_pad: profile.layout.half_focalized_tiles_pad,
+            half_focalized_borders_pads: profile.layout.half_focalized_borders_pad,
+            focalized_pads: profile.layout.focalized_padding,
+            scale_with_dpi: profile.layout.scale_with_dpi,
+            layout_strategy: profile.layout.layout_strategy.clone(),
+            monitors_config: profile.monitors_config.clone(),
+            workspaces_config: profile.workspaces_config.clone(),
+            ..self.clone()
+        })
+    }
 }
 
 impl Default for AppConfig {
@@ -91,12 +164,53 @@ impl TryFrom<AppConfigExternal> for AppConfig {
             return Err("Default workspace cannot be bound to a monitor".to_string());
         }
 
+        for target in v.monitor_aliases.values() {
+            MonitorSelector::from_str(target)?;
+        }
+
         let floating_wins_config = v.general.floating_wins.into();
+        let animations = &v.general.animations;
+        let animation_overrides = [
+            (AnimationKind::Open, &animations.open),
+            (AnimationKind::Close, &animations.close),
+            (AnimationKind::Swap, &animations.swap),
+            (AnimationKind::Resize, &animations.resize),
+            (AnimationKind::WorkspaceSwitch, &animations.workspace_switch),
+            (AnimationKind::Focalize, &animations.focalize),
+        ]
+        .into_iter()
+        .filter_map(|(k, o)| o.as_ref().map(|o| (k, (o.duration, o.animation_type))))
+        .collect();
+        let aliases = &v.monitor_aliases;
+        let ignore_rules: Vec<WinMatcher> = v
+            .core
+            .ignore_rules
+            .iter()
+            .map(|f| f.with_monitor_aliases(aliases))
+            .collect();
+        let rules: Vec<WindowRule> = v
+            .core
+            .rules
+            .iter()
+            .map(|r| WindowRule {
+                filter: r.filter.with_monitor_aliases(aliases),
+                ..r.clone()
+            })
+            .collect();
         let (ignore_filter, delayed_filter, other_rules) =
-            extract_rules(&v.core.ignore_rules, &v.core.rules, &v.general.floating_wins);
+            extract_rules(&ignore_rules, &rules, &v.general.floating_wins);
+        let title_change_filters = extract_title_change_filters(&rules);
         let layout_strategy = utils::get_layout_strategy(&v.layout.tiling_strategy, &v.layout.strategy);
         let monitors_config = utils::get_monitors_config(&v.monitors, &v.layout, &v.general.default_workspace);
         let workspaces_config = utils::get_workspaces_config(&v.workspaces, &v.monitors, &v.layout);
+        let profiles = v
+            .profiles
+            .iter()
+            .map(|(n, p)| {
+                let profile = utils::get_profile(p, &v).map_err(|e| format!("Profile '{n}': {e}"))?;
+                Ok((n.to_lowercase(), profile))
+            })
+            .collect::<Result<_, String>>()?;
 
         Ok(AppConfig {
             history_based_navigation: v.general.history_based_navigation,
@@ -112,21 +226,30 @@ impl TryFrom<AppConfigExternal> for AppConfig {
                     true => Some(v.general.animations.animation_type),
                     false => None,
                 },
+                workspace_transition: v.general.animations.workspace_transition,
+                overrides: animation_overrides,
             },
             floating_wins_config,
             default_workspace: v.general.default_workspace,
+            fallback_monitor: v.general.fallback_monitor,
+            events_coalescing: v.general.events_coalescing,
+            max_batch_latency: v.general.max_batch_latency,
             ignore_filter,
             delayed_filter,
             rules: other_rules,
+            title_change_filters,
             tiles_pad: v.layout.paddings.tiles,
             borders_pads: v.layout.paddings.borders,
             half_focalized_tiles_pad: v.layout.half_focalized_paddings.tiles,
             half_focalized_borders_pads: v.layout.half_focalized_paddings.borders,
             focalized_pads: v.layout.focalized_padding,
+            scale_with_dpi: v.layout.scale_with_dpi,
             layout_strategy,
             monitors_config,
+            monitor_aliases: v.monitor_aliases,
             workspaces_config,
             modules: v.modules,
+            profiles,
             allow_focus_on_empty_monitor: v.general.allow_focus_on_empty_monitor,
         })
     }
@@ -1,8 +1,10 @@
 use crate::modules::keybindings::configs::KeybindingsModuleConfigs;
 use crate::modules::overlays::configs::OverlaysModuleConfigs;
+use schemars::JsonSchema;
 use serde::Deserialize;
 
-#[derive(Deserialize, Clone, Debug, Default)]
+/// Modules configuration settings
+#[derive(Deserialize, JsonSchema, Clone, Debug, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct Modules {
     pub keybindings: KeybindingsModuleConfigs,
@@ -0,0 +1,89 @@
+use super::external::profiles::MonitorSet;
+use super::LayoutConfig;
+use super::MonitorConfig;
+use super::WorkspaceConfig;
+use crate::app::structs::monitor_selector::resolve_monitor;
+use crate::win32::api::monitor::Monitor;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProfile {
+    pub monitors_set: Option<MonitorSetMatcher>,
+    pub layout: LayoutConfig,
+    pub monitors_config: HashMap<String, MonitorConfig>,
+    pub workspaces_config: HashMap<String, WorkspaceConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MonitorSetMatcher {
+    ids: Vec<String>,
+    count: Option<u8>,
+    resolutions: Vec<(i32, i32)>,
+}
+
+impl MonitorSetMatcher {
+    /// Returns true if the connected monitors satisfy all the conditions
+    pub fn matches(&self, monitors: &[Monitor], aliases: &HashMap<String, String>) -> bool {
+        let has_id = |id: &String| resolve_monitor(id, aliases, monitors).is_some();
+        let has_resolution = |r: &(i32, i32)| monitors.iter().any(|m| m.resolution == *r);
+
+        self.count.is_none_or(|c| monitors.len() == c as usize)
+            && self.ids.iter().all(has_id)
+            && self.resolutions.iter().all(has_resolution)
+    }
+}
+
+impl From<MonitorSet> for MonitorSetMatcher {
+    fn from(value: MonitorSet) -> Self {
+        MonitorSetMatcher {
+            ids: value.ids,
+            count: value.count,
+            resolutions: value.resolutions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MonitorSetMatcher;
+    use crate::app::test_utils::monitor;
+    use std::collections::HashMap;
+
+    fn matcher(ids: &[&str], count: Option<u8>, resolutions: &[(i32, i32)]) -> MonitorSetMatcher {
+        MonitorSetMatcher {
+            ids: ids.iter().map(|id| id.to_string()).collect(),
+            count,
+            resolutions: resolutions.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_exact_match() {
+        let monitors = [monitor("MONITOR1", 0, true), monitor("MONITOR2", 1920, false)];
+        let aliases = HashMap::new();
+
+        assert!(matcher(&["MONITOR1", "MONITOR2"], Some(2), &[(1920, 1080)]).matches(&monitors, &aliases));
+    }
+
+    #[test]
+    fn test_subset_match() {
+        let monitors = [monitor("MONITOR1", 0, true), monitor("MONITOR2", 1920, false)];
+        let aliases = HashMap::from([("main".to_string(), "primary".to_string())]);
+
+        assert!(matcher(&["MONITOR2"], None, &[]).matches(&monitors, &aliases));
+        assert!(matcher(&["main"], None, &[]).matches(&monitors, &aliases));
+        assert!(matcher(&[], None, &[(1920, 1080)]).matches(&monitors, &aliases));
+        assert!(MonitorSetMatcher::default().matches(&monitors, &aliases));
+    }
+
+    #[test]
+    fn test_no_match() {
+        let monitors = [monitor("MONITOR1", 0, true), monitor("MONITOR2", 1920, false)];
+        let aliases = HashMap::new();
+
+        assert!(!matcher(&["MONITOR3"], None, &[]).matches(&monitors, &aliases));
+        assert!(!matcher(&["MONITOR1", "MONITOR3"], None, &[]).matches(&monitors, &aliases));
+        assert!(!matcher(&["MONITOR1"], Some(1), &[]).matches(&monitors, &aliases));
+        assert!(!matcher(&[], None, &[(2560, 1440)]).matches(&monitors, &aliases));
+    }
+}
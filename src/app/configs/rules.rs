@@ -1,11 +1,15 @@
 use super::{external, floating::FloatingWinsConfig};
 use crate::app::structs::win_matcher::WinMatcher;
+use crate::app::structs::win_matcher::WinQuery;
 use std::collections::HashSet;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct WindowRule {
     pub filter: WinMatcher,
     pub behavior: WindowBehavior,
+    pub priority: i32,
+    /// If true, the rules before this one are not applied to the matched windows
+    pub stop: bool,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -18,13 +22,38 @@ pub enum WindowBehavior {
         workspace: Option<String>,
         silent: bool,
     },
+    Focalize,
+    HalfFocalize,
+    /// Share of the split (in percent)
+    Ratio(u8),
+    Padding(u8),
+    Overlay(bool),
+    StartMinimized,
+    Topmost,
+}
+
+/// Delay before the insertion of a window (in milliseconds)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InsertDelay {
+    Fixed(u32),
+    /// Waits until the area of the window has not changed for the given time
+    UntilStable(u32),
+}
+
+impl std::fmt::Display for InsertDelay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InsertDelay::Fixed(delay) => write!(f, "{delay} ms"),
+            InsertDelay::UntilStable(delay) => write!(f, "until stable for {delay} ms"),
+        }
+    }
 }
 
 pub(crate) fn extract_rules(
     ignore_filters: &[WinMatcher],
     rules: &[external::core::WindowRule],
     floating_wins_ext: &external::general::FloatingWinsConfig,
-) -> (WinMatcher, Vec<(WinMatcher, u32)>, Vec<WindowRule>) {
+) -> (WinMatcher, Vec<(WinMatcher, InsertDelay)>, Vec<WindowRule>) {
     let (mut ignore_rules, other_rules): (Vec<_>, Vec<external::core::WindowRule>) = rules
         .iter()
         .cloned()
@@ -38,7 +67,14 @@ pub(crate) fn extract_rules(
     let delayed_filter = delayed_filter
         .iter()
         .map(|r| match &r.behavior {
-            external::core::WindowBehavior::DelayInsert { delay: wait } => (r.filter.clone(), *wait),
+            external::core::WindowBehavior::DelayInsert {
+                delay,
+                until_stable: false,
+            } => (r.filter.clone(), InsertDelay::Fixed(*delay)),
+            external::core::WindowBehavior::DelayInsert {
+                delay,
+                until_stable: true,
+            } => (r.filter.clone(), InsertDelay::UntilStable(*delay)),
             _ => unreachable!(),
         })
         .collect();
@@ -51,7 +87,7 @@ pub(crate) fn extract_rules(
         ignore_rules.extend(rules);
     }
 
-    let mondrian_filter = WinMatcher::Exename("mondrian.exe".to_owned());
+    let mondrian_filter = WinMatcher::Exename(WinQuery::contains("mondrian.exe"));
     let ignore_filter = match ignore_rules.is_empty() {
         true => mondrian_filter.clone(),
         false => {
@@ -61,10 +97,12 @@ pub(crate) fn extract_rules(
         }
     };
 
-    let other_rules = other_rules
+    let mut other_rules: Vec<WindowRule> = other_rules
         .iter()
         .map(|r| WindowRule {
             filter: r.filter.clone(),
+            priority: r.priority,
+            stop: r.stop,
             behavior: match &r.behavior {
                 external::core::WindowBehavior::Ignore | external::core::WindowBehavior::DelayInsert { .. } => {
                     unreachable!()
@@ -75,6 +113,7 @@ pub(crate) fn extract_rules(
                     centered,
                     size_fixed,
                     size_ratio,
+                    global,
                 } => {
                     let config_ext = external::general::FloatingWinsConfig {
                         size: size.unwrap_or(floating_wins_ext.size),
@@ -84,7 +123,10 @@ pub(crate) fn extract_rules(
                         size_fixed: size_fixed.unwrap_or(floating_wins_ext.size_fixed),
                     };
                     WindowBehavior::Float {
-                        config: config_ext.into(),
+                        config: FloatingWinsConfig {
+                            global: *global,
+                            ..config_ext.into()
+                        },
                     }
                 }
                 external::core::WindowBehavior::Insert {
@@ -96,9 +138,30 @@ pub(crate) fn extract_rules(
                     workspace: workspace.clone(),
                     silent: *silent,
                 },
+                external::core::WindowBehavior::Focalize => WindowBehavior::Focalize,
+                external::core::WindowBehavior::HalfFocalize => WindowBehavior::HalfFocalize,
+                external::core::WindowBehavior::Ratio(ratio) => WindowBehavior::Ratio((ratio * 100.0).round() as u8),
+                external::core::WindowBehavior::Padding(padding) => WindowBehavior::Padding(*padding),
+                external::core::WindowBehavior::Overlay(enabled) => WindowBehavior::Overlay(*enabled),
+                external::core::WindowBehavior::StartMinimized => WindowBehavior::StartMinimized,
+                external::core::WindowBehavior::Topmost => WindowBehavior::Topmost,
             },
         })
         .collect();
 
+    // INFO: the sort is stable, so the rules with the same priority keep their order
+    other_rules.sort_by_key(|r| r.priority);
+
     (ignore_filter, delayed_filter, other_rules)
 }
+
+/// Returns the filters of the rules that must be evaluated again when the title of a window changes
+pub(crate) fn extract_title_change_filters(rules: &[external::core::WindowRule]) -> Vec<WinMatcher> {
+    let mut filters: Vec<WinMatcher> = vec![];
+    for rule in rules.iter().filter(|r| r.reevaluate_on_title_change) {
+        if !filters.contains(&rule.filter) {
+            filters.push(rule.filter.clone());
+        }
+    }
+    filters
+}
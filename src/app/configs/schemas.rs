@@ -0,0 +1,79 @@
+use super::deserializers::TILING_STRATEGIES;
+use schemars::json_schema;
+use schemars::Schema;
+use schemars::SchemaGenerator;
+
+pub fn paddings<const MAX: u8>(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "oneOf": [
+            { "type": "integer", "minimum": 0, "maximum": MAX },
+            {
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0, "maximum": MAX },
+                "minItems": 2,
+                "maxItems": 2
+            },
+            {
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0, "maximum": MAX },
+                "minItems": 4,
+                "maxItems": 4
+            }
+        ]
+    })
+}
+
+pub fn tiling_strategy(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "string",
+        "enum": TILING_STRATEGIES
+    })
+}
+
+pub fn size_ratio(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": { "type": "number", "minimum": 0.1, "maximum": 1.0 },
+        "minItems": 2,
+        "maxItems": 2
+    })
+}
+
+pub fn size_fixed(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": { "type": "integer", "minimum": 100, "maximum": 10000 },
+        "minItems": 2,
+        "maxItems": 2
+    })
+}
+
+pub fn window_size(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": { "type": "integer", "minimum": 0, "maximum": u16::MAX },
+        "minItems": 2,
+        "maxItems": 2
+    })
+}
+
+pub fn workspace_id(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": "^[a-zA-Z0-9_.\\-:]{1,32}$"
+    })
+}
+
+pub fn behavior_shortcut(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "string",
+        "enum": ["ignore", "delayinsert", "float", "focalize", "halffocalize", "startminimized", "topmost"]
+    })
+}
+
+pub fn resolutions(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": { "type": "string", "pattern": "^[1-9][0-9]*x[1-9][0-9]*$" }
+    })
+}
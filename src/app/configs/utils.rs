@@ -1,8 +1,11 @@
 use super::{
     external::{
         self,
+        layout::PaddingsConfigs,
         layout_optional::{LayoutOptional, PaddingsOptionalConfigs},
+        AppConfigExternal,
     },
+    profiles::ConfigProfile,
     LayoutConfig, MonitorConfig, WorkspaceConfig,
 };
 use crate::app::area_tree::layout_strategy::LayoutStrategyEnum;
@@ -32,6 +35,7 @@ pub fn get_monitors_config(
                 MonitorConfig {
                     layout,
                     default_workspace: c.default_workspace.clone().unwrap_or(default_workspace.to_string()),
+                    reserved: c.reserved,
                 },
             )
         })
@@ -85,6 +89,60 @@ pub fn get_workspaces_config(
     workspaces
 }
 
+pub fn get_profile(
+    profile: &external::profiles::Profile,
+    ext_config: &AppConfigExternal,
+) -> Result<ConfigProfile, String> {
+    let default_workspace = &ext_config.general.default_workspace;
+    let layout = apply_layout_optional_config(&ext_config.layout, &profile.layout);
+
+    let mut monitors = ext_config.monitors.clone();
+    monitors.extend(profile.monitors.clone());
+    let mut workspaces = ext_config.workspaces.clone();
+    workspaces.extend(profile.workspaces.clone());
+
+    if workspaces
+        .get(default_workspace)
+        .is_some_and(|ws| ws.bind_to_monitor.is_some())
+    {
+        return Err("Default workspace cannot be bound to a monitor".to_string());
+    }
+
+    Ok(ConfigProfile {
+        monitors_set: profile.monitors_set.clone().map(|s| s.into()),
+        layout: get_layout_config(&LayoutOptional::default(), &layout),
+        monitors_config: get_monitors_config(&monitors, &layout, default_workspace),
+        workspaces_config: get_workspaces_config(&workspaces, &monitors, &layout),
+    })
+}
+
+fn apply_layout_optional_config(
+    ext_layout: &external::layout::Layout,
+    layout_optional: &LayoutOptional,
+) -> external::layout::Layout {
+    let l = layout_optional;
+    external::layout::Layout {
+        tiling_strategy: l.tiling_strategy.clone().unwrap_or(ext_layout.tiling_strategy.clone()),
+        paddings: PaddingsConfigs {
+            tiles: l.paddings.tiles.unwrap_or(ext_layout.paddings.tiles),
+            borders: l.paddings.borders.unwrap_or(ext_layout.paddings.borders),
+        },
+        half_focalized_paddings: PaddingsConfigs {
+            tiles: l
+                .half_focalized_paddings
+                .tiles
+                .unwrap_or(ext_layout.half_focalized_paddings.tiles),
+            borders: l
+                .half_focalized_paddings
+                .borders
+                .unwrap_or(ext_layout.half_focalized_paddings.borders),
+        },
+        focalized_padding: l.focalized_padding.unwrap_or(ext_layout.focalized_padding),
+        scale_with_dpi: l.scale_with_dpi.unwrap_or(ext_layout.scale_with_dpi),
+        strategy: ext_layout.strategy.clone(),
+    }
+}
+
 fn merge_layout_optional_config(
     base_layout_optional: &external::layout_optional::LayoutOptional,
     layout_optional: &external::layout_optional::LayoutOptional,
@@ -100,6 +158,7 @@ fn merge_layout_optional_config(
             borders: l.paddings.borders.or(base_layout_optional.paddings.borders),
         },
         focalized_padding: l.focalized_padding.or(base_layout_optional.focalized_padding),
+        scale_with_dpi: l.scale_with_dpi.or(base_layout_optional.scale_with_dpi),
         half_focalized_paddings: PaddingsOptionalConfigs {
             tiles: l
                 .half_focalized_paddings
@@ -138,6 +197,7 @@ fn get_layout_config(
             .half_focalized_paddings
             .tiles
             .unwrap_or(ext_layout.half_focalized_paddings.tiles),
+        scale_with_dpi: l.scale_with_dpi.unwrap_or(ext_layout.scale_with_dpi),
     }
 }
 
@@ -154,6 +214,7 @@ impl From<external::layout::Layout> for LayoutOptional {
                 tiles: Some(value.half_focalized_paddings.tiles),
                 borders: Some(value.half_focalized_paddings.borders),
             },
+            scale_with_dpi: Some(value.scale_with_dpi),
         }
     }
 }
@@ -0,0 +1,164 @@
+use super::mondrian_message::{MessagePriority, MondrianMessage};
+use super::structs::info_entry::InfoEntry;
+use crossbeam_channel::{Receiver, RecvError, SendError, Sender};
+use std::collections::VecDeque;
+
+/// Creates the queue of a module, with a channel for each priority
+pub fn module_queue() -> (ModuleQueue, ModuleQueueReceiver) {
+    let (user_tx, user_rx) = crossbeam_channel::unbounded();
+    let (background_tx, background_rx) = crossbeam_channel::unbounded();
+    let queue = ModuleQueue {
+        user_tx,
+        background_tx,
+        peak_depth: (0, 0),
+    };
+    (queue, ModuleQueueReceiver { user_rx, background_rx })
+}
+
+/// Sending side of the queue of a module
+pub struct ModuleQueue {
+    user_tx: Sender<MondrianMessage>,
+    background_tx: Sender<MondrianMessage>,
+    peak_depth: (usize, usize),
+}
+
+impl ModuleQueue {
+    pub fn send(&mut self, message: MondrianMessage) -> Result<(), SendError<MondrianMessage>> {
+        match message.priority() {
+            MessagePriority::User => self.user_tx.send(message)?,
+            MessagePriority::Background => self.background_tx.send(message)?,
+        };
+        self.peak_depth = (
+            self.peak_depth.0.max(self.user_tx.len()),
+            self.peak_depth.1.max(self.background_tx.len()),
+        );
+        Ok(())
+    }
+
+    /// Returns the current and the peak number of queued messages, for each priority
+    pub fn get_info_entry(&self, module_name: &str) -> InfoEntry {
+        let depth = |current: usize, peak: usize| format!("{current} queued (peak: {peak})");
+        InfoEntry::list(
+            format!("Module '{module_name}'"),
+            [
+                InfoEntry::simple("User commands", depth(self.user_tx.len(), self.peak_depth.0)),
+                InfoEntry::simple("Background events", depth(self.background_tx.len(), self.peak_depth.1)),
+            ],
+        )
+    }
+}
+
+/// Receiving side of the queue of a module, where the user commands jump ahead of the background events
+pub struct ModuleQueueReceiver {
+    user_rx: Receiver<MondrianMessage>,
+    background_rx: Receiver<MondrianMessage>,
+}
+
+impl ModuleQueueReceiver {
+    pub fn recv(&self) -> Result<MondrianMessage, RecvError> {
+        if let Ok(message) = self.user_rx.try_recv() {
+            return Ok(message);
+        }
+
+        crossbeam_channel::select! {
+            recv(self.user_rx) -> message => message,
+            recv(self.background_rx) -> message => message,
+        }
+    }
+}
+
+/// Messages waiting to be dispatched, split by priority
+#[derive(Default)]
+pub struct MessageLanes {
+    user: VecDeque<MondrianMessage>,
+    background: VecDeque<MondrianMessage>,
+}
+
+impl MessageLanes {
+    pub fn push(&mut self, message: MondrianMessage) {
+        match message.priority() {
+            MessagePriority::User => self.user.push_back(message),
+            MessagePriority::Background => self.background.push_back(message),
+        }
+    }
+
+    /// Returns the oldest user command or, if there is none, the oldest background event
+    pub fn pop(&mut self) -> Option<MondrianMessage> {
+        self.user.pop_front().or_else(|| self.background.pop_front())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.user.is_empty() && self.background.is_empty()
+    }
+
+    pub fn get_info_entry(&self) -> InfoEntry {
+        InfoEntry::list(
+            "Bus",
+            [
+                InfoEntry::simple("User commands", format!("{} queued", self.user.len())),
+                InfoEntry::simple("Background events", format!("{} queued", self.background.len())),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::module_queue;
+    use super::MessageLanes;
+    use crate::app::mondrian_message::MondrianMessage;
+    use crate::app::mondrian_message::WindowEvent;
+    use crate::app::structs::direction::Direction;
+    use crate::win32::window::window_ref::WindowRef;
+
+    fn background(win: isize) -> MondrianMessage {
+        MondrianMessage::WindowEvent(WindowEvent::Repositioned(WindowRef::from(win)))
+    }
+
+    #[test]
+    fn test_lanes_user_overtakes_background() {
+        let mut lanes = MessageLanes::default();
+        lanes.push(background(1));
+        lanes.push(background(2));
+        lanes.push(MondrianMessage::Focus(Direction::Left));
+        lanes.push(MondrianMessage::Focus(Direction::Right));
+
+        assert_eq!(lanes.pop(), Some(MondrianMessage::Focus(Direction::Left)));
+        assert_eq!(lanes.pop(), Some(MondrianMessage::Focus(Direction::Right)));
+        assert_eq!(lanes.pop(), Some(background(1)));
+        assert_eq!(lanes.pop(), Some(background(2)));
+        assert_eq!(lanes.pop(), None);
+        assert!(lanes.is_empty());
+    }
+
+    #[test]
+    fn test_module_queue_user_overtakes_background() {
+        let (mut queue, receiver) = module_queue();
+        queue.send(background(1)).unwrap();
+        queue.send(MondrianMessage::Invert).unwrap();
+        queue.send(background(2)).unwrap();
+        queue.send(MondrianMessage::Amplify).unwrap();
+
+        assert_eq!(receiver.recv(), Ok(MondrianMessage::Invert));
+        assert_eq!(receiver.recv(), Ok(MondrianMessage::Amplify));
+        assert_eq!(receiver.recv(), Ok(background(1)));
+        assert_eq!(receiver.recv(), Ok(background(2)));
+    }
+
+    #[test]
+    fn test_module_queue_peak_depth() {
+        let (mut queue, receiver) = module_queue();
+        queue.send(background(1)).unwrap();
+        queue.send(background(2)).unwrap();
+        queue.send(MondrianMessage::Invert).unwrap();
+        assert_eq!(queue.peak_depth, (1, 2));
+
+        receiver.recv().unwrap();
+        receiver.recv().unwrap();
+        queue.send(background(3)).unwrap();
+        assert_eq!(queue.peak_depth, (1, 2));
+
+        queue.send(background(4)).unwrap();
+        assert_eq!(queue.peak_depth, (1, 3));
+    }
+}
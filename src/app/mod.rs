@@ -2,7 +2,11 @@ pub mod app_lock;
 pub mod assets;
 pub mod cli_args;
 pub mod configs;
+pub mod message_queue;
 pub mod mondrian_message;
+pub mod rules_tester;
+#[cfg(test)]
+pub mod test_utils;
 
 pub mod area_tree {
     pub mod layout_strategy;
@@ -15,8 +19,10 @@ pub mod structs {
     pub mod area;
     pub mod direction;
     pub mod info_entry;
+    pub mod monitor_selector;
     pub mod orientation;
     pub mod paddings;
     pub mod point;
+    pub mod reserved_area;
     pub mod win_matcher;
 }
@@ -1,3 +1,4 @@
+use super::configs::changes::ConfigChanges;
 use super::structs::area::Area;
 use super::structs::direction::Direction;
 use super::structs::info_entry::InfoEntry;
@@ -5,6 +6,10 @@ use super::structs::info_entry::InfoEntryIcon;
 use crate::modules::tiles_manager::lib::tm::command::TMCommand;
 use crate::win32::window::window_ref::WindowRef;
 use regex::Regex;
+use schemars::json_schema;
+use schemars::JsonSchema;
+use schemars::Schema;
+use schemars::SchemaGenerator;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
@@ -121,6 +126,7 @@ pub enum WindowEvent {
     StartMoveSize(WindowRef),
     EndMoveSize(WindowRef, MoveSizeResult),
     Repositioned(WindowRef),
+    TitleChanged(WindowRef),
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -142,6 +148,7 @@ impl WindowEvent {
             | WindowEvent::Focused(winref)
             | WindowEvent::StartMoveSize(winref)
             | WindowEvent::Repositioned(winref)
+            | WindowEvent::TitleChanged(winref)
             | WindowEvent::EndMoveSize(winref, _) => *winref,
         }
     }
@@ -174,6 +181,8 @@ impl WindowTileState {
 #[derive(Debug, PartialEq, Clone)]
 pub enum MondrianMessage {
     RefreshConfig,
+    ConfigChanged(ConfigChanges),
+    SetProfile(String),
     OpenConfig,
     Retile,
     Configure,
@@ -218,6 +227,7 @@ pub enum MondrianMessage {
         infos: Vec<InfoEntry>,
     },
     ListManagedWindows,
+    ExplainWindow(Option<WindowRef>),
     OpenLogFolder,
     About,
     Quit,
@@ -229,71 +239,223 @@ pub enum MondrianMessage {
     SystemEvent(SystemEvent),
 }
 
+/// Priority of a message on the bus: the user commands (from keybindings, CLI or tray) are handled before the
+/// background events
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MessagePriority {
+    User,
+    Background,
+}
+
+impl MondrianMessage {
+    pub fn priority(&self) -> MessagePriority {
+        match self {
+            MondrianMessage::SetProfile(_)
+            | MondrianMessage::OpenConfig
+            | MondrianMessage::Retile
+            | MondrianMessage::Focus(_)
+            | MondrianMessage::FocusMonitor(_)
+            | MondrianMessage::FocusWorkspace { .. }
+            | MondrianMessage::MoveToWorkspace { .. }
+            | MondrianMessage::SwitchFocus
+            | MondrianMessage::Move(..)
+            | MondrianMessage::MoveInsert(..)
+            | MondrianMessage::Insert(_)
+            | MondrianMessage::Release(_)
+            | MondrianMessage::Peek(..)
+            | MondrianMessage::Resize(..)
+            | MondrianMessage::Invert
+            | MondrianMessage::Pause(_)
+            | MondrianMessage::PauseModule(..)
+            | MondrianMessage::Close
+            | MondrianMessage::Topmost
+            | MondrianMessage::Focalize
+            | MondrianMessage::HalfFocalize
+            | MondrianMessage::CycleFocalized { .. }
+            | MondrianMessage::Amplify
+            | MondrianMessage::Minimize
+            | MondrianMessage::ListManagedWindows
+            | MondrianMessage::ExplainWindow(_)
+            | MondrianMessage::OpenLogFolder
+            | MondrianMessage::About
+            | MondrianMessage::Quit => MessagePriority::User,
+            MondrianMessage::RefreshConfig
+            | MondrianMessage::ConfigChanged(_)
+            | MondrianMessage::Configure
+            | MondrianMessage::QueryInfo
+            | MondrianMessage::UpdatedWindows(..)
+            | MondrianMessage::CoreUpdateStart(..)
+            | MondrianMessage::CoreUpdateError
+            | MondrianMessage::CoreUpdateComplete
+            | MondrianMessage::QueryInfoResponse { .. }
+            | MondrianMessage::HealthCheckPing
+            | MondrianMessage::HealthCheckPong { .. }
+            | MondrianMessage::WindowEvent(_)
+            | MondrianMessage::SystemEvent(_) => MessagePriority::Background,
+        }
+    }
+}
+
+const DIRECTIONS: &[&str] = &["left", "right", "up", "down"];
+const WORKSPACE_PATTERN: &str = r"([a-zA-Z0-9_.\-:]{1,32})( ([a-zA-Z0-9_.\-:]+|'[a-zA-Z0-9_.\-: ]+'))?";
+const FLOATING_INC_PATTERN: &str = "(left|right|up|down)( (4[0-9]|[5-9][0-9]|[1-9][0-9]{2}|1000))?";
+
+/// Syntax of the actions, used both to parse them and to generate their JSON schema
+const ACTIONS: [ActionSyntax; 28] = [
+    ActionSyntax::new("refresh-config", ActionArgs::None),
+    ActionSyntax::new("open-config", ActionArgs::None),
+    ActionSyntax::new("set-profile", ActionArgs::pattern("<profile_name>", "[^ ]+", 1, 1)),
+    ActionSyntax::new("retile", ActionArgs::None),
+    ActionSyntax::new("minimize", ActionArgs::None),
+    ActionSyntax::new("close", ActionArgs::None),
+    ActionSyntax::new("toggle-topmost", ActionArgs::None),
+    ActionSyntax::new("switch-focus", ActionArgs::None),
+    ActionSyntax::new("focus", ActionArgs::one_of(DIRECTIONS, false)),
+    ActionSyntax::new("focus-monitor", ActionArgs::one_of(DIRECTIONS, false)),
+    ActionSyntax::new(
+        "focus-workspace",
+        ActionArgs::pattern("<workspace_id> [monitor]", WORKSPACE_PATTERN, 1, 2),
+    ),
+    ActionSyntax::new(
+        "move-to-workspace",
+        ActionArgs::pattern("<workspace_id> [monitor]", WORKSPACE_PATTERN, 1, 2),
+    ),
+    ActionSyntax::new(
+        "move-to-workspace-silent",
+        ActionArgs::pattern("<workspace_id> [monitor]", WORKSPACE_PATTERN, 1, 2),
+    ),
+    ActionSyntax::new("insert", ActionArgs::one_of(DIRECTIONS, false)),
+    ActionSyntax::new(
+        "move",
+        ActionArgs::pattern("<left|right|up|down> [40-1000]", FLOATING_INC_PATTERN, 1, 2),
+    ),
+    ActionSyntax::new(
+        "moveinsert",
+        ActionArgs::pattern("<left|right|up|down> [40-1000]", FLOATING_INC_PATTERN, 1, 2),
+    ),
+    ActionSyntax::new(
+        "resize",
+        ActionArgs::pattern(
+            "<left|right|up|down> <40-500> [40-500]",
+            "(left|right|up|down) (4[0-9]|[5-9][0-9]|[1-4][0-9]{2}|500)( (4[0-9]|[5-9][0-9]|[1-4][0-9]{2}|500))?",
+            2,
+            3,
+        ),
+    ),
+    ActionSyntax::new(
+        "peek",
+        ActionArgs::pattern(
+            "<left|right|up|down> <10-90>",
+            "(left|right|up|down) (1[0-9]|[2-8][0-9]|90)",
+            2,
+            2,
+        ),
+    ),
+    ActionSyntax::new("invert", ActionArgs::None),
+    ActionSyntax::new("release", ActionArgs::None),
+    ActionSyntax::new("focalize", ActionArgs::None),
+    ActionSyntax::new("half-focalize", ActionArgs::None),
+    ActionSyntax::new("cycle-focalized", ActionArgs::one_of(&["next", "prev"], true)),
+    ActionSyntax::new("amplify", ActionArgs::None),
+    ActionSyntax::new("dumpstateinfo", ActionArgs::None),
+    ActionSyntax::new("explain-window", ActionArgs::pattern("[hwnd]", "[0-9]+", 0, 1)),
+    ActionSyntax::new("pause", ActionArgs::one_of(&["keybindings", "overlays"], true)),
+    ActionSyntax::new("quit", ActionArgs::None),
+];
+
+struct ActionSyntax {
+    /// First word of the action
+    name: &'static str,
+    args: ActionArgs,
+}
+
+enum ActionArgs {
+    None,
+    /// A single argument among the given values
+    OneOf {
+        values: &'static [&'static str],
+        optional: bool,
+    },
+    /// Between `min` and `max` arguments, matching the pattern as a whole
+    Pattern {
+        usage: &'static str,
+        pattern: &'static str,
+        min: usize,
+        max: usize,
+    },
+}
+
+impl ActionArgs {
+    const fn one_of(values: &'static [&'static str], optional: bool) -> Self {
+        ActionArgs::OneOf { values, optional }
+    }
+
+    const fn pattern(usage: &'static str, pattern: &'static str, min: usize, max: usize) -> Self {
+        ActionArgs::Pattern {
+            usage,
+            pattern,
+            min,
+            max,
+        }
+    }
+}
+
+impl ActionSyntax {
+    const fn new(name: &'static str, args: ActionArgs) -> Self {
+        ActionSyntax { name, args }
+    }
+
+    fn accepts(&self, args_count: usize) -> bool {
+        match self.args {
+            ActionArgs::None => args_count == 0,
+            ActionArgs::OneOf { optional, .. } => args_count == 1 || (optional && args_count == 0),
+            ActionArgs::Pattern { min, max, .. } => (min..=max).contains(&args_count),
+        }
+    }
+
+    /// Returns the action as shown in the error messages, e.g. `focus <left|right|up|down>`
+    fn usage(&self) -> String {
+        match self.args {
+            ActionArgs::None => self.name.to_string(),
+            ActionArgs::OneOf { values, optional } => {
+                let (open, close) = if optional { ("[", "]") } else { ("<", ">") };
+                format!("{} {open}{}{close}", self.name, values.join("|"))
+            }
+            ActionArgs::Pattern { usage, .. } => format!("{} {}", self.name, usage),
+        }
+    }
+
+    /// Returns the complete forms of the action that can be listed in the JSON schema
+    fn schema_values(&self) -> Vec<String> {
+        let name = Some(self.name.to_string()).filter(|_| self.accepts(0));
+        let values: Vec<String> = match self.args {
+            ActionArgs::OneOf { values, .. } => values.iter().map(|v| format!("{} {}", self.name, v)).collect(),
+            _ => vec![],
+        };
+        name.into_iter().chain(values).collect()
+    }
+
+    /// Returns the pattern of the action with its arguments, for the JSON schema
+    fn schema_pattern(&self) -> Option<String> {
+        match self.args {
+            ActionArgs::Pattern { pattern, .. } => Some(format!("^{} {}$", self.name, pattern)),
+            _ => None,
+        }
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for MondrianMessage {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        let actions = [
-            "refresh-config",
-            "open-config",
-            "retile",
-            "minimize",
-            "close",
-            "toggle-topmost",
-            "switch-focus",
-            "focus <left|right|up|down>",
-            "focus-monitor <left|right|up|down>",
-            "focus-workspace <workspace_id>",
-            "move-to-workspace <workspace_id>",
-            "move-to-workspace-silent <workspace_id>",
-            "insert <left|right|up|down>",
-            "move <left|right|up|down> [40-1000]",
-            "moveinsert <left|right|up|down> [40-1000]",
-            "resize <left|right|up|down> <40-500> [40-500]",
-            "peek <left|right|up|down> <10-90>",
-            "invert",
-            "release",
-            "focalize",
-            "half-focalize",
-            "cycle-focalized [next|prev]",
-            "amplify",
-            "dumpstateinfo",
-            "pause [keybindings|overlays]",
-            "quit",
-        ];
-
         let s: String = Deserialize::deserialize(d)?;
         let s = s.to_lowercase();
         let parts: Vec<&str> = s.split(' ').map(|s| s.trim()).collect();
 
-        let valid_len = match parts[0] {
-            "refresh-config" => parts.len() == 1,
-            "open-config" => parts.len() == 1,
-            "retile" => parts.len() == 1,
-            "minimize" => parts.len() == 1,
-            "close" => parts.len() == 1,
-            "toggle-topmost" => parts.len() == 1,
-            "switch-focus" => parts.len() == 1,
-            "focus" => parts.len() == 2,
-            "focus-monitor" => parts.len() == 2,
-            "focus-workspace" => parts.len() == 2 || parts.len() == 3,
-            "move-to-workspace" => parts.len() == 2 || parts.len() == 3,
-            "move-to-workspace-silent" => parts.len() == 2 || parts.len() == 3,
-            "move" => parts.len() == 2 || parts.len() == 3,
-            "insert" => parts.len() == 2,
-            "moveinsert" => parts.len() == 2 || parts.len() == 3,
-            "resize" => parts.len() == 3 || parts.len() == 4,
-            "invert" => parts.len() == 1,
-            "release" => parts.len() == 1,
-            "peek" => parts.len() == 3,
-            "focalize" => parts.len() == 1,
-            "half-focalize" => parts.len() == 1,
-            "cycle-focalized" => parts.len() <= 2,
-            "amplify" => parts.len() == 1,
-            "dumpstateinfo" => parts.len() == 1,
-            "pause" => parts.len() <= 2,
-            "quit" => parts.len() == 1,
-            _ => false,
-        };
+        let valid_len = ACTIONS
+            .iter()
+            .find(|a| a.name == parts[0])
+            .is_some_and(|a| a.accepts(parts.len() - 1));
 
+        let actions: Vec<String> = ACTIONS.iter().map(ActionSyntax::usage).collect();
         let err = format!("Invalid action: {}, valid actions are: {:?}", s, actions.join(", "));
         if !valid_len {
             return Err(serde::de::Error::custom(err.clone()));
@@ -302,6 +464,7 @@ impl<'de> serde::Deserialize<'de> for MondrianMessage {
         match parts[0] {
             "refresh-config" => Ok(MondrianMessage::RefreshConfig),
             "open-config" => Ok(MondrianMessage::OpenConfig),
+            "set-profile" => Ok(MondrianMessage::SetProfile(parts[1].to_string())),
             "retile" => Ok(MondrianMessage::Retile),
             "minimize" => Ok(MondrianMessage::Minimize),
             "close" => Ok(MondrianMessage::Close),
@@ -390,6 +553,13 @@ impl<'de> serde::Deserialize<'de> for MondrianMessage {
             }
             "amplify" => Ok(MondrianMessage::Amplify),
             "dumpstateinfo" => Ok(MondrianMessage::QueryInfo),
+            "explain-window" => {
+                let hwnd: Option<isize> = parts
+                    .get(1)
+                    .map(|v| v.parse().map_err(|_| serde::de::Error::custom(err)))
+                    .transpose()?;
+                Ok(MondrianMessage::ExplainWindow(hwnd.map(WindowRef::from)))
+            }
             "release" => Ok(MondrianMessage::Release(None)),
             "pause" => {
                 let command = match parts.get(1).to_owned() {
@@ -436,6 +606,7 @@ impl Serialize for MondrianMessage {
         match self {
             MondrianMessage::RefreshConfig => serializer.serialize_str("refresh-config"),
             MondrianMessage::OpenConfig => serializer.serialize_str("open-config"),
+            MondrianMessage::SetProfile(name) => serializer.serialize_str(&format!("set-profile {}", name)),
             MondrianMessage::Retile => serializer.serialize_str("retile"),
             MondrianMessage::Minimize => serializer.serialize_str("minimize"),
             MondrianMessage::Focus(dir) => serializer.serialize_str(&format!("focus {}", dir)),
@@ -459,3 +630,42 @@ impl Serialize for MondrianMessage {
         }
     }
 }
+
+impl JsonSchema for MondrianMessage {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Action".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        let actions: Vec<String> = ACTIONS.iter().flat_map(ActionSyntax::schema_values).collect();
+        let patterns = ACTIONS.iter().filter_map(ActionSyntax::schema_pattern);
+        let any_of: Vec<serde_json::Value> = std::iter::once(serde_json::json!({ "enum": actions }))
+            .chain(patterns.map(|p| serde_json::json!({ "pattern": p })))
+            .collect();
+
+        json_schema!({
+            "type": "string",
+            "description": "Action to perform",
+            "anyOf": any_of
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActionSyntax, MondrianMessage, ACTIONS};
+
+    #[test]
+    fn test_schema_actions_parse() {
+        let parse = |s: &str| serde_json::from_value::<MondrianMessage>(serde_json::Value::from(s));
+        let actions: Vec<String> = ACTIONS.iter().flat_map(ActionSyntax::schema_values).collect();
+        for action in actions {
+            assert!(parse(&action).is_ok(), "{action}");
+        }
+
+        assert!(parse("focus-workspace 2 'MONITOR1'").is_ok());
+        assert!(parse("resize left 100 200").is_ok());
+        assert!(parse("resize left").is_err());
+        assert!(parse("unknown").is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use crate::app::configs::AppConfig;
+use crate::app::structs::area::Area;
+use crate::modules::logger::module::info_response_builder::build_info_response;
+use crate::modules::tiles_manager::lib::structs::rules::explain_rules;
+use crate::modules::tiles_manager::lib::structs::rules::get_rules_outcome;
+use crate::modules::tiles_manager::lib::structs::rules::RulesOutcome;
+use crate::win32::api::monitor::Monitor;
+use crate::win32::window::window_obj::WindowObjInfo;
+use serde::Deserialize;
+use serde::Deserializer;
+
+/// Window described by its properties, used to test the rules without opening it
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct WindowDescriptor {
+    pub exename: Option<String>,
+    pub path: Option<String>,
+    pub title: Option<String>,
+    pub classname: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_hex")]
+    pub style: u32,
+    #[serde(default, deserialize_with = "deserialize_hex")]
+    pub exstyle: u32,
+    pub monitor: Option<String>,
+    pub size: Option<(u16, u16)>,
+    #[serde(default)]
+    pub has_owner: bool,
+    #[serde(default)]
+    pub has_parent: bool,
+    /// Expected outcome of the rules (only used in batch mode)
+    #[serde(default)]
+    pub expect: Option<ExpectedOutcome>,
+}
+
+/// Expected outcome of the rules for a window. Only the specified fields are checked.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedOutcome {
+    pub ignored: Option<bool>,
+    pub delayed: Option<bool>,
+    pub floating: Option<bool>,
+    pub monitor: Option<String>,
+    pub workspace: Option<String>,
+}
+
+impl ExpectedOutcome {
+    /// Returns the description of the fields of the outcome that differ from the expected ones
+    fn mismatches(&self, outcome: &RulesOutcome) -> Vec<String> {
+        let mut mismatches = vec![];
+        let mut check = |name: &str, expected: Option<String>, actual: String| {
+            if let Some(expected) = expected.filter(|e| *e != actual) {
+                mismatches.push(format!("{name}: expected {expected}, got {actual}"));
+            }
+        };
+
+        let fmt_opt = |v: &Option<String>| v.clone().unwrap_or("/".to_string());
+        check(
+            "ignored",
+            self.ignored.map(|v| v.to_string()),
+            outcome.ignored.to_string(),
+        );
+        check(
+            "delayed",
+            self.delayed.map(|v| v.to_string()),
+            outcome.delay.is_some().to_string(),
+        );
+        check(
+            "floating",
+            self.floating.map(|v| v.to_string()),
+            outcome.floating.to_string(),
+        );
+        check("monitor", self.monitor.clone(), fmt_opt(&outcome.monitor));
+        check("workspace", self.workspace.clone(), fmt_opt(&outcome.workspace));
+        mismatches
+    }
+}
+
+impl WindowObjInfo for WindowDescriptor {
+    fn get_title(&self) -> Option<String> {
+        Some(self.title.clone().unwrap_or_default())
+    }
+
+    fn get_exe_name(&self) -> Option<String> {
+        let exe_name = self.path.as_ref().and_then(|p| p.split('\\').next_back());
+        Some(self.exename.as_deref().or(exe_name).unwrap_or_default().to_string())
+    }
+
+    fn get_exe_path(&self) -> Option<String> {
+        Some(self.path.clone().or(self.exename.clone()).unwrap_or_default())
+    }
+
+    fn get_class_name(&self) -> Option<String> {
+        Some(self.classname.clone().unwrap_or_default())
+    }
+
+    fn get_area(&self) -> Option<Area> {
+        self.size.map(|(w, h)| Area::new(0, 0, w, h))
+    }
+
+    fn get_visible_area(&self) -> Option<Area> {
+        self.get_area()
+    }
+
+    fn get_borders(&self) -> Option<(i32, i32, i32, i32)> {
+        None
+    }
+
+    fn is_visible(&self) -> bool {
+        true
+    }
+
+    fn is_iconic(&self) -> bool {
+        false
+    }
+
+    fn is_cloaked(&self) -> bool {
+        false
+    }
+
+    fn is_topmost(&self) -> bool {
+        false
+    }
+
+    fn is_fullscreen(&self) -> bool {
+        false
+    }
+
+    fn is_maximized(&self) -> bool {
+        false
+    }
+
+    fn get_window_style(&self) -> u32 {
+        self.style
+    }
+
+    fn get_window_exstyle(&self) -> u32 {
+        self.exstyle
+    }
+
+    fn has_owner(&self) -> bool {
+        self.has_owner
+    }
+
+    fn has_parent(&self) -> bool {
+        self.has_parent
+    }
+
+    fn get_monitor_id(&self, _monitors: &[Monitor]) -> Option<String> {
+        self.monitor.clone()
+    }
+}
+
+/// Parses a window style written as an hexadecimal string (e.g. "16cf0000")
+pub fn parse_hex(value: &str) -> Result<u32, String> {
+    let value = value.trim_start_matches("0x");
+    u32::from_str_radix(value, 16).map_err(|_| format!("'{value}' is not a valid hexadecimal value"))
+}
+
+fn deserialize_hex<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: String = Deserialize::deserialize(deserializer)?;
+    parse_hex(&value).map_err(serde::de::Error::custom)
+}
+
+/// Returns the report of the rules matching the window, along with the resulting outcome. The
+/// `monitor` filters are resolved against the given monitors.
+pub fn explain_window_rules(config: &AppConfig, window: &WindowDescriptor, monitors: &[Monitor]) -> String {
+    let infos = explain_rules(
+        &config.ignore_filter,
+        &config.delayed_filter,
+        &config.rules,
+        window.clone(),
+        monitors,
+    );
+    build_info_response(&infos, 3, "▸")
+}
+
+/// Returns the report of the outcome of the rules for each window, along with the number of
+/// windows whose outcome differs from the expected one
+pub fn test_rules(config: &AppConfig, windows: &[WindowDescriptor], monitors: &[Monitor]) -> (String, usize) {
+    let mut report = String::new();
+    let mut failures = 0;
+    for (i, w) in windows.iter().enumerate() {
+        let outcome = get_rules_outcome(
+            &config.ignore_filter,
+            &config.delayed_filter,
+            &config.rules,
+            w.clone(),
+            monitors,
+        );
+        let mismatches = w.expect.as_ref().map(|e| e.mismatches(&outcome)).unwrap_or_default();
+        let status = match (w.expect.is_some(), mismatches.is_empty()) {
+            (false, _) => "INFO",
+            (true, true) => "PASS",
+            (true, false) => "FAIL",
+        };
+
+        let name = [&w.exename, &w.classname, &w.title]
+            .iter()
+            .filter_map(|v| v.as_ref().map(|v| format!("{v:?}")))
+            .collect::<Vec<String>>()
+            .join(" ");
+        report.push_str(&format!("[{status}] #{} {name} -> {outcome}\n", i + 1));
+        mismatches
+            .iter()
+            .for_each(|m| report.push_str(&format!("       {m}\n")));
+        failures += usize::from(!mismatches.is_empty());
+    }
+
+    (report, failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_hex, test_rules, ExpectedOutcome, WindowDescriptor};
+    use crate::app::configs::rules::{WindowBehavior, WindowRule};
+    use crate::app::configs::AppConfig;
+    use crate::app::structs::win_matcher::{WinMatcher, WinQuery};
+    use crate::app::test_utils::window;
+    use crate::modules::tiles_manager::lib::structs::rules::RulesOutcome;
+    use crate::win32::window::window_obj::WindowObjInfo;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("16cf0000"), Ok(0x16cf0000));
+        assert_eq!(parse_hex("0x16CF0000"), Ok(0x16cf0000));
+        assert!(parse_hex("0xinvalid").is_err());
+        assert!(parse_hex("").is_err());
+    }
+
+    #[test]
+    fn test_exe_name_from_path() {
+        let path = Some("C:\\Program Files\\App\\app.exe".to_string());
+        let w = WindowDescriptor {
+            path: path.clone(),
+            ..Default::default()
+        };
+        assert_eq!(w.get_exe_name(), Some("app.exe".to_string()));
+        assert_eq!(w.get_exe_path(), Some("C:\\Program Files\\App\\app.exe".to_string()));
+
+        let w = WindowDescriptor {
+            path,
+            ..window("other.exe")
+        };
+        assert_eq!(w.get_exe_name(), Some("other.exe".to_string()));
+
+        let w = window("other.exe");
+        assert_eq!(w.get_exe_path(), Some("other.exe".to_string()));
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let outcome = RulesOutcome {
+            floating: true,
+            workspace: Some("2".to_string()),
+            ..Default::default()
+        };
+        let expected = ExpectedOutcome {
+            ignored: Some(false),
+            floating: Some(true),
+            monitor: Some("MONITOR1".to_string()),
+            workspace: Some("3".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            expected.mismatches(&outcome),
+            ["monitor: expected MONITOR1, got /", "workspace: expected 3, got 2"]
+        );
+        assert!(ExpectedOutcome::default().mismatches(&outcome).is_empty());
+    }
+
+    #[test]
+    fn test_test_rules() {
+        let insert = WindowBehavior::Insert {
+            monitor: None,
+            workspace: Some("2".to_string()),
+            silent: false,
+        };
+        let config = AppConfig {
+            ignore_filter: WinMatcher::Exename(WinQuery::contains("ignored.exe")),
+            delayed_filter: vec![],
+            rules: vec![WindowRule {
+                filter: WinMatcher::Exename(WinQuery::contains("app.exe")),
+                behavior: insert,
+                priority: 0,
+                stop: false,
+            }],
+            ..Default::default()
+        };
+        let windows = [
+            WindowDescriptor {
+                path: Some("C:\\App\\app.exe".to_string()),
+                expect: Some(ExpectedOutcome {
+                    workspace: Some("2".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            WindowDescriptor {
+                expect: Some(ExpectedOutcome {
+                    ignored: Some(false),
+                    ..Default::default()
+                }),
+                ..window("ignored.exe")
+            },
+            window("other.exe"),
+        ];
+
+        let (report, failures) = test_rules(&config, &windows, &[]);
+        let statuses: Vec<&str> = report.lines().filter(|l| l.starts_with('[')).map(|l| &l[..6]).collect();
+        assert_eq!(statuses, ["[PASS]", "[FAIL]", "[INFO]"]);
+        assert!(report.contains("ignored: expected false, got true"));
+        assert_eq!(failures, 1);
+    }
+}
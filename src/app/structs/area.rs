@@ -281,7 +281,7 @@ impl From<Area> for (i32, i32, i32, i32) {
 
 #[cfg(test)]
 mod tests {
-    use super::{Area, Orientation};
+    use super::{Area, Orientation, Paddings};
 
     #[test]
     fn test_area() {
@@ -326,4 +326,13 @@ mod tests {
         assert_eq!(area1.get_center(), (50, 50));
         assert_eq!(area2.get_center(), (49, 49));
     }
+
+    #[test]
+    fn test_with_scaled_paddings() {
+        let area = Area::new(0, 0, 1920, 1080);
+        let paddings = Paddings::new(140, 100, 140, 100).scaled(2.5);
+
+        assert_eq!(paddings, Paddings::new(350, 250, 350, 250));
+        assert_eq!(area.with_paddings(paddings), Area::new(250, 350, 1420, 380));
+    }
 }
@@ -0,0 +1,162 @@
+use crate::win32::api::monitor::Monitor;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Monitor name, as a monitor id or a positional selector
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonitorSelector {
+    Id(String),
+    Primary,
+    Leftmost,
+    Rightmost,
+    Index(usize),
+    Portrait,
+    Landscape,
+}
+
+impl MonitorSelector {
+    /// Returns the id of the selected monitor, if connected. Monitors are ordered by their
+    /// position, from left to right and then from top to bottom.
+    pub fn resolve(&self, monitors: &[Monitor]) -> Option<String> {
+        let mut monitors: Vec<&Monitor> = monitors.iter().collect();
+        monitors.sort_by_key(|m| (m.monitor_area.x, m.monitor_area.y));
+
+        let is_portrait = |m: &&&Monitor| m.monitor_area.height > m.monitor_area.width;
+        let monitor = match self {
+            MonitorSelector::Id(id) => monitors
+                .iter()
+                .find(|m| m.id.eq_ignore_ascii_case(id) || m.hw_id.eq_ignore_ascii_case(id)),
+            MonitorSelector::Primary => monitors.iter().find(|m| m.primary),
+            MonitorSelector::Leftmost => monitors.first(),
+            MonitorSelector::Rightmost => monitors.last(),
+            MonitorSelector::Index(i) => i.checked_sub(1).and_then(|i| monitors.get(i)),
+            MonitorSelector::Portrait => monitors.iter().find(is_portrait),
+            MonitorSelector::Landscape => monitors.iter().find(|m| !is_portrait(m)),
+        };
+
+        monitor.map(|m| m.id.clone())
+    }
+}
+
+impl FromStr for MonitorSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let selector = match s.to_lowercase().as_str() {
+            "primary" => MonitorSelector::Primary,
+            "leftmost" => MonitorSelector::Leftmost,
+            "rightmost" => MonitorSelector::Rightmost,
+            "portrait" => MonitorSelector::Portrait,
+            "landscape" => MonitorSelector::Landscape,
+            s => match s.strip_prefix("index:") {
+                Some(i) => MonitorSelector::Index(i.parse().map_err(|_| format!("Invalid monitor index: {i}"))?),
+                None => MonitorSelector::Id(s.to_string()),
+            },
+        };
+        Ok(selector)
+    }
+}
+
+/// Returns the id of the monitor with the given name, which can be a monitor id, an alias (see
+/// `monitor_aliases`) or a positional selector
+pub fn resolve_monitor(name: &str, aliases: &HashMap<String, String>, monitors: &[Monitor]) -> Option<String> {
+    let name = aliases
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+        .map_or(name, |(_, target)| target.as_str());
+    MonitorSelector::from_str(name).ok()?.resolve(monitors)
+}
+
+/// Returns the id of the monitor receiving the windows of a disconnected monitor: the one with the
+/// given name or, if it is not connected, the primary monitor
+pub fn resolve_fallback_monitor(name: &str, aliases: &HashMap<String, String>, monitors: &[Monitor]) -> Option<String> {
+    resolve_monitor(name, aliases, monitors).or_else(|| MonitorSelector::Primary.resolve(monitors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resolve_fallback_monitor;
+    use super::resolve_monitor;
+    use super::MonitorSelector;
+    use crate::app::structs::area::Area;
+    use crate::app::test_utils::monitor;
+    use crate::win32::api::monitor::Monitor;
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    fn portrait(id: &str, x: i32) -> Monitor {
+        Monitor {
+            monitor_area: Area::new(x, 0, 1080, 1920),
+            workspace_area: Area::new(x, 0, 1080, 1880),
+            ..monitor(id, x, false)
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let parse = |s: &str| MonitorSelector::from_str(s);
+        assert_eq!(parse("primary"), Ok(MonitorSelector::Primary));
+        assert_eq!(parse("LeftMost"), Ok(MonitorSelector::Leftmost));
+        assert_eq!(parse("rightmost"), Ok(MonitorSelector::Rightmost));
+        assert_eq!(parse("portrait"), Ok(MonitorSelector::Portrait));
+        assert_eq!(parse("landscape"), Ok(MonitorSelector::Landscape));
+        assert_eq!(parse("index:2"), Ok(MonitorSelector::Index(2)));
+        assert_eq!(parse("MONITOR1"), Ok(MonitorSelector::Id("monitor1".to_string())));
+        assert!(parse("index:two").is_err());
+        assert!(parse("index:-1").is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let monitors = [
+            monitor("MONITOR2", 1920, true),
+            portrait("MONITOR3", 3840),
+            monitor("MONITOR1", 0, false),
+        ];
+        let resolve = |s: &str| MonitorSelector::from_str(s).unwrap().resolve(&monitors);
+
+        assert_eq!(resolve("monitor3"), Some("MONITOR3".to_string()));
+        assert_eq!(resolve("hw_monitor1"), Some("MONITOR1".to_string()));
+        assert_eq!(resolve("primary"), Some("MONITOR2".to_string()));
+        assert_eq!(resolve("leftmost"), Some("MONITOR1".to_string()));
+        assert_eq!(resolve("rightmost"), Some("MONITOR3".to_string()));
+        assert_eq!(resolve("index:2"), Some("MONITOR2".to_string()));
+        assert_eq!(resolve("portrait"), Some("MONITOR3".to_string()));
+        assert_eq!(resolve("landscape"), Some("MONITOR1".to_string()));
+        assert_eq!(resolve("index:0"), None);
+        assert_eq!(resolve("index:4"), None);
+        assert_eq!(resolve("MONITOR4"), None);
+    }
+
+    #[test]
+    fn test_resolve_monitor_with_aliases() {
+        let monitors = [monitor("MONITOR1", 0, true), monitor("MONITOR2", 1920, false)];
+        let aliases = HashMap::from([("Side".to_string(), "rightmost".to_string())]);
+
+        assert_eq!(
+            resolve_monitor("side", &aliases, &monitors),
+            Some("MONITOR2".to_string())
+        );
+        assert_eq!(
+            resolve_monitor("MONITOR1", &aliases, &monitors),
+            Some("MONITOR1".to_string())
+        );
+        assert_eq!(resolve_monitor("other", &aliases, &monitors), None);
+    }
+
+    #[test]
+    fn test_resolve_fallback_monitor() {
+        let monitors = [monitor("MONITOR1", 0, false), monitor("MONITOR2", 1920, true)];
+        let aliases = HashMap::from([("main".to_string(), "MONITOR1".to_string())]);
+
+        assert_eq!(
+            resolve_fallback_monitor("main", &aliases, &monitors),
+            Some("MONITOR1".to_string())
+        );
+        assert_eq!(
+            resolve_fallback_monitor("MONITOR3", &aliases, &monitors),
+            Some("MONITOR2".to_string())
+        );
+        assert_eq!(resolve_fallback_monitor("MONITOR3", &aliases, &[]), None);
+    }
+}
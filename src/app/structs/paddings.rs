@@ -1,16 +1,17 @@
 use serde::Deserialize;
 use serde::Serialize;
+use serde::Serializer;
 
-#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq)]
+#[derive(Deserialize, Default, Clone, Copy, Debug, PartialEq)]
 pub struct Paddings {
-    pub left: u8,
-    pub right: u8,
-    pub top: u8,
-    pub bottom: u8,
+    pub left: i16,
+    pub right: i16,
+    pub top: i16,
+    pub bottom: i16,
 }
 
 impl Paddings {
-    pub fn new(top: u8, right: u8, bottom: u8, left: u8) -> Self {
+    pub fn new(top: i16, right: i16, bottom: i16, left: i16) -> Self {
         Paddings {
             left,
             right,
@@ -19,7 +20,29 @@ impl Paddings {
         }
     }
 
-    pub fn full(padding: u8) -> Self {
+    pub fn full(padding: i16) -> Self {
         Paddings::new(padding, padding, padding, padding)
     }
+
+    /// Returns the paddings multiplied by the given factor
+    pub fn scaled(&self, factor: f32) -> Self {
+        let scale = |p: i16| (p as f32 * factor).round() as i16;
+        Paddings::new(scale(self.top), scale(self.right), scale(self.bottom), scale(self.left))
+    }
+}
+
+impl Serialize for Paddings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Paddings {
+            left,
+            right,
+            top,
+            bottom,
+        } = *self;
+        match (top == bottom, left == right) {
+            (true, true) if top == left => serializer.serialize_i16(top),
+            (true, true) => [top, left].serialize(serializer),
+            _ => [top, right, bottom, left].serialize(serializer),
+        }
+    }
 }
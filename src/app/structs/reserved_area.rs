@@ -0,0 +1,36 @@
+use super::area::Area;
+use crate::app::configs::deserializers;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Space of the monitor reserved to external bars, excluded from the tiling area (in pixels)
+#[derive(Deserialize, Serialize, JsonSchema, Default, Clone, Copy, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReservedArea {
+    /// Space reserved at the top of the monitor
+    #[serde(deserialize_with = "deserializers::to_u16_max::<2000,_>")]
+    #[schemars(range(max = 2000))]
+    pub top: u16,
+    /// Space reserved at the bottom of the monitor
+    #[serde(deserialize_with = "deserializers::to_u16_max::<2000,_>")]
+    #[schemars(range(max = 2000))]
+    pub bottom: u16,
+    /// Space reserved on the left side of the monitor
+    #[serde(deserialize_with = "deserializers::to_u16_max::<2000,_>")]
+    #[schemars(range(max = 2000))]
+    pub left: u16,
+    /// Space reserved on the right side of the monitor
+    #[serde(deserialize_with = "deserializers::to_u16_max::<2000,_>")]
+    #[schemars(range(max = 2000))]
+    pub right: u16,
+}
+
+impl ReservedArea {
+    /// Returns the given area without the reserved space
+    pub fn apply(&self, area: Area) -> Area {
+        let (top, bottom) = (self.top as i16, self.bottom as i16);
+        let (left, right) = (self.left as i16, self.right as i16);
+        area.pad((left, right), (top, bottom))
+    }
+}
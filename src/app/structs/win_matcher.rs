@@ -1,19 +1,136 @@
+use crate::app::configs::schemas;
+use crate::app::structs::area::Area;
+use crate::app::structs::monitor_selector::MonitorSelector;
+use crate::win32::api::monitor::enum_display_monitors;
+use crate::win32::api::monitor::Monitor;
 use crate::win32::window::window_obj::WindowObjInfo;
+use schemars::JsonSchema;
+use schemars::Schema;
+use schemars::SchemaGenerator;
 use serde::Deserialize;
 use serde::Serialize;
+use serde::Serializer;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(from = "WinMatcherExt")]
+#[serde(try_from = "WinMatcherExt")]
 pub enum WinMatcher {
-    Exename(String),
-    Title(String),
-    Classname(String),
-    Style(String),
+    Exename(WinQuery),
+    Path(WinQuery),
+    Title(WinQuery),
+    Classname(WinQuery),
+    Style(WinQuery),
+    Exstyle(WinQuery),
+    Monitor(WinQuery),
+    HasOwner(bool),
+    HasParent(bool),
+    MinSize(u16, u16),
+    MaxSize(u16, u16),
+    Not(Box<WinMatcher>),
     Any(HashSet<WinMatcher>),
     All(HashSet<WinMatcher>),
 }
 
+/// Value a window property is matched against. Values enclosed in slashes are compiled to
+/// regexes, shared between the clones of the query.
+#[derive(Clone, Debug)]
+pub struct WinQuery {
+    query: String,
+    exact: bool,
+    ignore_case: bool,
+    regex: Option<Arc<regex::Regex>>,
+    /// Query compared with the values, lowercased if the case is ignored
+    needle: String,
+}
+
+impl WinQuery {
+    /// Creates a query matching the values that contain the given one
+    pub fn contains(value: &str) -> WinQuery {
+        WinQuery::plain(value.to_string(), false, false)
+    }
+
+    fn plain(query: String, exact: bool, ignore_case: bool) -> WinQuery {
+        let needle = match ignore_case {
+            true => query.to_lowercase(),
+            false => query.clone(),
+        };
+        WinQuery {
+            query,
+            exact,
+            ignore_case,
+            regex: None,
+            needle,
+        }
+    }
+
+    /// Creates a query from its textual representation, compiling the regex (if any).
+    /// If `exact` is true, the whole value must match the query, otherwise a part of it is enough.
+    pub fn parse(query: &str, exact: bool, ignore_case: bool) -> Result<WinQuery, String> {
+        if !(query.len() > 1 && query.starts_with('/') && query.ends_with('/')) {
+            return Ok(WinQuery::plain(query.to_string(), exact, ignore_case));
+        }
+
+        let pattern = &query[1..query.len() - 1];
+        let pattern = match exact {
+            true => format!("^(?:{pattern})$"),
+            false => pattern.to_string(),
+        };
+        let regex = regex::RegexBuilder::new(&pattern)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_err(|e| format!("invalid regex {query}: {e}"))?;
+        Ok(WinQuery {
+            regex: Some(Arc::new(regex)),
+            ..WinQuery::plain(query.to_string(), exact, ignore_case)
+        })
+    }
+
+    /// Returns the monitor selected by the query, unless it is a regex
+    fn monitor_selector(&self) -> Option<MonitorSelector> {
+        match self.regex {
+            Some(_) => None,
+            None => MonitorSelector::from_str(&self.query).ok(),
+        }
+    }
+
+    fn matches(&self, value: &Option<String>) -> bool {
+        let Some(value) = value else {
+            return true;
+        };
+
+        if let Some(re) = &self.regex {
+            return re.is_match(value);
+        }
+
+        let value = match self.ignore_case {
+            true => Cow::Owned(value.to_lowercase()),
+            false => Cow::Borrowed(value.as_str()),
+        };
+        match self.exact {
+            true => value == self.needle,
+            false => value.contains(&self.needle),
+        }
+    }
+}
+
+impl PartialEq for WinQuery {
+    fn eq(&self, other: &Self) -> bool {
+        self.query == other.query && self.exact == other.exact && self.ignore_case == other.ignore_case
+    }
+}
+
+impl Eq for WinQuery {}
+
+impl Serialize for WinQuery {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.query)
+    }
+}
+
 impl Default for WinMatcher {
     fn default() -> Self {
         WinMatcher::Any(HashSet::new())
@@ -21,53 +138,189 @@ impl Default for WinMatcher {
 }
 
 impl WinMatcher {
-    fn match_value(&self, query: &str, value: &Option<String>) -> bool {
-        match query.starts_with('/') && query.ends_with('/') {
-            true => {
-                let re = regex::Regex::new(&query[1..query.len() - 1]).unwrap();
-                value.as_ref().is_none_or(|v| re.is_match(v))
-            }
-            false => value.as_ref().is_none_or(|v| v.contains(query)),
-        }
-    }
-
+    /// Returns whether the window matches the filter. The connected monitors are enumerated only if
+    /// a `monitor` filter is evaluated.
     pub fn matches<T: WindowObjInfo>(&self, window: T) -> bool {
         self.matches_internal(&mut window.into())
     }
 
-    fn matches_internal<T: WindowObjInfo>(&self, window: &mut WinMatcherTarget<T>) -> bool {
+    /// Returns whether the window matches the filter, resolving the `monitor` filters against the
+    /// given monitors
+    pub fn matches_on<T: WindowObjInfo>(&self, window: T, monitors: &[Monitor]) -> bool {
+        self.matches_internal(&mut WinMatcherTarget::with_monitors(window, monitors))
+    }
+
+    /// Returns the filter with the monitor aliases (see `monitor_aliases`) of its `monitor` filters
+    /// replaced by their targets
+    pub fn with_monitor_aliases(&self, aliases: &HashMap<String, String>) -> WinMatcher {
+        let replace = |filters: &HashSet<WinMatcher>| filters.iter().map(|f| f.with_monitor_aliases(aliases)).collect();
         match self {
-            WinMatcher::Exename(query) => self.match_value(query, window.exe_name()),
-            WinMatcher::Title(query) => self.match_value(query, window.title()),
-            WinMatcher::Classname(query) => self.match_value(query, window.class_name()),
-            WinMatcher::Style(query) => self.match_value(query, &Some(window.style())),
+            WinMatcher::Monitor(query) if query.regex.is_none() => {
+                let target = aliases.iter().find(|(a, _)| a.eq_ignore_ascii_case(&query.query));
+                let query = target.map_or(query.clone(), |(_, target)| {
+                    WinQuery::plain(target.clone(), query.exact, query.ignore_case)
+                });
+                WinMatcher::Monitor(query)
+            }
+            WinMatcher::Not(filter) => WinMatcher::Not(Box::new(filter.with_monitor_aliases(aliases))),
+            WinMatcher::Any(filters) => WinMatcher::Any(replace(filters)),
+            WinMatcher::All(filters) => WinMatcher::All(replace(filters)),
+            filter => filter.clone(),
+        }
+    }
+
+    fn matches_internal<T: WindowObjInfo>(&self, window: &mut WinMatcherTarget<'_, T>) -> bool {
+        match self {
+            WinMatcher::Exename(query) => query.matches(window.exe_name()),
+            WinMatcher::Path(query) => query.matches(window.exe_path()),
+            WinMatcher::Title(query) => query.matches(window.title()),
+            WinMatcher::Classname(query) => query.matches(window.class_name()),
+            WinMatcher::Style(query) => query.matches(&Some(window.style())),
+            WinMatcher::Exstyle(query) => query.matches(&Some(window.exstyle())),
+            WinMatcher::Monitor(query) => match query.monitor_selector().and_then(|s| s.resolve(window.monitors())) {
+                Some(id) => window.monitor().as_ref().is_none_or(|m| *m == id),
+                None => query.matches(window.monitor()),
+            },
+            WinMatcher::HasOwner(owned) => window.win_obj.has_owner() == *owned,
+            WinMatcher::HasParent(parented) => window.win_obj.has_parent() == *parented,
+            WinMatcher::MinSize(w, h) => window.area().is_none_or(|a| a.width >= *w && a.height >= *h),
+            WinMatcher::MaxSize(w, h) => window.area().is_none_or(|a| a.width <= *w && a.height <= *h),
+            WinMatcher::Not(filter) => !filter.matches_internal(window),
             WinMatcher::Any(filters) => filters.iter().any(|f| f.matches_internal(window)),
             WinMatcher::All(filters) => !filters.is_empty() && filters.iter().all(|f| f.matches_internal(window)),
         }
     }
 }
 
-pub struct WinMatcherTarget<T: WindowObjInfo> {
+impl WinMatcher {
+    /// Returns whether the window matches the filter, along with the description of the
+    /// sub-filter that decided it
+    pub fn explain<T: WindowObjInfo>(&self, window: T, monitors: &[Monitor]) -> (bool, String) {
+        self.explain_internal(&mut WinMatcherTarget::with_monitors(window, monitors))
+    }
+
+    fn explain_internal<T: WindowObjInfo>(&self, window: &mut WinMatcherTarget<'_, T>) -> (bool, String) {
+        match self {
+            WinMatcher::Not(filter) => {
+                let (matched, reason) = filter.explain_internal(window);
+                (!matched, format!("not ({reason})"))
+            }
+            WinMatcher::Any(filters) => filters
+                .iter()
+                .map(|f| f.explain_internal(window))
+                .find(|(matched, _)| *matched)
+                .unwrap_or((false, "none of the filters matched".to_string())),
+            WinMatcher::All(filters) if filters.is_empty() => (false, "empty filter".to_string()),
+            WinMatcher::All(filters) => filters
+                .iter()
+                .map(|f| f.explain_internal(window))
+                .find(|(matched, _)| !*matched)
+                .unwrap_or((true, "all the filters matched".to_string())),
+            filter => {
+                let matched = filter.matches_internal(window);
+                let value = match filter {
+                    WinMatcher::Exename(_) => fmt_value(window.exe_name()),
+                    WinMatcher::Path(_) => fmt_value(window.exe_path()),
+                    WinMatcher::Title(_) => fmt_value(window.title()),
+                    WinMatcher::Classname(_) => fmt_value(window.class_name()),
+                    WinMatcher::Style(_) => fmt_value(&Some(window.style())),
+                    WinMatcher::Exstyle(_) => fmt_value(&Some(window.exstyle())),
+                    WinMatcher::Monitor(_) => fmt_value(window.monitor()),
+                    WinMatcher::HasOwner(_) => window.win_obj.has_owner().to_string(),
+                    WinMatcher::HasParent(_) => window.win_obj.has_parent().to_string(),
+                    _ => window
+                        .area()
+                        .map_or("/".to_string(), |a| format!("[{}, {}]", a.width, a.height)),
+                };
+                (matched, format!("{filter} (window: {value})"))
+            }
+        }
+    }
+}
+
+fn fmt_value(value: &Option<String>) -> String {
+    value.as_ref().map_or("/".to_string(), |v| format!("{v:?}"))
+}
+
+impl std::fmt::Display for WinQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.query)?;
+        match (self.exact, self.ignore_case) {
+            (true, true) => write!(f, " [exact, ignore_case]"),
+            (true, false) => write!(f, " [exact]"),
+            (false, true) => write!(f, " [ignore_case]"),
+            (false, false) => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for WinMatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sorted = |filters: &HashSet<WinMatcher>| {
+            let mut filters: Vec<String> = filters.iter().map(|m| m.to_string()).collect();
+            filters.sort();
+            filters
+        };
+        match self {
+            WinMatcher::Exename(q) => write!(f, "exename = {q}"),
+            WinMatcher::Path(q) => write!(f, "path = {q}"),
+            WinMatcher::Title(q) => write!(f, "title = {q}"),
+            WinMatcher::Classname(q) => write!(f, "classname = {q}"),
+            WinMatcher::Style(q) => write!(f, "style = {q}"),
+            WinMatcher::Exstyle(q) => write!(f, "exstyle = {q}"),
+            WinMatcher::Monitor(q) => write!(f, "monitor = {q}"),
+            WinMatcher::HasOwner(b) => write!(f, "has_owner = {b}"),
+            WinMatcher::HasParent(b) => write!(f, "has_parent = {b}"),
+            WinMatcher::MinSize(w, h) => write!(f, "min_size = [{w}, {h}]"),
+            WinMatcher::MaxSize(w, h) => write!(f, "max_size = [{w}, {h}]"),
+            WinMatcher::Not(m) => write!(f, "not = {{ {m} }}"),
+            WinMatcher::Any(ms) => {
+                let filters: Vec<String> = sorted(ms).into_iter().map(|m| format!("{{ {m} }}")).collect();
+                write!(f, "any = [{}]", filters.join(", "))
+            }
+            WinMatcher::All(ms) => write!(f, "{}", sorted(ms).join(", ")),
+        }
+    }
+}
+
+pub struct WinMatcherTarget<'a, T: WindowObjInfo> {
     win_obj: T,
+    monitors: Option<Cow<'a, [Monitor]>>,
     title: Option<Option<String>>,
     class_name: Option<Option<String>>,
     exe_name: Option<Option<String>>,
+    exe_path: Option<Option<String>>,
     style: Option<String>,
+    exstyle: Option<String>,
+    area: Option<Option<Area>>,
+    monitor: Option<Option<String>>,
 }
 
-impl<T: WindowObjInfo> From<T> for WinMatcherTarget<T> {
+impl<T: WindowObjInfo> From<T> for WinMatcherTarget<'_, T> {
     fn from(value: T) -> Self {
         WinMatcherTarget {
             win_obj: value,
+            monitors: None,
             title: None,
             class_name: None,
             exe_name: None,
+            exe_path: None,
             style: None,
+            exstyle: None,
+            area: None,
+            monitor: None,
         }
     }
 }
 
-impl<T: WindowObjInfo> WinMatcherTarget<T> {
+impl<'a, T: WindowObjInfo> WinMatcherTarget<'a, T> {
+    pub fn with_monitors(win_obj: T, monitors: &'a [Monitor]) -> Self {
+        WinMatcherTarget {
+            monitors: Some(Cow::Borrowed(monitors)),
+            ..WinMatcherTarget::from(win_obj)
+        }
+    }
+
     pub fn title(&mut self) -> &Option<String> {
         self.title.get_or_insert(self.win_obj.get_title())
     }
@@ -80,11 +333,37 @@ impl<T: WindowObjInfo> WinMatcherTarget<T> {
         self.exe_name.get_or_insert(self.win_obj.get_exe_name())
     }
 
+    pub fn exe_path(&mut self) -> &Option<String> {
+        self.exe_path.get_or_insert(self.win_obj.get_exe_path())
+    }
+
     pub fn style(&mut self) -> String {
         self.style
             .get_or_insert(format!("{:x}", self.win_obj.get_window_style()))
             .clone()
     }
+
+    pub fn exstyle(&mut self) -> String {
+        self.exstyle
+            .get_or_insert(format!("{:x}", self.win_obj.get_window_exstyle()))
+            .clone()
+    }
+
+    pub fn area(&mut self) -> Option<Area> {
+        *self.area.get_or_insert(self.win_obj.get_area())
+    }
+
+    /// Returns the connected monitors, enumerating them if they were not given
+    pub fn monitors(&mut self) -> &[Monitor] {
+        self.monitors.get_or_insert_with(|| Cow::Owned(enum_display_monitors()))
+    }
+
+    /// Returns the id of the monitor the window is on (e.g. "MONITOR1")
+    pub fn monitor(&mut self) -> &Option<String> {
+        let monitors = self.monitors.get_or_insert_with(|| Cow::Owned(enum_display_monitors()));
+        self.monitor
+            .get_or_insert_with(|| self.win_obj.get_monitor_id(monitors))
+    }
 }
 
 impl std::hash::Hash for WinMatcher {
@@ -93,44 +372,268 @@ impl std::hash::Hash for WinMatcher {
     }
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+impl JsonSchema for WinMatcher {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "WinMatcher".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        WinMatcherExt::json_schema(generator)
+    }
+}
+
+/// Filter to match windows. A window matches if it matches all the specified fields.
+/// Values enclosed in slashes (e.g. "/Value[0-9]/") are treated as regexes
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
 #[serde(deny_unknown_fields)]
+#[schemars(extend("minProperties" = 1))]
 struct WinMatcherExt {
+    /// Class name of the window to match
     pub classname: Option<String>,
+    /// Executable name of the window to match
     pub exename: Option<String>,
+    /// Full path of the executable of the window to match
+    pub path: Option<String>,
+    /// Title of the window to match
     pub title: Option<String>,
+    /// Style of the window to match
     pub style: Option<String>,
+    /// Extended style of the window to match
+    pub exstyle: Option<String>,
+    /// Monitor the window is on, as a monitor id (e.g. "MONITOR1"), alias or positional selector
+    pub monitor: Option<String>,
+    /// If true, matches the windows with an owner window (e.g. dialogs), if false the ones without
+    pub has_owner: Option<bool>,
+    /// If true, matches the windows with a parent window, if false the ones without
+    pub has_parent: Option<bool>,
+    /// Minimum size of the window (in pixels), as [width, height]
+    #[serde(default)]
+    #[schemars(schema_with = "schemas::window_size")]
+    pub min_size: Option<(u16, u16)>,
+    /// Maximum size of the window (in pixels), as [width, height]
+    #[serde(default)]
+    #[schemars(schema_with = "schemas::window_size")]
+    pub max_size: Option<(u16, u16)>,
+    /// If true, the string fields must match the whole value instead of a part of it
+    #[serde(default)]
+    pub exact: bool,
+    /// If true, the string fields are matched case-insensitively
+    #[serde(default)]
+    pub ignore_case: bool,
+    /// Matches if the window matches at least one of the filters
+    pub any: Option<Vec<WinMatcher>>,
+    /// Matches if the window matches all the filters
+    pub all: Option<Vec<WinMatcher>>,
+    /// Matches if the window doesn't match the filter
+    pub not: Option<Box<WinMatcher>>,
 }
 
-impl From<&WinMatcherExt> for WinMatcher {
-    fn from(v: &WinMatcherExt) -> Self {
+impl TryFrom<&WinMatcherExt> for WinMatcher {
+    type Error = String;
+
+    fn try_from(v: &WinMatcherExt) -> Result<Self, Self::Error> {
         let mut matchers: Vec<WinMatcher> = Vec::new();
+        let parse = |field: &str, query: &str| {
+            WinQuery::parse(query, v.exact, v.ignore_case).map_err(|e| format!("`{field}`: {e}"))
+        };
 
         if let Some(exename) = &v.exename {
-            matchers.push(WinMatcher::Exename(exename.clone()));
+            matchers.push(WinMatcher::Exename(parse("exename", exename)?));
+        }
+
+        if let Some(path) = &v.path {
+            matchers.push(WinMatcher::Path(parse("path", path)?));
         }
 
         if let Some(classname) = &v.classname {
-            matchers.push(WinMatcher::Classname(classname.clone()));
+            matchers.push(WinMatcher::Classname(parse("classname", classname)?));
         }
 
         if let Some(title) = &v.title {
-            matchers.push(WinMatcher::Title(title.clone()));
+            matchers.push(WinMatcher::Title(parse("title", title)?));
         }
 
         if let Some(style) = &v.style {
-            matchers.push(WinMatcher::Style(style.clone()));
+            matchers.push(WinMatcher::Style(parse("style", style)?));
         }
 
-        match matchers.len() == 1 {
-            true => matchers[0].clone(),
-            false => WinMatcher::All(matchers.into_iter().collect()),
+        if let Some(exstyle) = &v.exstyle {
+            matchers.push(WinMatcher::Exstyle(parse("exstyle", exstyle)?));
+        }
+
+        if let Some(monitor) = &v.monitor {
+            matchers.push(WinMatcher::Monitor(parse("monitor", monitor)?));
+        }
+
+        if let Some(owned) = v.has_owner {
+            matchers.push(WinMatcher::HasOwner(owned));
+        }
+
+        if let Some(parented) = v.has_parent {
+            matchers.push(WinMatcher::HasParent(parented));
+        }
+
+        if let Some((w, h)) = v.min_size {
+            matchers.push(WinMatcher::MinSize(w, h));
+        }
+
+        if let Some((w, h)) = v.max_size {
+            matchers.push(WinMatcher::MaxSize(w, h));
+        }
+
+        if let Some(any) = &v.any {
+            if any.is_empty() {
+                return Err("`any` must contain at least one filter".to_string());
+            }
+            matchers.push(WinMatcher::Any(any.iter().cloned().collect()));
+        }
+
+        if let Some(all) = &v.all {
+            if all.is_empty() {
+                return Err("`all` must contain at least one filter".to_string());
+            }
+            matchers.extend(all.iter().cloned());
+        }
+
+        if let Some(not) = &v.not {
+            matchers.push(WinMatcher::Not(not.clone()));
+        }
+
+        match matchers.len() {
+            0 => Err("A filter must contain at least one window property".to_string()),
+            1 => Ok(matchers[0].clone()),
+            _ => Ok(WinMatcher::All(matchers.into_iter().collect())),
         }
     }
 }
 
-impl From<WinMatcherExt> for WinMatcher {
-    fn from(v: WinMatcherExt) -> Self {
-        WinMatcher::from(&v)
+impl TryFrom<WinMatcherExt> for WinMatcher {
+    type Error = String;
+
+    fn try_from(v: WinMatcherExt) -> Result<Self, Self::Error> {
+        WinMatcher::try_from(&v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WinMatcher, WinQuery};
+    use crate::app::rules_tester::WindowDescriptor;
+    use crate::app::test_utils::{monitor, window};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn monitor_filter(query: &str) -> WinMatcher {
+        WinMatcher::Monitor(WinQuery::parse(query, false, false).unwrap())
+    }
+
+    fn filter(value: serde_json::Value) -> Result<WinMatcher, String> {
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
+    fn titled_window(exename: &str, title: &str, size: (u16, u16)) -> WindowDescriptor {
+        WindowDescriptor {
+            title: Some(title.to_string()),
+            size: Some(size),
+            ..window(exename)
+        }
+    }
+
+    #[test]
+    fn test_exact_and_ignore_case() {
+        let window = titled_window("firefox.exe", "Mozilla Firefox", (800, 600));
+        let matches = |value: serde_json::Value| filter(value).unwrap().matches(window.clone());
+
+        assert!(matches(json!({ "title": "Firefox" })));
+        assert!(!matches(json!({ "title": "firefox" })));
+        assert!(matches(json!({ "title": "firefox", "ignore_case": true })));
+        assert!(!matches(json!({ "title": "Firefox", "exact": true })));
+        assert!(matches(
+            json!({ "title": "mozilla firefox", "exact": true, "ignore_case": true })
+        ));
+        assert!(matches(
+            json!({ "title": "/^moz.*fox$/", "exact": true, "ignore_case": true })
+        ));
+        assert!(!matches(json!({ "title": "/^Moz/", "exact": true })));
+    }
+
+    #[test]
+    fn test_any_all_not() {
+        let window = titled_window("app.exe", "Main", (800, 600));
+        let matches = |value: serde_json::Value| filter(value).unwrap().matches(window.clone());
+
+        assert!(matches(json!({ "exename": "app.exe", "title": "Main" })));
+        assert!(!matches(json!({ "exename": "app.exe", "title": "Other" })));
+        assert!(matches(
+            json!({ "any": [{ "exename": "other.exe" }, { "title": "Main" }] })
+        ));
+        assert!(!matches(
+            json!({ "any": [{ "exename": "other.exe" }, { "title": "Other" }] })
+        ));
+        assert!(matches(json!({ "all": [{ "exename": "app" }, { "title": "Ma" }] })));
+        assert!(!matches(json!({ "all": [{ "exename": "app" }, { "title": "Other" }] })));
+        assert!(matches(json!({ "not": { "exename": "other.exe" } })));
+        assert!(!matches(
+            json!({ "not": { "any": [{ "exename": "other.exe" }, { "title": "Main" }] } })
+        ));
+    }
+
+    #[test]
+    fn test_size_filters() {
+        let window = titled_window("app.exe", "Main", (800, 600));
+        let matches = |value: serde_json::Value| filter(value).unwrap().matches(window.clone());
+
+        assert!(matches(json!({ "min_size": [640, 480] })));
+        assert!(matches(json!({ "min_size": [800, 600] })));
+        assert!(!matches(json!({ "min_size": [1024, 480] })));
+        assert!(matches(json!({ "max_size": [1024, 768] })));
+        assert!(!matches(json!({ "max_size": [640, 768] })));
+        assert!(matches(json!({ "min_size": [640, 480], "max_size": [1024, 768] })));
+    }
+
+    #[test]
+    fn test_empty_filters() {
+        let error = filter(json!({ "any": [] })).unwrap_err();
+        assert!(error.contains("`any` must contain at least one filter"), "{error}");
+
+        let error = filter(json!({ "all": [] })).unwrap_err();
+        assert!(error.contains("`all` must contain at least one filter"), "{error}");
+
+        let error = filter(json!({ "exact": true })).unwrap_err();
+        assert!(error.contains("at least one window property"), "{error}");
+    }
+
+    #[test]
+    fn test_monitor_filter() {
+        let monitors = [monitor("MONITOR1", 0, true), monitor("MONITOR2", 1920, false)];
+        let window = WindowDescriptor {
+            monitor: Some("MONITOR2".to_string()),
+            ..Default::default()
+        };
+
+        assert!(monitor_filter("MONITOR2").matches_on(window.clone(), &monitors));
+        assert!(monitor_filter("hw_monitor2").matches_on(window.clone(), &monitors));
+        assert!(monitor_filter("rightmost").matches_on(window.clone(), &monitors));
+        assert!(monitor_filter("index:2").matches_on(window.clone(), &monitors));
+        assert!(monitor_filter("/MONITOR[0-9]/").matches_on(window.clone(), &monitors));
+        assert!(!monitor_filter("primary").matches_on(window.clone(), &monitors));
+        assert!(!monitor_filter("MONITOR1").matches_on(window, &monitors));
+    }
+
+    #[test]
+    fn test_monitor_aliases() {
+        let monitors = [monitor("MONITOR1", 0, true), monitor("MONITOR2", 1920, false)];
+        let window = WindowDescriptor {
+            monitor: Some("MONITOR2".to_string()),
+            ..Default::default()
+        };
+        let aliases = HashMap::from([("Side".to_string(), "rightmost".to_string())]);
+
+        let filter = WinMatcher::Not(Box::new(monitor_filter("side")));
+        assert!(filter.matches_on(window.clone(), &monitors));
+
+        let filter = filter.with_monitor_aliases(&aliases);
+        assert_eq!(filter, WinMatcher::Not(Box::new(monitor_filter("rightmost"))));
+        assert!(!filter.matches_on(window, &monitors));
     }
 }
@@ -0,0 +1,27 @@
+use crate::app::rules_tester::WindowDescriptor;
+use crate::app::structs::area::Area;
+use crate::win32::api::monitor::Monitor;
+
+/// Landscape 1920x1080 monitor at the given horizontal offset
+pub fn monitor(id: &str, x: i32, primary: bool) -> Monitor {
+    Monitor {
+        handle: 0,
+        id: id.to_string(),
+        hw_id: format!("HW_{id}"),
+        primary,
+        resolution: (1920, 1080),
+        workspace: (1920, 1040),
+        monitor_area: Area::new(x, 0, 1920, 1080),
+        workspace_area: Area::new(x, 0, 1920, 1040),
+        offset: (x, 0),
+        dpi: 96,
+    }
+}
+
+/// Window with the given executable name and no other property
+pub fn window(exename: &str) -> WindowDescriptor {
+    WindowDescriptor {
+        exename: Some(exename.to_string()),
+        ..Default::default()
+    }
+}
@@ -1,20 +1,37 @@
 use crate::app::app_lock::AppLock;
 use crate::app::assets::Asset;
 use crate::app::cli_args::CliArgs;
+use crate::app::cli_args::Command;
+use crate::app::configs::changes::ConfigChanges;
+use crate::app::configs::generator;
+use crate::app::configs::loader;
 use crate::app::configs::AppConfig;
+use crate::app::message_queue::module_queue;
+use crate::app::message_queue::MessageLanes;
+use crate::app::message_queue::ModuleQueue;
+use crate::app::message_queue::ModuleQueueReceiver;
 use crate::app::mondrian_message::MondrianMessage;
+use crate::app::mondrian_message::SystemEvent;
+use crate::app::rules_tester;
+use crate::app::rules_tester::WindowDescriptor;
 use crate::app::structs::info_entry::{InfoEntry, InfoEntryIcon};
+use crate::app::structs::win_matcher::WinMatcher;
 use crate::modules::events_monitor::module::EventsMonitor;
 use crate::modules::file_watcher::module::FileWatcher;
 use crate::modules::healthcheck::module::HealthCheck;
 use crate::modules::keybindings::module::Keybindings;
+use crate::modules::logger::module::info_response_builder::build_info_response;
 use crate::modules::logger::module::Logger;
 use crate::modules::overlays::module::Overlays;
+use crate::modules::tiles_manager::lib::structs::rules::explain_rules;
 use crate::modules::tiles_manager::module::TilesManagerModule;
 use crate::modules::tray::module::Tray;
 use crate::modules::{Module, ModuleEnum};
 use crate::win32::api::gdiplus::{init_gdiplus, shutdown_gdiplus};
 use crate::win32::api::monitor::enum_display_monitors;
+use crate::win32::api::window::enum_user_manageable_windows;
+use crate::win32::api::window::get_foreground_window;
+use crate::win32::window::window_ref::WindowRef;
 use clap::Parser;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
@@ -27,6 +44,7 @@ use log4rs::append::rolling_file::RollingFileAppender;
 use log4rs::config::{Appender, Root};
 use log4rs::encode::pattern::PatternEncoder;
 use log4rs::filter::threshold::ThresholdFilter;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
@@ -36,6 +54,45 @@ use windows::Win32::UI::HiDpi::{SetProcessDpiAwarenessContext, DPI_AWARENESS_CON
 pub fn main() {
     let args = CliArgs::parse();
 
+    if let Some(command) = &args.command {
+        match command {
+            Command::Schema => print!("{}", generator::json_schema()),
+            Command::DefaultConfig => print!("{}", generator::default_config()),
+            Command::ExplainWindow { hwnd, filter } => {
+                std::process::exit(explain_window(&args.get_config_path(), *hwnd, filter.as_deref()));
+            }
+            Command::TestRules {
+                exe,
+                path,
+                title,
+                class,
+                style,
+                exstyle,
+                monitor,
+                size,
+                has_owner,
+                has_parent,
+                batch,
+            } => {
+                let window = WindowDescriptor {
+                    exename: exe.clone(),
+                    path: path.clone(),
+                    title: title.clone(),
+                    classname: class.clone(),
+                    style: style.unwrap_or_default(),
+                    exstyle: exstyle.unwrap_or_default(),
+                    monitor: monitor.clone(),
+                    size: *size,
+                    has_owner: *has_owner,
+                    has_parent: *has_parent,
+                    expect: None,
+                };
+                std::process::exit(test_rules(&args.get_config_path(), &window, batch.as_ref()));
+            }
+        }
+        return;
+    }
+
     init_logger(
         args.is_file_all_enabled(),
         args.is_file_error_enabled(),
@@ -50,11 +107,10 @@ pub fn main() {
         }
     };
 
-    let cfg_file = dirs::home_dir()
-        .expect("Failed to get home dir")
-        .join(".config/mondrian/mondrian.toml");
+    let cfg_file = args.get_config_path();
 
     log_info();
+    log::info!("Configuration file: {}", cfg_file.display());
     start_app(&cfg_file, args.dump_info, args.health_check);
 }
 
@@ -64,9 +120,10 @@ fn start_app(cfg_file: &PathBuf, dump_info: bool, health_check: bool) {
     }
     init_gdiplus();
 
-    let config = load_configs(cfg_file)
+    let base_config = load_configs(cfg_file)
         .inspect_err(|e| log::error!("Can't read config file: {}", e))
         .unwrap_or_default();
+    let (config, profile) = apply_profile(&base_config, None);
     let shared_config = Arc::new(RwLock::new(config));
 
     let (bus_tx, bus_rx) = crossbeam_channel::unbounded();
@@ -86,7 +143,7 @@ fn start_app(cfg_file: &PathBuf, dump_info: bool, health_check: bool) {
         modules.push(HealthCheck::new(modules_names, bus_tx.clone()).into());
     }
 
-    let mut modules_map: HashMap<String, (Sender<MondrianMessage>, JoinHandle<()>)> = HashMap::new();
+    let mut modules_map: HashMap<String, (ModuleQueue, JoinHandle<()>)> = HashMap::new();
 
     for mut m in modules.into_iter() {
         let module_name = m.name().to_lowercase();
@@ -95,7 +152,7 @@ fn start_app(cfg_file: &PathBuf, dump_info: bool, health_check: bool) {
         }
 
         let local_shared_config = shared_config.clone();
-        let (tx, rx) = crossbeam_channel::unbounded();
+        let (tx, rx) = module_queue();
         let bus_tx = bus_tx.clone();
         let th = thread::spawn(move || module_handler(&mut m, local_shared_config, bus_tx, rx));
 
@@ -106,7 +163,12 @@ fn start_app(cfg_file: &PathBuf, dump_info: bool, health_check: bool) {
         bus_tx.send(MondrianMessage::QueryInfo).ok();
     }
 
-    event_dispatcher(bus_rx, bus_tx, modules_map, shared_config, cfg_file);
+    let profiles = ConfigProfiles {
+        base_config,
+        active: profile,
+        manual: None,
+    };
+    event_dispatcher(bus_rx, bus_tx, modules_map, shared_config, profiles, cfg_file);
     shutdown_gdiplus();
 
     log::info!("Application stopped!");
@@ -118,12 +180,113 @@ fn load_configs(app_cfg_file: &PathBuf) -> Result<AppConfig, String> {
     }
 
     if !app_cfg_file.exists() {
+        if loader::ConfigFormat::from_path(app_cfg_file) != loader::ConfigFormat::Toml {
+            return Err(format!("Can't find config file '{}'", app_cfg_file.display()));
+        }
         let default_cfg = Asset::get_string("configs/mondrian.toml").map_err(|e| e.to_string())?;
         std::fs::write(app_cfg_file, default_cfg).map_err(|e| e.to_string())?;
     }
 
-    let file_content = std::fs::read_to_string(app_cfg_file).expect("Something went wrong reading the file");
-    toml::from_str::<AppConfig>(&file_content).map_err(|e| e.to_string())
+    loader::read_config(app_cfg_file)
+}
+
+/// Returns the configuration with the given profile applied or, if `None`, with the first profile
+/// matching the connected monitors (if any), along with the name of the applied profile.
+fn apply_profile(base_config: &AppConfig, profile: Option<&str>) -> (AppConfig, Option<String>) {
+    let name = match profile {
+        Some(name) => Some(name.to_string()),
+        None => base_config.find_profile(&enum_display_monitors()),
+    };
+
+    match name.and_then(|n| base_config.with_profile(&n).map(|c| (c, n))) {
+        Some((config, name)) => {
+            log::info!("Configuration profile '{}' applied", name);
+            (config, Some(name))
+        }
+        None => (base_config.clone(), None),
+    }
+}
+
+/// Prints which rules match the window with the given handle, the first window matching the given
+/// filter or the focused window (in this order). Returns the exit code (i.e. 1 if the config
+/// file or the filter is invalid or if no window is found).
+fn explain_window(cfg_file: &PathBuf, hwnd: Option<isize>, filter: Option<&str>) -> i32 {
+    let base_config = match loader::read_config(cfg_file) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Can't read config file: {}", e);
+            return 1;
+        }
+    };
+    let (config, _) = apply_profile(&base_config, None);
+
+    let window = match (hwnd, filter) {
+        (Some(hwnd), _) => Some(WindowRef::from(hwnd)),
+        (None, Some(filter)) => {
+            let matcher = toml::from_str::<toml::Table>(&format!("filter = {filter}"))
+                .map_err(|e| e.to_string())
+                .and_then(|t| WinMatcher::deserialize(t["filter"].clone()).map_err(|e| e.to_string()));
+            match matcher {
+                Ok(m) => enum_user_manageable_windows().into_iter().find(|w| m.matches(*w)),
+                Err(e) => {
+                    eprintln!("Invalid filter: {}", e);
+                    return 1;
+                }
+            }
+        }
+        (None, None) => get_foreground_window().map(WindowRef::from),
+    };
+
+    let Some(window) = window else {
+        eprintln!("No window found");
+        return 1;
+    };
+    let infos = explain_rules(
+        &config.ignore_filter,
+        &config.delayed_filter,
+        &config.rules,
+        window.snapshot(),
+        &enum_display_monitors(),
+    );
+    println!("{}", build_info_response(&infos, 3, "▸"));
+    0
+}
+
+/// Prints the outcome of the rules for the given window or, in batch mode, for each window in
+/// the given JSON file. Returns the exit code (i.e. 1 if the file can't be read or if any outcome
+/// differs from the expected one).
+fn test_rules(cfg_file: &PathBuf, window: &WindowDescriptor, batch: Option<&PathBuf>) -> i32 {
+    let config = match loader::read_config(cfg_file) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Can't read config file: {}", e);
+            return 1;
+        }
+    };
+
+    let Some(batch) = batch else {
+        print!(
+            "{}",
+            rules_tester::explain_window_rules(&config, window, &enum_display_monitors())
+        );
+        return 0;
+    };
+
+    let windows = std::fs::read_to_string(batch)
+        .map_err(|e| e.to_string())
+        .and_then(|s| serde_json::from_str::<Vec<WindowDescriptor>>(&s).map_err(|e| e.to_string()));
+    match windows {
+        Ok(windows) => {
+            let (report, failures) = rules_tester::test_rules(&config, &windows, &enum_display_monitors());
+            print!("{report}");
+            println!("\n{} windows tested, {} failed", windows.len(), failures);
+            i32::from(failures > 0)
+        }
+        Err(e) => {
+            eprintln!("Can't read windows file '{}': {}", batch.display(), e);
+            1
+        }
+    }
 }
 
 fn init_logger(file_all: bool, file_errors: bool, level: log::LevelFilter) {
@@ -197,20 +360,38 @@ fn log_info() {
     }
 }
 
-fn get_info_entries(cfg_file: &PathBuf) -> Vec<InfoEntry> {
+fn get_info_entries(
+    cfg_file: &PathBuf,
+    profile: Option<&str>,
+    lanes: &MessageLanes,
+    modules_map: &HashMap<String, (ModuleQueue, JoinHandle<()>)>,
+) -> Vec<InfoEntry> {
     let configs = load_configs(cfg_file);
     let configs_info = configs
         .map(|_| "Ok".to_string())
         .unwrap_or_else(|e| format!("ERROR - {:?}", e));
     let infos = InfoEntry::simple("Configuration", configs_info).with_icon(InfoEntryIcon::Configs);
-    vec![infos]
+    let profile_info =
+        InfoEntry::simple("Configuration profile", profile.unwrap_or("/")).with_icon(InfoEntryIcon::Configs);
+    let mut modules: Vec<(&String, &ModuleQueue)> = modules_map.iter().map(|(n, (q, _))| (n, q)).collect();
+    modules.sort_by(|a, b| a.0.cmp(b.0));
+    let queues = std::iter::once(lanes.get_info_entry()).chain(modules.iter().map(|(n, q)| q.get_info_entry(n)));
+    let queues_info = InfoEntry::list("Message queues", queues).with_icon(InfoEntryIcon::General);
+    vec![infos, profile_info, queues_info]
+}
+
+/// Configuration without profiles applied, along with the active profile
+struct ConfigProfiles {
+    base_config: AppConfig,
+    active: Option<String>,
+    manual: Option<String>,
 }
 
 fn module_handler(
     m: &mut ModuleEnum,
     shared_config: Arc<RwLock<AppConfig>>,
     tx: Sender<MondrianMessage>,
-    rx: Receiver<MondrianMessage>,
+    rx: ModuleQueueReceiver,
 ) {
     let mut config = shared_config.read().unwrap().clone();
 
@@ -221,7 +402,11 @@ fn module_handler(
     loop {
         let event = if let Ok(e) = rx.recv() { e } else { continue };
         if matches!(event, MondrianMessage::RefreshConfig) {
-            config = shared_config.read().unwrap().clone();
+            let new_config = shared_config.read().unwrap().clone();
+            let changes = ConfigChanges::diff(&config, &new_config);
+            config = new_config;
+            m.handle(&MondrianMessage::ConfigChanged(changes), &config, &tx);
+            continue;
         }
         m.handle(&event, &config, &tx);
         if matches!(event, MondrianMessage::Quit) {
@@ -234,12 +419,22 @@ fn module_handler(
 fn event_dispatcher(
     bus_rx: Receiver<MondrianMessage>,
     bus_tx: Sender<MondrianMessage>,
-    mut modules_map: HashMap<String, (Sender<MondrianMessage>, JoinHandle<()>)>,
+    mut modules_map: HashMap<String, (ModuleQueue, JoinHandle<()>)>,
     shared_config: Arc<RwLock<AppConfig>>,
+    mut profiles: ConfigProfiles,
     cfg_file: &PathBuf,
 ) {
+    let mut lanes = MessageLanes::default();
     loop {
-        let event = if let Ok(e) = bus_rx.recv() { e } else { continue };
+        if lanes.is_empty() {
+            let Ok(event) = bus_rx.recv() else { continue };
+            lanes.push(event);
+        }
+
+        // INFO: the messages already on the bus are sorted by priority, so that user commands jump the queue
+        bus_rx.try_iter().for_each(|e| lanes.push(e));
+        let Some(event) = lanes.pop() else { continue };
+        let prev_profile = profiles.active.clone();
 
         match &event {
             MondrianMessage::QueryInfo => {
@@ -247,18 +442,51 @@ fn event_dispatcher(
                     .send(MondrianMessage::QueryInfoResponse {
                         name: "General".to_string(),
                         icon: InfoEntryIcon::General,
-                        infos: get_info_entries(cfg_file),
+                        infos: get_info_entries(cfg_file, profiles.active.as_deref(), &lanes, &modules_map),
                     })
                     .ok();
             }
             MondrianMessage::RefreshConfig => {
                 if let Ok(c) = load_configs(cfg_file).inspect_err(|e| log::error!("Can't read config file: {}", e)) {
-                    shared_config.write().unwrap().clone_from(&c);
+                    profiles.base_config = c;
                 }
+                let manual = profiles
+                    .manual
+                    .as_deref()
+                    .filter(|p| profiles.base_config.profiles.contains_key(*p));
+                let (config, profile) = apply_profile(&profiles.base_config, manual);
+                shared_config.write().unwrap().clone_from(&config);
+                profiles.active = profile;
+            }
+            MondrianMessage::SetProfile(name) if !profiles.base_config.profiles.contains_key(name) => {
+                log::warn!("Configuration profile '{}' not found", name);
+            }
+            MondrianMessage::SetProfile(name) => {
+                let (config, profile) = apply_profile(&profiles.base_config, Some(name));
+                shared_config.write().unwrap().clone_from(&config);
+                profiles.manual = Some(name.clone());
+                profiles.active = profile;
+            }
+            MondrianMessage::SystemEvent(SystemEvent::MonitorsLayoutChanged) => {
+                // INFO: the profile selected manually is kept until the monitors change
+                let (config, profile) = apply_profile(&profiles.base_config, None);
+                if profile != profiles.active {
+                    shared_config.write().unwrap().clone_from(&config);
+                }
+                profiles.manual = None;
+                profiles.active = profile;
             }
             _ => (),
         }
 
+        // INFO: modules are notified of the new configuration before the event that triggered it
+        let profile_changed = profiles.active != prev_profile;
+        if profile_changed && !matches!(event, MondrianMessage::RefreshConfig) {
+            modules_map.values_mut().for_each(|(tx, _)| {
+                tx.send(MondrianMessage::RefreshConfig).ok();
+            });
+        }
+
         match &event {
             MondrianMessage::OpenConfig => drop(open::that(cfg_file.clone())),
             MondrianMessage::OpenLogFolder => drop(open::that("logs")),
@@ -1,11 +1,15 @@
-use crate::app::{configs::AppConfig, structs::win_matcher};
+use crate::app::{
+    configs::{rules::InsertDelay, AppConfig},
+    structs::win_matcher,
+};
 
 pub struct EventMonitorModuleConfigs {
     pub default_insert_in_monitor: bool,
     pub default_free_move_in_monitor: bool,
     pub detect_maximized_windows: bool,
     pub filter: Option<win_matcher::WinMatcher>,
-    pub delayed_filter: Vec<(win_matcher::WinMatcher, u32)>,
+    pub delayed_filter: Vec<(win_matcher::WinMatcher, InsertDelay)>,
+    pub title_change_filters: Vec<win_matcher::WinMatcher>,
 }
 
 impl Default for EventMonitorModuleConfigs {
@@ -16,6 +20,7 @@ impl Default for EventMonitorModuleConfigs {
             detect_maximized_windows: true,
             filter: None,
             delayed_filter: vec![],
+            title_change_filters: vec![],
         }
     }
 }
@@ -28,6 +33,7 @@ impl From<&AppConfig> for EventMonitorModuleConfigs {
             detect_maximized_windows: app_configs.detect_maximized_windows,
             filter: Some(app_configs.ignore_filter.clone()),
             delayed_filter: app_configs.delayed_filter.clone(),
+            title_change_filters: app_configs.title_change_filters.clone(),
         }
     }
 }
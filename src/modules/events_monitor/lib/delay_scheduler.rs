@@ -0,0 +1,237 @@
+use crate::app::configs::rules::InsertDelay;
+use crate::app::mondrian_message::{MondrianMessage, WindowEvent};
+use crate::app::structs::area::Area;
+use crate::win32::window::window_obj::WindowObjInfo;
+use crate::win32::window::window_ref::WindowRef;
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Maximum time a window can wait for its area to be stable
+const MAX_STABLE_WAIT: Duration = Duration::from_secs(20);
+/// Interval between two checks of the area of a window waiting for it to be stable
+const STABLE_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+type AreaLookup = fn(WindowRef) -> Option<Area>;
+
+enum SchedulerMessage {
+    Schedule(WindowRef, InsertDelay),
+    Cancel(WindowRef),
+    Quit,
+}
+
+/// Sends a `Repositioned` event for each scheduled window once its delay is elapsed. All the
+/// delays run on a single timer thread, and the pending ones can be cancelled.
+pub struct DelayScheduler {
+    tx: Sender<SchedulerMessage>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl DelayScheduler {
+    pub fn new(sender: Sender<MondrianMessage>) -> DelayScheduler {
+        DelayScheduler::with_area_lookup(sender, |w| w.get_area())
+    }
+
+    /// Creates a scheduler reading the areas of the windows with the given function
+    fn with_area_lookup(sender: Sender<MondrianMessage>, get_area: AreaLookup) -> DelayScheduler {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let thread = thread::spawn(move || run_scheduler(rx, sender, get_area));
+        DelayScheduler {
+            tx,
+            thread: Some(thread),
+        }
+    }
+
+    /// Schedules the event of the window, replacing the pending one if any
+    pub fn schedule(&self, window: WindowRef, delay: InsertDelay) {
+        self.tx.send(SchedulerMessage::Schedule(window, delay)).ok();
+    }
+
+    pub fn cancel(&self, window: WindowRef) {
+        self.tx.send(SchedulerMessage::Cancel(window)).ok();
+    }
+}
+
+impl Drop for DelayScheduler {
+    fn drop(&mut self) {
+        self.tx.send(SchedulerMessage::Quit).ok();
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+struct PendingEvent {
+    delay: InsertDelay,
+    scheduled_at: Instant,
+    deadline: Instant,
+    last_area: Option<Area>,
+    last_change: Instant,
+}
+
+impl PendingEvent {
+    fn new(delay: InsertDelay, area: Option<Area>, now: Instant) -> PendingEvent {
+        let deadline = match delay {
+            InsertDelay::Fixed(delay) => now + Duration::from_millis(delay.into()),
+            InsertDelay::UntilStable(delay) => now + STABLE_CHECK_INTERVAL.min(Duration::from_millis(delay.into())),
+        };
+        PendingEvent {
+            delay,
+            scheduled_at: now,
+            deadline,
+            last_area: area,
+            last_change: now,
+        }
+    }
+
+    /// Returns true if the event is ready to be sent, otherwise moves the deadline to the next check
+    fn is_ready(&mut self, area: Option<Area>, now: Instant) -> bool {
+        let stable_for = match self.delay {
+            InsertDelay::Fixed(_) => return true,
+            InsertDelay::UntilStable(delay) => Duration::from_millis(delay.into()),
+        };
+
+        if area != self.last_area {
+            self.last_area = area;
+            self.last_change = now;
+        }
+
+        if now - self.last_change >= stable_for || now - self.scheduled_at >= MAX_STABLE_WAIT {
+            return true;
+        }
+
+        self.deadline = now + STABLE_CHECK_INTERVAL.min(stable_for);
+        false
+    }
+}
+
+fn run_scheduler(rx: Receiver<SchedulerMessage>, sender: Sender<MondrianMessage>, get_area: AreaLookup) {
+    let mut pending: HashMap<WindowRef, PendingEvent> = HashMap::new();
+    loop {
+        let message = match pending.values().map(|p| p.deadline).min() {
+            Some(deadline) => rx.recv_deadline(deadline),
+            None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match message {
+            Ok(SchedulerMessage::Schedule(window, delay)) => {
+                pending.insert(window, PendingEvent::new(delay, get_area(window), Instant::now()));
+            }
+            Ok(SchedulerMessage::Cancel(window)) => {
+                pending.remove(&window);
+            }
+            Ok(SchedulerMessage::Quit) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        let now = Instant::now();
+        let mut ready = vec![];
+        for (window, event) in pending.iter_mut().filter(|(_, e)| e.deadline <= now) {
+            if event.is_ready(get_area(*window), now) {
+                ready.push(*window);
+            }
+        }
+
+        for window in ready {
+            pending.remove(&window);
+            sender
+                .send(WindowEvent::Repositioned(window).into())
+                .inspect_err(|_| log::warn!("Failed to send delayed event for window {:?}", window))
+                .ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DelayScheduler;
+    use super::PendingEvent;
+    use super::MAX_STABLE_WAIT;
+    use crate::app::configs::rules::InsertDelay;
+    use crate::app::mondrian_message::{MondrianMessage, WindowEvent};
+    use crate::app::structs::area::Area;
+    use crate::win32::window::window_ref::WindowRef;
+    use crossbeam_channel::Receiver;
+    use std::time::{Duration, Instant};
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn area(x: i32) -> Option<Area> {
+        Some(Area::new(x, 0, 100, 100))
+    }
+
+    fn scheduler() -> (DelayScheduler, Receiver<MondrianMessage>) {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        (DelayScheduler::with_area_lookup(tx, |_| None), rx)
+    }
+
+    fn repositioned(win: isize) -> MondrianMessage {
+        WindowEvent::Repositioned(WindowRef::from(win)).into()
+    }
+
+    #[test]
+    fn test_fixed_delay() {
+        let now = Instant::now();
+        let mut event = PendingEvent::new(InsertDelay::Fixed(200), area(0), now);
+
+        assert_eq!(event.deadline, now + ms(200));
+        assert!(event.is_ready(area(100), now + ms(200)));
+    }
+
+    #[test]
+    fn test_until_stable_delay() {
+        let now = Instant::now();
+        let mut event = PendingEvent::new(InsertDelay::UntilStable(100), area(0), now);
+        assert_eq!(event.deadline, now + ms(50));
+
+        // INFO: each change of the area restarts the wait
+        assert!(!event.is_ready(area(10), now + ms(50)));
+        assert_eq!(event.deadline, now + ms(100));
+        assert!(!event.is_ready(area(10), now + ms(100)));
+        assert!(!event.is_ready(area(10), now + ms(140)));
+        assert!(event.is_ready(area(10), now + ms(150)));
+    }
+
+    #[test]
+    fn test_until_stable_max_wait() {
+        let now = Instant::now();
+        let mut event = PendingEvent::new(InsertDelay::UntilStable(100), area(0), now);
+
+        assert!(!event.is_ready(area(1), now + MAX_STABLE_WAIT - ms(50)));
+        assert!(event.is_ready(area(2), now + MAX_STABLE_WAIT));
+    }
+
+    #[test]
+    fn test_scheduler_sends_event() {
+        let (scheduler, rx) = scheduler();
+        scheduler.schedule(WindowRef::from(1), InsertDelay::Fixed(10));
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(repositioned(1)));
+    }
+
+    #[test]
+    fn test_scheduler_cancel() {
+        let (scheduler, rx) = scheduler();
+        scheduler.schedule(WindowRef::from(1), InsertDelay::Fixed(100));
+        scheduler.cancel(WindowRef::from(1));
+        scheduler.schedule(WindowRef::from(2), InsertDelay::Fixed(200));
+
+        // INFO: the messages are handled in order, so the cancelled event would come first
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(repositioned(2)));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_scheduler_reschedule() {
+        let (scheduler, rx) = scheduler();
+        scheduler.schedule(WindowRef::from(1), InsertDelay::Fixed(60_000));
+        scheduler.schedule(WindowRef::from(1), InsertDelay::Fixed(10));
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(repositioned(1)));
+        drop(scheduler);
+        assert!(rx.try_recv().is_err());
+    }
+}
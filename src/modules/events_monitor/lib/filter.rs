@@ -3,7 +3,8 @@ use crate::app::structs::win_matcher;
 
 pub fn skip_window(event: &WindowEvent, win_match: &win_matcher::WinMatcher) -> bool {
     match event {
-        WindowEvent::Closed(_) => false,
+        // INFO: the window may have to be removed because it started matching an ignore rule
+        WindowEvent::Closed(_) | WindowEvent::TitleChanged(_) => false,
         command => {
             let info = command.get_window_ref().snapshot();
             if win_match.matches(info.clone()) {
@@ -1,4 +1,6 @@
+use super::delay_scheduler::DelayScheduler;
 use super::filter::skip_window;
+use crate::app::configs::rules::InsertDelay;
 use crate::app::mondrian_message::{MondrianMessage, WindowEvent};
 use crate::app::structs::win_matcher::WinMatcher;
 use crate::win32::api::window::{enum_user_manageable_windows, is_user_manageable_window, is_window_visible};
@@ -7,39 +9,31 @@ use crate::win32::win_events_manager::WinEventHandler;
 use crate::win32::window::window_ref::WindowRef;
 use crossbeam_channel::Sender;
 use std::collections::HashSet;
-use std::thread;
 use windows::Win32::Foundation::HWND;
 use windows::Win32::UI::WindowsAndMessaging::{
     EVENT_OBJECT_CLOAKED, EVENT_OBJECT_DESTROY, EVENT_OBJECT_HIDE, EVENT_OBJECT_SHOW, EVENT_OBJECT_UNCLOAKED,
     EVENT_SYSTEM_FOREGROUND, EVENT_SYSTEM_MINIMIZEEND,
 };
 
-enum QueueMessage {
-    Event(WindowEvent, u32),
-    Quit,
-}
-
 pub struct OpenCloseEventHandler {
     sender: Sender<MondrianMessage>,
     filter: WinMatcher,
     windows: HashSet<WindowRef>,
-    delayed_filter: Vec<(WinMatcher, u32)>,
-    message_queue_tx: Option<Sender<QueueMessage>>,
-    message_queue_thread: Option<thread::JoinHandle<()>>,
+    delayed_filter: Vec<(WinMatcher, InsertDelay)>,
+    scheduler: Option<DelayScheduler>,
 }
 
 impl OpenCloseEventHandler {
     pub fn new(
         sender: Sender<MondrianMessage>,
         filter: WinMatcher,
-        delayed_filter: Vec<(WinMatcher, u32)>,
+        delayed_filter: Vec<(WinMatcher, InsertDelay)>,
     ) -> OpenCloseEventHandler {
         OpenCloseEventHandler {
             sender,
             filter,
             windows: HashSet::new(),
-            message_queue_tx: None,
-            message_queue_thread: None,
+            scheduler: None,
             delayed_filter,
         }
     }
@@ -63,11 +57,7 @@ impl OpenCloseEventHandler {
 
             // INFO: sends a reposition event after a delay for the matched windows
             if let Some((_, delay)) = self.delayed_filter.iter().find(|(f, _)| f.matches(winref)) {
-                self.message_queue_tx
-                    .as_ref()
-                    .unwrap()
-                    .send(QueueMessage::Event(WindowEvent::Repositioned(winref), *delay))
-                    .ok();
+                self.scheduler.as_ref().unwrap().schedule(winref, *delay);
             }
         }
     }
@@ -81,19 +71,7 @@ impl WinEventHandler for OpenCloseEventHandler {
             .map(|w| w.hwnd.into())
             .collect();
 
-        let (tx, rx) = crossbeam_channel::unbounded();
-        self.message_queue_tx = Some(tx);
-        let sender = self.sender.clone();
-        self.message_queue_thread = Some(thread::spawn(move || loop {
-            match rx.recv() {
-                Ok(QueueMessage::Event(event, delay)) => {
-                    thread::sleep(std::time::Duration::from_millis(delay.into()));
-                    sender.send(event.into()).ok();
-                }
-                Ok(QueueMessage::Quit) => break,
-                Err(_) => break,
-            }
-        }));
+        self.scheduler = Some(DelayScheduler::new(self.sender.clone()));
     }
 
     fn handle(&mut self, event: &WindowsEvent) {
@@ -129,6 +107,10 @@ impl WinEventHandler for OpenCloseEventHandler {
 
         if is_cloaked || ((is_destroyed || is_hidden) && !is_window_visible(event.hwnd)) {
             self.windows.remove(&event.hwnd.into());
+            // INFO: the pending delayed insertion is useless once the window is closed
+            if let Some(scheduler) = self.scheduler.as_ref() {
+                scheduler.cancel(event.hwnd.into());
+            }
             self.sender
                 .send(WindowEvent::Closed(event.hwnd.into()).into())
                 .inspect_err(|e| log::warn!("Failed to send close event: {:?}", e))
@@ -149,12 +131,3 @@ impl WinEventHandler for OpenCloseEventHandler {
         .into()
     }
 }
-
-impl Drop for OpenCloseEventHandler {
-    fn drop(&mut self) {
-        if let Some(tx) = self.message_queue_tx.take() {
-            tx.send(QueueMessage::Quit).ok();
-            self.message_queue_thread.take().unwrap().join().ok();
-        }
-    }
-}
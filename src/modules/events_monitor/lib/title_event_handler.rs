@@ -0,0 +1,92 @@
+use crate::app::mondrian_message::{MondrianMessage, WindowEvent};
+use crate::app::structs::win_matcher::WinMatcher;
+use crate::win32::api::window::{enum_user_manageable_windows, is_user_manageable_window};
+use crate::win32::callbacks::win_event_hook::WindowsEvent;
+use crate::win32::win_events_manager::WinEventHandler;
+use crate::win32::window::window_ref::WindowRef;
+use crossbeam_channel::Sender;
+use std::collections::HashMap;
+use windows::Win32::UI::WindowsAndMessaging::{
+    EVENT_OBJECT_DESTROY, EVENT_OBJECT_NAMECHANGE, EVENT_OBJECT_SHOW, EVENT_SYSTEM_FOREGROUND, OBJID_WINDOW,
+};
+
+/// Sends a `TitleChanged` event when, after a title change, a window starts or stops matching
+/// one of the rules that must be evaluated again
+pub struct TitleEventHandler {
+    sender: Sender<MondrianMessage>,
+    filters: Vec<WinMatcher>,
+    matches: HashMap<WindowRef, Vec<bool>>,
+}
+
+impl TitleEventHandler {
+    pub fn new(sender: Sender<MondrianMessage>, filters: Vec<WinMatcher>) -> TitleEventHandler {
+        TitleEventHandler {
+            sender,
+            filters,
+            matches: HashMap::new(),
+        }
+    }
+
+    fn get_matches(&self, window: WindowRef) -> Vec<bool> {
+        let snapshot = window.snapshot();
+        self.filters.iter().map(|f| f.matches(snapshot.clone())).collect()
+    }
+}
+
+impl WinEventHandler for TitleEventHandler {
+    fn init(&mut self) {
+        if self.filters.is_empty() {
+            return;
+        }
+
+        self.matches = enum_user_manageable_windows()
+            .into_iter()
+            .map(|w| (w, self.get_matches(w)))
+            .collect();
+    }
+
+    fn handle(&mut self, event: &WindowsEvent) {
+        if self.filters.is_empty() || event.hwnd.is_invalid() || event.id_object != OBJID_WINDOW.0 {
+            return;
+        }
+
+        let winref = WindowRef::new(event.hwnd);
+        if event.event == EVENT_OBJECT_DESTROY {
+            self.matches.remove(&winref);
+            return;
+        }
+
+        if !is_user_manageable_window(event.hwnd, true, true, true) {
+            return;
+        }
+
+        // INFO: the matches are recorded when the window is shown, to detect the title changes
+        // happening right after the window is opened
+        if event.event != EVENT_OBJECT_NAMECHANGE {
+            if !self.matches.contains_key(&winref) {
+                let matches = self.get_matches(winref);
+                self.matches.insert(winref, matches);
+            }
+            return;
+        }
+
+        let matches = self.get_matches(winref);
+        let prev_matches = self.matches.insert(winref, matches.clone());
+        if prev_matches.is_some_and(|m| m != matches) {
+            self.sender
+                .send(WindowEvent::TitleChanged(winref).into())
+                .inspect_err(|_| log::warn!("Failed to send title change event for window {:?}", event.hwnd))
+                .ok();
+        }
+    }
+
+    fn get_managed_events(&self) -> Option<Vec<u32>> {
+        vec![
+            EVENT_OBJECT_NAMECHANGE,
+            EVENT_OBJECT_SHOW,
+            EVENT_SYSTEM_FOREGROUND,
+            EVENT_OBJECT_DESTROY,
+        ]
+        .into()
+    }
+}
@@ -5,6 +5,8 @@ use super::lib::minimize_event_handler::MinimizeEventHandler;
 use super::lib::open_event_handler::OpenCloseEventHandler;
 use super::lib::position_event_handler::PositionEventHandler;
 use super::lib::system_events_detector;
+use super::lib::title_event_handler::TitleEventHandler;
+use crate::app::configs::changes::ConfigSection;
 use crate::app::configs::AppConfig;
 use crate::app::mondrian_message::MondrianMessage;
 use crate::app::mondrian_message::SystemEvent;
@@ -132,6 +134,7 @@ impl EventsMonitor {
         let bus_tx = self.bus_tx.clone();
         let filter = self.configs.filter.clone().unwrap();
         let delayed_filter = self.configs.delayed_filter.clone();
+        let title_change_filters = self.configs.title_change_filters.clone();
         self.win_events_thread = Some(thread::spawn(move || {
             thread_id.store(get_current_thread_id(), Ordering::SeqCst);
 
@@ -154,6 +157,9 @@ impl EventsMonitor {
                 wem.hook(MaximizeEventHandler::new(bus_tx.clone(), filter.clone()));
             }
 
+            // INFO: always hooked, the handler does nothing while no rule depends on the window title
+            wem.hook(TitleEventHandler::new(bus_tx.clone(), title_change_filters));
+
             wem.start_event_loop();
         }));
     }
@@ -237,7 +243,7 @@ impl ModuleImpl for EventsMonitor {
             MondrianMessage::Configure => {
                 self.configure(app_configs.into());
             }
-            MondrianMessage::RefreshConfig => {
+            MondrianMessage::ConfigChanged(changes) if changes.any(&[ConfigSection::General, ConfigSection::Rules]) => {
                 self.configure(app_configs.into());
                 Module::restart(self);
             }
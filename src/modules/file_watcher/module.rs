@@ -1,3 +1,4 @@
+use crate::app::configs::loader;
 use crate::app::configs::AppConfig;
 use crate::app::mondrian_message::MondrianMessage;
 use crate::modules::module_impl::ModuleImpl;
@@ -15,6 +16,7 @@ use std::time::Duration;
 pub struct FileWatcher {
     config_watcher_debouncer: Option<Debouncer<RecommendedWatcher>>,
     config_path: PathBuf,
+    watched_paths: Vec<PathBuf>,
     running: bool,
     enabled: bool,
     bus_tx: Sender<MondrianMessage>,
@@ -27,6 +29,7 @@ impl FileWatcher {
             running: false,
             enabled: true,
             config_path: config_path.as_ref().to_path_buf(),
+            watched_paths: vec![],
             bus_tx,
         }
     }
@@ -47,12 +50,19 @@ impl FileWatcher {
             .inspect_err(|_| log::warn!("Error creating config watcher debouncer"));
 
         if let Ok(mut wd) = watcher_debouncer {
-            let res = wd
-                .watcher()
-                .watch(&self.config_path, notify::RecursiveMode::NonRecursive)
-                .inspect_err(|_| log::error!("Error watching config file"));
-
-            if res.is_ok() {
+            let paths = loader::get_config_files(&self.config_path);
+            let watched_paths: Vec<PathBuf> = paths
+                .into_iter()
+                .filter(|p| {
+                    wd.watcher()
+                        .watch(p, notify::RecursiveMode::NonRecursive)
+                        .inspect_err(|_| log::error!("Error watching config file {}", p.display()))
+                        .is_ok()
+                })
+                .collect();
+
+            if !watched_paths.is_empty() {
+                self.watched_paths = watched_paths;
                 self.config_watcher_debouncer = Some(wd);
                 self.running = true;
             }
@@ -77,7 +87,9 @@ impl ModuleImpl for FileWatcher {
         }
 
         if let Some(mut wd) = self.config_watcher_debouncer.take() {
-            let _ = wd.watcher().unwatch(&self.config_path);
+            for path in self.watched_paths.drain(..) {
+                let _ = wd.watcher().unwatch(&path);
+            }
         }
 
         self.running = false;
@@ -110,7 +122,8 @@ impl ModuleImpl for FileWatcher {
             MondrianMessage::Configure => {
                 Module::enable(self, app_configs.auto_reload_configs);
             }
-            MondrianMessage::RefreshConfig => {
+            MondrianMessage::ConfigChanged(_) => {
+                // INFO: always restarted, since the included files could have changed
                 Module::enable(self, app_configs.auto_reload_configs);
                 Module::restart(self);
             }
@@ -1,26 +1,46 @@
 use crate::app::{configs::AppConfig, mondrian_message::MondrianMessage};
 use inputbot::KeybdKey::{self, *};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
 use serde::{de::Error, Deserialize, Deserializer};
 use std::collections::HashMap;
 use std::fmt::Debug;
 
-#[derive(Clone, Debug, Deserialize, Default)]
+/// Keybindings module settings
+#[derive(Clone, Debug, Deserialize, JsonSchema, Default, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct KeybindingsModuleConfigs {
+    /// Enables or disables the module
+    #[schemars(example = true)]
     pub enabled: bool,
 
+    /// Custom keybindings, each one with the format `{ modifiers = "MODIFIERS", key = "KEY", action = "ACTION" }`
     #[serde(deserialize_with = "deserialize_bindings")]
+    #[schemars(with = "Vec<ExternalBinding>", example = default_bindings())]
+    #[schemars(extend("x-item-comments" = {
+        "0": ["Navigation"],
+        "4": ["Movement"],
+        "8": ["Insert"],
+        "12": ["Resize"],
+        "16": [
+            "Workspaces",
+            "{ action = \"focus-workspace 1\", key = \"1\", modifiers = \"ALT\" },",
+            "{ action = \"focus-workspace 2\", key = \"2\", modifiers = \"ALT\" },",
+            "{ action = \"move-to-workspace 1\", key = \"1\", modifiers = \"CTRL+ALT\" },",
+            "{ action = \"move-to-workspace 2\", key = \"2\", modifiers = \"CTRL+ALT\" },",
+            "Others"
+        ]
+    }))]
     pub bindings: Vec<Binding>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Binding {
     modifiers: Vec<KeybdKey>,
     key: KeybdKey,
     action: MondrianMessage,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone, Copy, Debug)]
 enum ModifierKey {
     Shift,
     Control,
@@ -36,18 +56,79 @@ enum ModifierKey {
     RightWin,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+const MODIFIERS: [(&str, ModifierKey); 12] = [
+    ("ALT", ModifierKey::Alt),
+    ("CTRL", ModifierKey::Control),
+    ("SHIFT", ModifierKey::Shift),
+    ("WIN", ModifierKey::Win),
+    ("LALT", ModifierKey::LeftAlt),
+    ("LCTRL", ModifierKey::LeftControl),
+    ("LSHIFT", ModifierKey::LeftShift),
+    ("LWIN", ModifierKey::LeftWin),
+    ("RALT", ModifierKey::RightAlt),
+    ("RCTRL", ModifierKey::RightControl),
+    ("RSHIFT", ModifierKey::RightShift),
+    ("RWIN", ModifierKey::RightWin),
+];
+
+const NAMED_KEYS: [(&str, KeybdKey); 38] = [
+    ("LEFT", LeftKey),
+    ("RIGHT", RightKey),
+    ("UP", UpKey),
+    ("DOWN", DownKey),
+    ("F1", F1Key),
+    ("F2", F2Key),
+    ("F3", F3Key),
+    ("F4", F4Key),
+    ("F5", F5Key),
+    ("F6", F6Key),
+    ("F7", F7Key),
+    ("F8", F8Key),
+    ("F9", F9Key),
+    ("F10", F10Key),
+    ("F11", F11Key),
+    ("F12", F12Key),
+    ("F13", F13Key),
+    ("F14", F14Key),
+    ("F15", F15Key),
+    ("F16", F16Key),
+    ("F17", F17Key),
+    ("F18", F18Key),
+    ("F19", F19Key),
+    ("F20", F20Key),
+    ("F21", F21Key),
+    ("F22", F22Key),
+    ("F23", F23Key),
+    ("F24", F24Key),
+    ("NUM0", Numpad0Key),
+    ("NUM1", Numpad1Key),
+    ("NUM2", Numpad2Key),
+    ("NUM3", Numpad3Key),
+    ("NUM4", Numpad4Key),
+    ("NUM5", Numpad5Key),
+    ("NUM6", Numpad6Key),
+    ("NUM7", Numpad7Key),
+    ("NUM8", Numpad8Key),
+    ("NUM9", Numpad9Key),
+];
+
+#[derive(Deserialize, JsonSchema, Clone, Debug)]
 #[serde(deny_unknown_fields)]
+#[schemars(transform = add_modifiers_aliases)]
 struct ExternalBinding {
+    /// Modifiers to be pressed, joined by "+" (e.g. "WIN+SHIFT")
     #[serde(
         default,
         alias = "modifier",
         alias = "mod",
         deserialize_with = "deserialize_modifiers"
     )]
+    #[schemars(schema_with = "modifiers_schema")]
     pub modifiers: Vec<ModifierKey>,
 
+    /// Key to be pressed, as a single character or a named key (e.g. "left", "F1", "NUM0")
     #[serde(deserialize_with = "deserialize_key")]
+    #[schemars(schema_with = "key_schema")]
     pub key: KeybdKey,
     pub action: MondrianMessage,
 }
@@ -214,21 +295,8 @@ where
 }
 
 fn parse_modifier(modifier: String) -> Option<ModifierKey> {
-    match modifier.trim().to_uppercase().as_str() {
-        "ALT" => Some(ModifierKey::Alt),
-        "CTRL" => Some(ModifierKey::Control),
-        "SHIFT" => Some(ModifierKey::Shift),
-        "WIN" => Some(ModifierKey::Win),
-        "LALT" => Some(ModifierKey::LeftAlt),
-        "LCTRL" => Some(ModifierKey::LeftControl),
-        "LSHIFT" => Some(ModifierKey::LeftShift),
-        "LWIN" => Some(ModifierKey::LeftWin),
-        "RALT" => Some(ModifierKey::RightAlt),
-        "RCTRL" => Some(ModifierKey::RightControl),
-        "RSHIFT" => Some(ModifierKey::RightShift),
-        "RWIN" => Some(ModifierKey::RightWin),
-        _ => None,
-    }
+    let modifier = modifier.trim().to_uppercase();
+    MODIFIERS.iter().find(|(n, _)| *n == modifier).map(|(_, m)| *m)
 }
 
 fn parse_key(key: String) -> Option<inputbot::KeybdKey> {
@@ -236,46 +304,79 @@ fn parse_key(key: String) -> Option<inputbot::KeybdKey> {
         let key = key.chars().next().unwrap();
         inputbot::get_keybd_key(key)
     } else {
-        match key.trim().to_uppercase().as_str() {
-            "LEFT" => Some(LeftKey),
-            "RIGHT" => Some(RightKey),
-            "UP" => Some(UpKey),
-            "DOWN" => Some(DownKey),
-            "F1" => Some(F1Key),
-            "F2" => Some(F2Key),
-            "F3" => Some(F3Key),
-            "F4" => Some(F4Key),
-            "F5" => Some(F5Key),
-            "F6" => Some(F6Key),
-            "F7" => Some(F7Key),
-            "F8" => Some(F8Key),
-            "F9" => Some(F9Key),
-            "F10" => Some(F10Key),
-            "F11" => Some(F11Key),
-            "F12" => Some(F12Key),
-            "F13" => Some(F13Key),
-            "F14" => Some(F14Key),
-            "F15" => Some(F15Key),
-            "F16" => Some(F16Key),
-            "F17" => Some(F17Key),
-            "F18" => Some(F18Key),
-            "F19" => Some(F19Key),
-            "F20" => Some(F20Key),
-            "F21" => Some(F21Key),
-            "F22" => Some(F22Key),
-            "F23" => Some(F23Key),
-            "F24" => Some(F24Key),
-            "NUM0" => Some(Numpad0Key),
-            "NUM1" => Some(Numpad1Key),
-            "NUM2" => Some(Numpad2Key),
-            "NUM3" => Some(Numpad3Key),
-            "NUM4" => Some(Numpad4Key),
-            "NUM5" => Some(Numpad5Key),
-            "NUM6" => Some(Numpad6Key),
-            "NUM7" => Some(Numpad7Key),
-            "NUM8" => Some(Numpad8Key),
-            "NUM9" => Some(Numpad9Key),
-            _ => None,
+        let key = key.trim().to_uppercase();
+        NAMED_KEYS.iter().find(|(n, _)| *n == key).map(|(_, k)| *k)
+    }
+}
+
+fn modifiers_schema(_: &mut SchemaGenerator) -> Schema {
+    let names: Vec<String> = MODIFIERS
+        .iter()
+        .flat_map(|(n, _)| [n.to_string(), n.to_lowercase()])
+        .collect();
+    let modifier = format!("({})", names.join("|"));
+    json_schema!({
+        "type": "string",
+        "pattern": format!("^{modifier}(\\+{modifier})*$")
+    })
+}
+
+fn key_schema(_: &mut SchemaGenerator) -> Schema {
+    let names: Vec<String> = NAMED_KEYS
+        .iter()
+        .flat_map(|(n, _)| [n.to_string(), n.to_lowercase()])
+        .collect();
+    json_schema!({
+        "type": "string",
+        "anyOf": [
+            { "minLength": 1, "maxLength": 1 },
+            { "enum": names }
+        ]
+    })
+}
+
+fn add_modifiers_aliases(schema: &mut Schema) {
+    let modifiers = schema.get("properties").and_then(|p| p.get("modifiers")).cloned();
+    if let (Some(modifiers), Some(props)) = (modifiers, schema.get_mut("properties")) {
+        if let Some(props) = props.as_object_mut() {
+            props.insert("modifier".to_string(), modifiers.clone());
+            props.insert("mod".to_string(), modifiers);
         }
     }
 }
+
+fn default_bindings() -> serde_json::Value {
+    let bind = |modifiers: &str, key: &str, action: &str| {
+        serde_json::json!({
+            "modifiers": modifiers,
+            "key": key,
+            "action": action,
+        })
+    };
+    let directions = ["left", "right", "up", "down"];
+    let directional = [
+        ("WIN", "focus"),
+        ("WIN+ALT", "moveinsert"),
+        ("WIN+CTRL+ALT", "insert"),
+        ("WIN+SHIFT", "resize"),
+    ];
+
+    let mut bindings: Vec<serde_json::Value> = directional
+        .iter()
+        .flat_map(|(m, a)| {
+            directions.iter().map(move |d| match *a {
+                "resize" => bind(m, d, &format!("{a} {d} 150")),
+                _ => bind(m, d, &format!("{a} {d}")),
+            })
+        })
+        .collect();
+    bindings.extend([
+        bind("ALT", "r", "release"),
+        bind("ALT", "f", "focalize"),
+        bind("ALT", "m", "minimize"),
+        bind("ALT", "v", "invert"),
+        bind("ALT+SHIFT", "p", "pause"),
+        bind("ALT+CTRL+SHIFT", "i", "dumpstateinfo"),
+    ]);
+    serde_json::Value::Array(bindings)
+}
@@ -1,3 +1,4 @@
+use crate::app::configs::changes::ConfigSection;
 use crate::app::configs::AppConfig;
 use crate::app::mondrian_message::MondrianMessage;
 use crate::app::structs::info_entry::InfoEntry;
@@ -151,7 +152,7 @@ impl ModuleImpl for Keybindings {
                 Module::enable(self, app_configs.modules.keybindings.enabled);
                 self.configure(app_configs.into());
             }
-            MondrianMessage::RefreshConfig => {
+            MondrianMessage::ConfigChanged(changes) if changes.contains(ConfigSection::Keybindings) => {
                 Module::enable(self, app_configs.modules.keybindings.enabled);
                 self.configure(app_configs.into());
                 Module::restart(self);
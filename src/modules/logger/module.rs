@@ -97,7 +97,7 @@ impl ModuleImpl for Logger {
     }
 }
 
-mod info_response_builder {
+pub(crate) mod info_response_builder {
     use crate::app::structs::info_entry::InfoEntry;
 
     pub fn build_info_response<P: Into<String> + Clone + Copy>(
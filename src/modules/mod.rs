@@ -2,6 +2,7 @@ pub mod events_monitor {
     pub mod configs;
     pub mod module;
     pub(crate) mod lib {
+        pub mod delay_scheduler;
         pub mod filter;
         pub mod focus_event_handler;
         pub mod maximize_event_handler;
@@ -9,6 +10,7 @@ pub mod events_monitor {
         pub mod open_event_handler;
         pub mod position_event_handler;
         pub mod system_events_detector;
+        pub mod title_event_handler;
     }
 }
 
@@ -17,6 +19,7 @@ pub mod tiles_manager {
     pub mod module;
     pub(crate) mod lib {
         pub mod containers;
+        pub mod frame_sink;
         pub mod structs;
         pub mod tm;
         pub mod utils;
@@ -3,53 +3,77 @@ use super::lib::overlay::OverlayParams;
 use crate::app::configs::deserializers;
 use crate::app::configs::AppConfig;
 use crate::app::mondrian_message::WindowTileState;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+/// Overlays module settings
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct OverlaysModuleConfigs {
+    /// Enables or disables the module
     pub enabled: bool,
+    /// Updates the overlays while dragging the window
     pub update_while_dragging: bool,
+    /// Updates the overlays while the animation is running
     pub update_while_animating: bool,
 
+    /// Thickness of the overlays borders (in pixels)
     #[serde(deserialize_with = "deserializers::to_u8_max::<100,_>")]
+    #[schemars(range(max = 100), example = 2)]
     pub thickness: u8,
 
+    /// Border radius of the overlays (in pixels)
     #[serde(deserialize_with = "deserializers::to_u8_max::<100,_>")]
+    #[schemars(range(max = 100), example = 10)]
     pub border_radius: u8,
 
+    /// Padding between the overlay and the window (in pixels)
     #[serde(deserialize_with = "deserializers::to_u8_max::<30,_>")]
+    #[schemars(range(max = 30))]
     pub padding: u8,
 
+    /// Scales thickness, border radius and padding by the DPI of the monitor of the window
+    pub scale_with_dpi: bool,
+
+    /// Active overlay settings
     #[serde(
         default = "ExtOverlayParams::default_active",
         deserialize_with = "deserialize_active"
     )]
+    #[schemars(with = "ExtOptOverlayParams")]
     active: ExtOverlayParams,
 
+    /// Inactive overlay settings
     #[serde(
         default = "ExtOverlayParams::default_inactive",
         deserialize_with = "deserialize_inactive"
     )]
+    #[schemars(with = "ExtOptOverlayParams", example = ExtOverlayParams::new(true, Color::solid(105, 105, 105)))]
     inactive: ExtOverlayParams,
 
+    /// Focalized overlay settings
     #[serde(
         default = "ExtOverlayParams::default_focalized",
         deserialize_with = "deserialize_focalized"
     )]
+    #[schemars(with = "ExtOptOverlayParams")]
     focalized: ExtOverlayParams,
 
+    /// Floating overlay settings
     #[serde(
         default = "ExtOverlayParams::default_floating",
         deserialize_with = "deserialize_floating"
     )]
+    #[schemars(with = "ExtOptOverlayParams")]
     floating: ExtOverlayParams,
 
+    /// Half-focalized overlay settings
     #[serde(
         default = "ExtOverlayParams::default_half_focalized",
         deserialize_with = "deserialize_half_focalized"
     )]
+    #[schemars(with = "ExtOptOverlayParams")]
     half_focalized: ExtOverlayParams,
 }
 
@@ -62,6 +86,7 @@ impl Default for OverlaysModuleConfigs {
             thickness: 4,
             border_radius: 15,
             padding: 0,
+            scale_with_dpi: false,
             active: ExtOverlayParams::default_active(),
             inactive: ExtOverlayParams::default_inactive(),
             focalized: ExtOverlayParams::default_focalized(),
@@ -82,9 +107,10 @@ impl OverlaysModuleConfigs {
         OverlayParams::new(
             true,
             Color::new(0, 0, 0, 0),
-            self.thickness,
-            self.border_radius,
-            self.padding,
+            self.thickness.into(),
+            self.border_radius.into(),
+            self.padding.into(),
+            self.scale_with_dpi,
         )
     }
 
@@ -141,9 +167,10 @@ impl OverlaysModuleConfigs {
         OverlayParams::new(
             ext_overlay_params.enabled,
             ext_overlay_params.color,
-            self.thickness,
-            self.border_radius,
-            self.padding,
+            self.thickness.into(),
+            self.border_radius.into(),
+            self.padding.into(),
+            self.scale_with_dpi,
         )
     }
 
@@ -156,14 +183,16 @@ impl OverlaysModuleConfigs {
     }
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
 #[serde(deny_unknown_fields)]
 struct ExtOptOverlayParams {
+    /// Shows or hides the overlay
     pub enabled: Option<bool>,
+    /// Color of the overlay
     pub color: Option<Color>,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 struct ExtOverlayParams {
     pub enabled: bool,
     pub color: Color,
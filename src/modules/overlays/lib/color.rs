@@ -1,12 +1,17 @@
 use core::fmt;
 use regex::Regex;
+use schemars::json_schema;
+use schemars::JsonSchema;
+use schemars::Schema;
+use schemars::SchemaGenerator;
 use serde::de;
 use serde::de::Visitor;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
+use serde::Serializer;
 
-#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Color {
     red: u8,
     green: u8,
@@ -33,6 +38,37 @@ impl Color {
     }
 }
 
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let rgb = format!("#{:02X}{:02X}{:02X}", self.red, self.green, self.blue);
+        match self.alpha {
+            255 => serializer.serialize_str(&rgb),
+            alpha => serializer.serialize_str(&format!("{rgb}{alpha:02X}")),
+        }
+    }
+}
+
+impl JsonSchema for Color {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Color".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "Color, as a hex string (\"#RRGGBB\"/\"#RRGGBBAA\") or as an array ([R, G, B]/[R, G, B, A])",
+            "oneOf": [
+                { "type": "string", "pattern": "^#?([0-9A-Fa-f]{6}|[0-9A-Fa-f]{8})$" },
+                {
+                    "type": "array",
+                    "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+                    "minItems": 3,
+                    "maxItems": 4
+                }
+            ]
+        })
+    }
+}
+
 impl<'de> Deserialize<'de> for Color {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
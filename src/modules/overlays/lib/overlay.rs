@@ -3,7 +3,9 @@ use super::utils::overlay;
 use super::utils::overlay::WM_USER_CONFIGURE;
 use crate::win32::api::misc::post_empty_message;
 use crate::win32::api::misc::post_message;
+use crate::win32::api::monitor::DEFAULT_DPI;
 use crate::win32::api::window::destroy_window;
+use crate::win32::api::window::get_dpi_for_window;
 use crate::win32::api::window::show_window;
 use crate::win32::win_event_loop::start_win_event_loop;
 use crate::win32::window::window_ref::WindowRef;
@@ -31,6 +33,7 @@ pub struct Overlay {
     current_state: Arc<(Mutex<InitState>, Condvar)>,
     params: OverlayParams,
     class_name: String,
+    dpi: u32,
 }
 
 impl Overlay {
@@ -42,6 +45,7 @@ impl Overlay {
             overlay_handle: Arc::new(AtomicIsize::new(0)),
             params,
             main_thread: None,
+            dpi: DEFAULT_DPI,
         }
     }
 
@@ -51,11 +55,12 @@ impl Overlay {
         }
 
         self.params = params.unwrap_or(self.params);
+        self.dpi = self.get_target_dpi();
 
         let class_name = self.class_name.clone();
         let overlay_handle = self.overlay_handle.clone();
         let target = self.target;
-        let params = self.params;
+        let params = self.params.scaled(self.dpi);
         let current_state = self.current_state.clone();
 
         Self::set_init_state(&self.current_state, InitState::Creating);
@@ -109,8 +114,10 @@ impl Overlay {
         }
 
         self.params = params;
+        self.dpi = self.get_target_dpi();
         if let Some(o) = self.get_overlay_handle() {
-            overlay::move_to_target(o, self.target.into(), &self.params);
+            let params = self.params.scaled(self.dpi);
+            overlay::move_to_target(o, self.target.into(), &params);
             post_message(o, WM_USER_CONFIGURE, Some(params));
         };
 
@@ -125,8 +132,17 @@ impl Overlay {
     }
 
     pub fn reposition(&mut self) {
-        if let Some(o) = self.get_overlay_handle() {
-            overlay::move_to_target(o, self.target.into(), &self.params);
+        let Some(o) = self.get_overlay_handle() else {
+            return;
+        };
+
+        // INFO: the overlay is redrawn if the window moved to a monitor with a different DPI
+        let dpi = self.get_target_dpi();
+        let params = self.params.scaled(dpi);
+        overlay::move_to_target(o, self.target.into(), &params);
+        if dpi != self.dpi {
+            self.dpi = dpi;
+            post_message(o, WM_USER_CONFIGURE, Some(params));
         }
     }
 
@@ -142,6 +158,15 @@ impl Overlay {
         }
     }
 
+    fn get_target_dpi(&self) -> u32 {
+        match self.params.scale_with_dpi {
+            true => Some(get_dpi_for_window(self.target.into()))
+                .filter(|d| *d > 0)
+                .unwrap_or(DEFAULT_DPI),
+            false => DEFAULT_DPI,
+        }
+    }
+
     fn set_init_state(state_variable: &Arc<(Mutex<InitState>, Condvar)>, state: InitState) {
         let (lock, cvar) = &**state_variable;
         let mut guard = lock.lock().unwrap();
@@ -160,9 +185,10 @@ impl Drop for Overlay {
 pub struct OverlayParams {
     pub enabled: bool,
     pub color: Color,
-    pub thickness: u8,
-    pub border_radius: u8,
-    pub padding: u8,
+    pub thickness: i16,
+    pub border_radius: i16,
+    pub padding: i16,
+    pub scale_with_dpi: bool,
 }
 
 impl Default for OverlayParams {
@@ -172,17 +198,41 @@ impl Default for OverlayParams {
 }
 
 impl OverlayParams {
-    pub fn new(enabled: bool, color: Color, thickness: u8, border_radius: u8, padding: u8) -> OverlayParams {
+    pub fn new(
+        enabled: bool,
+        color: Color,
+        thickness: i16,
+        border_radius: i16,
+        padding: i16,
+        scale_with_dpi: bool,
+    ) -> OverlayParams {
         OverlayParams {
             enabled,
             color,
             thickness,
             border_radius,
             padding,
+            scale_with_dpi,
         }
     }
 
     pub fn empty() -> OverlayParams {
-        OverlayParams::new(false, Color::solid(0, 0, 0), 0, 0, 0)
+        OverlayParams::new(false, Color::solid(0, 0, 0), 0, 0, 0, false)
+    }
+
+    /// Returns the parameters scaled by the given DPI, if `scale_with_dpi` is enabled
+    pub fn scaled(&self, dpi: u32) -> OverlayParams {
+        if !self.scale_with_dpi {
+            return *self;
+        }
+
+        let factor = dpi as f32 / DEFAULT_DPI as f32;
+        let scale = |v: i16| (v as f32 * factor).round() as i16;
+        OverlayParams {
+            thickness: scale(self.thickness),
+            border_radius: scale(self.border_radius),
+            padding: scale(self.padding),
+            ..*self
+        }
     }
 }
@@ -218,10 +218,10 @@ pub mod overlay {
         hwnd
     }
 
-    pub fn get_box_from_target(target: HWND, thickness: u8, padding: u8) -> Option<Area> {
+    pub fn get_box_from_target(target: HWND, thickness: i16, padding: i16) -> Option<Area> {
         let offset = 1.5 * thickness as f32;
-        let shift1 = offset.ceil() as i16 + (padding as i16) - 1;
-        let shift2 = (2.0 * offset).ceil() as i16 + 2 * (padding as i16) - 2;
+        let shift1 = offset.ceil() as i16 + padding - 1;
+        let shift2 = (2.0 * offset).ceil() as i16 + 2 * padding - 2;
         let visible_area = WindowRef::new(target).get_visible_area()?;
         Some(visible_area.shift((-shift1, -shift1, shift2, shift2)))
     }
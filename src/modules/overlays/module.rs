@@ -5,6 +5,7 @@ use super::lib::overlay_manager::OverlaysManagerEnum;
 use super::lib::overlay_manager::OverlaysManagerTrait;
 use super::lib::overlays_event_handler::OverlayEventHandler;
 use super::lib::utils::overlay::overlay_win_proc;
+use crate::app::configs::changes::ConfigSection;
 use crate::app::configs::AppConfig;
 use crate::app::mondrian_message::MondrianMessage;
 use crate::app::mondrian_message::WindowEvent;
@@ -122,7 +123,7 @@ impl ModuleImpl for Overlays {
                 Module::enable(self, app_configs.modules.overlays.enabled);
                 self.configure(app_configs.into());
             }
-            MondrianMessage::RefreshConfig => {
+            MondrianMessage::ConfigChanged(changes) if changes.contains(ConfigSection::Overlays) => {
                 Module::enable(self, app_configs.modules.overlays.enabled);
                 self.configure(app_configs.into());
                 Module::restart(self);
@@ -5,6 +5,8 @@ use crate::app::configs::AppConfig;
 pub struct CoreModuleConfigs {
     pub move_cursor_on_focus: bool,
     pub history_based_navigation: bool,
+    pub events_coalescing: u32,
+    pub max_batch_latency: u32,
     pub tm_configs: TilesManagerConfig,
 }
 
@@ -13,6 +15,8 @@ impl From<&AppConfig> for CoreModuleConfigs {
         CoreModuleConfigs {
             move_cursor_on_focus: configs.move_cursor_on_focus,
             history_based_navigation: configs.history_based_navigation,
+            events_coalescing: configs.events_coalescing,
+            max_batch_latency: configs.max_batch_latency,
             tm_configs: TilesManagerConfig::from(configs),
         }
     }
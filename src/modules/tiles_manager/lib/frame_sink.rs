@@ -0,0 +1,152 @@
+use crate::app::structs::area::Area;
+use crate::win32::window::window_obj::WindowObjHandler;
+use crate::win32::window::window_obj::WindowObjInfo;
+use crate::win32::window::window_ref::WindowRef;
+use windows::Win32::UI::WindowsAndMessaging::SWP_NOACTIVATE;
+use windows::Win32::UI::WindowsAndMessaging::SWP_NOSENDCHANGING;
+use windows::Win32::UI::WindowsAndMessaging::SWP_NOZORDER;
+use windows::Win32::UI::WindowsAndMessaging::SWP_SHOWWINDOW;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_LAYERED;
+
+/// Destination of the frames of the animations: every change made to the windows by the
+/// `WindowAnimationPlayer` goes through it
+pub trait FrameSink: Send + Sync {
+    fn get_area(&self, window: WindowRef) -> Option<Area>;
+    /// Returns true if the window is already layered, in which case it can't fade
+    fn is_layered(&self, window: WindowRef) -> bool;
+    /// Moves the window to an intermediate area of its animation
+    fn draw_frame(&self, window: WindowRef, area: Area) -> Result<(), ()>;
+    /// Moves the window to its final area, showing it
+    fn place(&self, window: WindowRef, area: Area);
+    fn set_topmost(&self, window: WindowRef, topmost: bool);
+    fn set_opacity(&self, window: WindowRef, alpha: Option<u8>);
+    fn minimize(&self, window: WindowRef);
+}
+
+/// Frame sink moving the actual windows
+pub struct Win32FrameSink;
+
+impl FrameSink for Win32FrameSink {
+    fn get_area(&self, window: WindowRef) -> Option<Area> {
+        window.get_area()
+    }
+
+    fn is_layered(&self, window: WindowRef) -> bool {
+        window.get_window_exstyle() & WS_EX_LAYERED.0 != 0
+    }
+
+    fn draw_frame(&self, window: WindowRef, area: Area) -> Result<(), ()> {
+        let flags = SWP_NOSENDCHANGING | SWP_NOACTIVATE | SWP_NOZORDER;
+        window.resize_and_move(area.get_origin(), area.get_size(), false, flags)
+    }
+
+    fn place(&self, window: WindowRef, area: Area) {
+        let flags = SWP_SHOWWINDOW | SWP_NOSENDCHANGING | SWP_NOACTIVATE | SWP_NOZORDER;
+        window
+            .resize_and_move(area.get_origin(), area.get_size(), false, flags)
+            .ok();
+    }
+
+    fn set_topmost(&self, window: WindowRef, topmost: bool) {
+        window.set_topmost(topmost).ok();
+    }
+
+    fn set_opacity(&self, window: WindowRef, alpha: Option<u8>) {
+        window.set_opacity(alpha).ok();
+    }
+
+    fn minimize(&self, window: WindowRef) {
+        window.minimize(false);
+    }
+}
+
+#[cfg(test)]
+pub use recording::RecordingFrameSink;
+#[cfg(test)]
+pub use recording::SinkEvent;
+
+#[cfg(test)]
+mod recording {
+    use super::FrameSink;
+    use crate::app::structs::area::Area;
+    use crate::win32::window::window_ref::WindowRef;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum SinkEvent {
+        Frame(WindowRef, Area),
+        Place(WindowRef, Area),
+        Topmost(WindowRef, bool),
+        Opacity(WindowRef, Option<u8>),
+        Minimize(WindowRef),
+    }
+
+    /// Frame sink recording the changes instead of applying them to the actual windows
+    #[derive(Default)]
+    pub struct RecordingFrameSink {
+        areas: Mutex<HashMap<WindowRef, Area>>,
+        events: Mutex<Vec<SinkEvent>>,
+    }
+
+    impl RecordingFrameSink {
+        pub fn new(areas: impl IntoIterator<Item = (WindowRef, Area)>) -> Self {
+            RecordingFrameSink {
+                areas: Mutex::new(areas.into_iter().collect()),
+                events: Mutex::new(Vec::new()),
+            }
+        }
+
+        pub fn events(&self) -> Vec<SinkEvent> {
+            self.events.lock().unwrap().clone()
+        }
+
+        /// Returns the intermediate areas of the window, in order
+        pub fn frames(&self, window: WindowRef) -> Vec<Area> {
+            self.events()
+                .into_iter()
+                .filter_map(|e| match e {
+                    SinkEvent::Frame(w, area) if w == window => Some(area),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        fn record(&self, event: SinkEvent) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    impl FrameSink for RecordingFrameSink {
+        fn get_area(&self, window: WindowRef) -> Option<Area> {
+            self.areas.lock().unwrap().get(&window).copied()
+        }
+
+        fn is_layered(&self, _window: WindowRef) -> bool {
+            false
+        }
+
+        fn draw_frame(&self, window: WindowRef, area: Area) -> Result<(), ()> {
+            self.areas.lock().unwrap().insert(window, area);
+            self.record(SinkEvent::Frame(window, area));
+            Ok(())
+        }
+
+        fn place(&self, window: WindowRef, area: Area) {
+            self.areas.lock().unwrap().insert(window, area);
+            self.record(SinkEvent::Place(window, area));
+        }
+
+        fn set_topmost(&self, window: WindowRef, topmost: bool) {
+            self.record(SinkEvent::Topmost(window, topmost));
+        }
+
+        fn set_opacity(&self, window: WindowRef, alpha: Option<u8>) {
+            self.record(SinkEvent::Opacity(window, alpha));
+        }
+
+        fn minimize(&self, window: WindowRef) {
+            self.record(SinkEvent::Minimize(window));
+        }
+    }
+}
@@ -1,25 +1,47 @@
 use crate::{
-    app::configs::{
-        floating::FloatingWinsConfig,
-        rules::{WindowBehavior, WindowRule},
+    app::{
+        configs::{
+            floating::FloatingWinsConfig,
+            rules::{InsertDelay, WindowBehavior, WindowRule},
+        },
+        structs::{
+            info_entry::{InfoEntry, InfoEntryIcon},
+            win_matcher::WinMatcher,
+        },
     },
-    win32::window::window_ref::WindowRef,
+    win32::{api::monitor::Monitor, window::window_obj::WindowObjInfo},
 };
+use std::fmt::Debug;
+use std::fmt::Display;
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct WorkspaceOptions {
     pub workspace: String,
     pub silent: bool,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct AddOptions {
     pub monitor: Option<String>,
     pub workspace_options: Option<WorkspaceOptions>,
     pub floating_config: Option<FloatingWinsConfig>,
+    pub focalize: bool,
+    pub half_focalize: bool,
+    pub ratio: Option<u8>,
+    pub start_minimized: bool,
+    pub window_options: WindowOptions,
+}
+
+/// Options applied to a tiled window for as long as it is managed
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct WindowOptions {
+    pub padding: Option<u8>,
+    pub overlay: Option<bool>,
+    pub topmost: bool,
 }
 
 impl AddOptions {
+    /// Merges the options set by the rule, overriding the ones set by the previous rules
     pub fn merge_with_rule(&mut self, other: &WindowRule) {
         match &other.behavior {
             WindowBehavior::Float { config } => self.floating_config = Some(*config),
@@ -28,36 +50,303 @@ impl AddOptions {
                 workspace,
                 silent,
             } => {
-                self.monitor = monitor.clone();
-                self.workspace_options = workspace.as_ref().map(|w| WorkspaceOptions {
-                    workspace: w.clone(),
-                    silent: *silent,
-                });
+                if monitor.is_some() {
+                    self.monitor = monitor.clone();
+                }
+                if let Some(workspace) = workspace {
+                    self.workspace_options = Some(WorkspaceOptions {
+                        workspace: workspace.clone(),
+                        silent: *silent,
+                    });
+                }
             }
+            WindowBehavior::Focalize => self.focalize = true,
+            WindowBehavior::HalfFocalize => self.half_focalize = true,
+            WindowBehavior::Ratio(ratio) => self.ratio = Some(*ratio),
+            WindowBehavior::Padding(padding) => self.window_options.padding = Some(*padding),
+            WindowBehavior::Overlay(enabled) => self.window_options.overlay = Some(*enabled),
+            WindowBehavior::StartMinimized => self.start_minimized = true,
+            WindowBehavior::Topmost => self.window_options.topmost = true,
         }
     }
 }
 
 pub trait Rules {
-    fn get_add_options(&self, window: WindowRef) -> Option<AddOptions>;
+    fn get_add_options<T: WindowObjInfo + Clone>(&self, window: T, monitors: &[Monitor]) -> Option<AddOptions>;
 }
 
-impl Rules for Vec<WindowRule> {
-    fn get_add_options(&self, window: WindowRef) -> Option<AddOptions> {
-        let rules = find_matches(self, window);
-        let mut empty = true;
+impl Rules for [WindowRule] {
+    fn get_add_options<T: WindowObjInfo + Clone>(&self, window: T, monitors: &[Monitor]) -> Option<AddOptions> {
+        let rules: Vec<&WindowRule> = find_matches(self, window, monitors).collect();
+        if rules.is_empty() {
+            return None;
+        }
+
+        // INFO: the rules are sorted by priority, so a stop rule discards the ones before it
+        let start = rules.iter().rposition(|r| r.stop).unwrap_or(0);
         let mut options = AddOptions::default();
-        rules.for_each(|rule| {
-            empty = false;
-            options.merge_with_rule(rule);
-        });
-        match empty {
-            true => None,
-            false => Some(options),
+        rules[start..].iter().for_each(|r| options.merge_with_rule(r));
+        Some(options)
+    }
+}
+
+fn find_matches<'a, T: WindowObjInfo + Clone + 'a>(
+    rules: &'a [WindowRule],
+    window: T,
+    monitors: &'a [Monitor],
+) -> impl Iterator<Item = &'a WindowRule> {
+    rules
+        .iter()
+        .filter(move |r| r.filter.matches_on(window.clone(), monitors))
+}
+
+/// What happens to a window when it opens, according to the rules
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RulesOutcome {
+    pub ignored: bool,
+    pub delay: Option<InsertDelay>,
+    pub floating: bool,
+    pub monitor: Option<String>,
+    pub workspace: Option<String>,
+}
+
+impl Display for RulesOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ignored {
+            return write!(f, "ignored");
         }
+
+        let mut details = vec![match self.floating {
+            true => "floating".to_string(),
+            false => "tiled".to_string(),
+        }];
+        details.extend(self.monitor.as_ref().map(|m| format!("monitor: {m}")));
+        details.extend(self.workspace.as_ref().map(|w| format!("workspace: {w}")));
+        details.extend(self.delay.map(|d| format!("delayed: {d}")));
+        write!(f, "managed ({})", details.join(", "))
     }
 }
 
-fn find_matches(rules: &[WindowRule], window: WindowRef) -> impl Iterator<Item = &WindowRule> {
-    rules.iter().filter(move |r| r.filter.matches(window))
+/// Returns what happens to the window when it opens, according to the ignore filter, the delayed
+/// insertion filters and the rules
+pub fn get_rules_outcome<T: WindowObjInfo + Clone>(
+    ignore_filter: &WinMatcher,
+    delayed_filter: &[(WinMatcher, InsertDelay)],
+    rules: &[WindowRule],
+    window: T,
+    monitors: &[Monitor],
+) -> RulesOutcome {
+    if ignore_filter.matches_on(window.clone(), monitors) {
+        return RulesOutcome {
+            ignored: true,
+            ..Default::default()
+        };
+    }
+
+    let delay = delayed_filter
+        .iter()
+        .find(|(f, _)| f.matches_on(window.clone(), monitors))
+        .map(|(_, d)| *d);
+    let options = rules.get_add_options(window, monitors).unwrap_or_default();
+    RulesOutcome {
+        ignored: false,
+        delay,
+        floating: options.floating_config.is_some(),
+        monitor: options.monitor,
+        workspace: options.workspace_options.map(|w| w.workspace),
+    }
+}
+
+/// Returns the options given by the rules to a managed window, or `None` if the window is ignored.
+/// A managed window is added again when this outcome changes (e.g. after a title change)
+pub fn get_managed_outcome<T: WindowObjInfo + Clone>(
+    ignore_filter: &WinMatcher,
+    rules: &[WindowRule],
+    window: T,
+    monitors: &[Monitor],
+) -> Option<AddOptions> {
+    match ignore_filter.matches_on(window.clone(), monitors) {
+        true => None,
+        false => Some(rules.get_add_options(window, monitors).unwrap_or_default()),
+    }
+}
+
+/// Returns, for each rule, the ignore filter and the delayed insertion filters, whether they match
+/// the window and the sub-filter that decided it, along with the resulting `AddOptions`
+pub fn explain_rules<T: WindowObjInfo + Clone + Debug>(
+    ignore_filter: &WinMatcher,
+    delayed_filter: &[(WinMatcher, InsertDelay)],
+    rules: &[WindowRule],
+    window: T,
+    monitors: &[Monitor],
+) -> Vec<InfoEntry> {
+    let explain = |title: String, filter: &WinMatcher| {
+        let (matched, reason) = filter.explain(window.clone(), monitors);
+        let (icon, value) = match matched {
+            true => (InfoEntryIcon::Enabled, "matched"),
+            false => (InfoEntryIcon::Disabled, "not matched"),
+        };
+        let subentries = [
+            InfoEntry::simple("Filter", filter.to_string()),
+            InfoEntry::simple("Decided by", reason),
+        ];
+        InfoEntry::new(title, Some(value.to_string()), subentries, icon)
+    };
+
+    let window_info = format!("{:?}", window);
+    let ignore_info = explain("Ignore filter".to_string(), ignore_filter);
+    let delayed_info = delayed_filter
+        .iter()
+        .enumerate()
+        .map(|(i, (f, delay))| explain(format!("Delayed insertion #{} ({delay})", i + 1), f));
+    let stopped_at = rules
+        .iter()
+        .rposition(|r| r.stop && r.filter.matches_on(window.clone(), monitors));
+    let rules_info = rules.iter().enumerate().map(|(i, r)| {
+        let behavior = match &r.behavior {
+            WindowBehavior::Float { config } if config.global => "float (global)".to_string(),
+            WindowBehavior::Float { .. } => "float".to_string(),
+            WindowBehavior::Insert {
+                monitor,
+                workspace,
+                silent,
+            } => format!(
+                "insert (monitor: {}, workspace: {}, silent: {silent})",
+                monitor.as_deref().unwrap_or("/"),
+                workspace.as_deref().unwrap_or("/")
+            ),
+            WindowBehavior::Focalize => "focalize".to_string(),
+            WindowBehavior::HalfFocalize => "half-focalize".to_string(),
+            WindowBehavior::Ratio(ratio) => format!("ratio ({ratio}%)"),
+            WindowBehavior::Padding(padding) => format!("padding ({padding} px)"),
+            WindowBehavior::Overlay(enabled) => format!("overlay ({enabled})"),
+            WindowBehavior::StartMinimized => "start minimized".to_string(),
+            WindowBehavior::Topmost => "topmost".to_string(),
+        };
+        let stop = if r.stop { ", stop" } else { "" };
+        let title = format!("Rule #{} ({behavior}, priority: {}{stop})", i + 1, r.priority);
+        match stopped_at.filter(|s| i < *s) {
+            Some(s) => explain(format!("{title}, not applied: stopped by Rule #{}", s + 1), &r.filter),
+            None => explain(title, &r.filter),
+        }
+    });
+    let options = rules
+        .get_add_options(window.clone(), monitors)
+        .map_or("/".to_string(), |o| format!("{o:?}"));
+    let outcome = get_rules_outcome(ignore_filter, delayed_filter, rules, window, monitors);
+
+    vec![
+        InfoEntry::simple("Window", window_info.trim_start_matches("WindowSnapshot ")).with_icon(InfoEntryIcon::Window),
+        ignore_info,
+        InfoEntry::list("Delayed insertion rules", delayed_info).with_icon(InfoEntryIcon::Configs),
+        InfoEntry::list("Rules", rules_info).with_icon(InfoEntryIcon::Configs),
+        InfoEntry::simple("Resulting add options", options).with_icon(InfoEntryIcon::Action),
+        InfoEntry::simple("Outcome", outcome.to_string()).with_icon(InfoEntryIcon::Action),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_managed_outcome, AddOptions, Rules, WorkspaceOptions};
+    use crate::app::configs::rules::{WindowBehavior, WindowRule};
+    use crate::app::rules_tester::WindowDescriptor;
+    use crate::app::structs::win_matcher::{WinMatcher, WinQuery};
+    use crate::app::test_utils::window;
+
+    fn rule(exename: &str, behavior: WindowBehavior, priority: i32, stop: bool) -> WindowRule {
+        WindowRule {
+            filter: WinMatcher::Exename(WinQuery::contains(exename)),
+            behavior,
+            priority,
+            stop,
+        }
+    }
+
+    fn insert(monitor: Option<&str>, workspace: Option<&str>) -> WindowBehavior {
+        WindowBehavior::Insert {
+            monitor: monitor.map(String::from),
+            workspace: workspace.map(String::from),
+            silent: false,
+        }
+    }
+
+    fn workspace(name: &str) -> Option<WorkspaceOptions> {
+        Some(WorkspaceOptions {
+            workspace: name.to_string(),
+            silent: false,
+        })
+    }
+
+    #[test]
+    fn test_merge_last_defined_wins() {
+        let rules = [
+            rule("app.exe", insert(None, Some("1")), 0, false),
+            rule("app.exe", WindowBehavior::Ratio(30), 0, false),
+            rule("app.exe", insert(None, Some("2")), 0, false),
+            rule("other.exe", insert(None, Some("3")), 0, false),
+        ];
+
+        let options = rules.get_add_options(window("app.exe"), &[]).unwrap();
+        assert_eq!(options.workspace_options, workspace("2"));
+        assert_eq!(options.ratio, Some(30));
+        assert!(rules.get_add_options(window("none.exe"), &[]).is_none());
+    }
+
+    #[test]
+    fn test_merge_by_field() {
+        // INFO: the rules are sorted by priority, in ascending order
+        let rules = [
+            rule("app.exe", insert(Some("MONITOR1"), Some("web")), 0, false),
+            rule("app.exe", insert(Some("MONITOR2"), None), 10, false),
+        ];
+
+        let options = rules.get_add_options(window("app.exe"), &[]).unwrap();
+        assert_eq!(options.monitor, Some("MONITOR2".to_string()));
+        assert_eq!(options.workspace_options, workspace("web"));
+    }
+
+    #[test]
+    fn test_stop() {
+        let rules = [
+            rule("app.exe", insert(None, Some("1")), 0, false),
+            rule("app.exe", WindowBehavior::Focalize, 5, true),
+            rule("other.exe", WindowBehavior::HalfFocalize, 8, true),
+            rule("app.exe", WindowBehavior::Ratio(30), 10, false),
+        ];
+
+        let options = rules.get_add_options(window("app.exe"), &[]).unwrap();
+        assert_eq!(options.workspace_options, None);
+        assert!(options.focalize);
+        assert!(!options.half_focalize);
+        assert_eq!(options.ratio, Some(30));
+    }
+
+    #[test]
+    fn test_managed_outcome_on_title_change() {
+        let ignore_filter = WinMatcher::Title(WinQuery::contains("Splash"));
+        let rules = [WindowRule {
+            filter: WinMatcher::Title(WinQuery::contains("Picture-in-Picture")),
+            behavior: WindowBehavior::Topmost,
+            priority: 0,
+            stop: false,
+        }];
+        let titled = |title: &str| WindowDescriptor {
+            title: Some(title.to_string()),
+            ..window("app.exe")
+        };
+
+        let outcome = get_managed_outcome(&ignore_filter, &rules, titled("Loading"), &[]);
+        assert_eq!(outcome, Some(AddOptions::default()));
+
+        // INFO: the window is left untouched if the new title does not change the outcome
+        assert_eq!(
+            get_managed_outcome(&ignore_filter, &rules, titled("Home"), &[]),
+            outcome
+        );
+
+        let outcome = get_managed_outcome(&ignore_filter, &rules, titled("Picture-in-Picture"), &[]);
+        assert!(outcome.is_some_and(|o| o.window_options.topmost));
+
+        assert_eq!(get_managed_outcome(&ignore_filter, &rules, titled("Splash"), &[]), None);
+    }
 }
@@ -0,0 +1,93 @@
+use crate::modules::tiles_manager::lib::window_animation_player::AnimationKind;
+use crate::win32::window::window_ref::WindowRef;
+
+/// Batch of commands handled with a single layout update
+#[derive(Default)]
+pub struct LayoutBatch {
+    active: bool,
+    pending: Option<PendingUpdate>,
+}
+
+/// Layout update postponed until the end of the current batch
+#[derive(Debug, Default, PartialEq)]
+pub struct PendingUpdate {
+    pub animate: bool,
+    pub win_in_focus: Option<WindowRef>,
+    pub animation_kind: AnimationKind,
+}
+
+impl LayoutBatch {
+    pub fn begin(&mut self) {
+        self.active = true;
+    }
+
+    /// Merges the layout update into the pending one, returns false if no batch is active
+    pub fn postpone(&mut self, animate: bool, win_in_focus: Option<WindowRef>, animation_kind: AnimationKind) -> bool {
+        if !self.active {
+            return false;
+        }
+
+        let pending = self.pending.get_or_insert_with(PendingUpdate::default);
+        pending.animate |= animate;
+        pending.win_in_focus = win_in_focus.or(pending.win_in_focus);
+        if animation_kind != AnimationKind::Default {
+            pending.animation_kind = animation_kind;
+        }
+        true
+    }
+
+    /// Ends the batch, returning the layout update postponed during it (if any)
+    pub fn end(&mut self) -> Option<PendingUpdate> {
+        self.active = false;
+        self.pending.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayoutBatch;
+    use super::PendingUpdate;
+    use crate::modules::tiles_manager::lib::window_animation_player::AnimationKind;
+    use crate::win32::window::window_ref::WindowRef;
+
+    #[test]
+    fn test_no_batch() {
+        let mut batch = LayoutBatch::default();
+        assert!(!batch.postpone(true, None, AnimationKind::Default));
+        assert_eq!(batch.end(), None);
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let mut batch = LayoutBatch::default();
+        batch.begin();
+        assert_eq!(batch.end(), None);
+    }
+
+    #[test]
+    fn test_batch_merges_updates() {
+        let mut batch = LayoutBatch::default();
+        batch.begin();
+        assert!(batch.postpone(false, Some(WindowRef::from(1)), AnimationKind::Swap));
+        assert!(batch.postpone(true, None, AnimationKind::Default));
+        assert!(batch.postpone(false, Some(WindowRef::from(2)), AnimationKind::Default));
+
+        let expected = PendingUpdate {
+            animate: true,
+            win_in_focus: Some(WindowRef::from(2)),
+            animation_kind: AnimationKind::Swap,
+        };
+        assert_eq!(batch.end(), Some(expected));
+    }
+
+    #[test]
+    fn test_batch_ends_once() {
+        let mut batch = LayoutBatch::default();
+        batch.begin();
+        batch.postpone(true, None, AnimationKind::Open);
+        assert!(batch.end().is_some());
+
+        assert!(!batch.postpone(true, None, AnimationKind::Open));
+        assert_eq!(batch.end(), None);
+    }
+}
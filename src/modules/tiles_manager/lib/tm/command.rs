@@ -1,12 +1,16 @@
+use super::configs::TilesManagerConfig;
 use crate::app::{
-    mondrian_message::{MondrianMessage, SystemEvent, WindowEvent},
+    mondrian_message::{MondrianMessage, MoveSizeResult, SystemEvent, WindowEvent},
     structs::direction::Direction,
 };
+use crate::modules::tiles_manager::lib::window_animation_player::AnimationKind;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum TMCommand {
     WindowEvent(WindowEvent),
     SystemEvent(SystemEvent),
+    ConfigChanged(Box<TilesManagerConfig>),
     Focus(Direction),
     FocusMonitor(Direction),
     Close,
@@ -49,10 +53,12 @@ impl TMCommand {
                 | WindowEvent::Maximized(..)
                 | WindowEvent::Unmaximized(..)
                 | WindowEvent::Repositioned(..)
+                | WindowEvent::TitleChanged(..)
                 | WindowEvent::EndMoveSize(..) => true,
                 WindowEvent::Focused(..) | WindowEvent::StartMoveSize(..) => false,
             },
             TMCommand::SystemEvent(..)
+            | TMCommand::ConfigChanged(..)
             | TMCommand::Close
             | TMCommand::Insert(..)
             | TMCommand::Move(..)
@@ -79,6 +85,108 @@ impl TMCommand {
     }
 }
 
+impl TMCommand {
+    /// Returns the kind of change the command makes to the layout, used to pick its animation settings
+    pub fn animation_kind(&self) -> AnimationKind {
+        match self {
+            TMCommand::WindowEvent(WindowEvent::Opened(..) | WindowEvent::Restored(..)) => AnimationKind::Open,
+            TMCommand::WindowEvent(WindowEvent::Closed(..) | WindowEvent::Minimized(..))
+            | TMCommand::Close
+            | TMCommand::Minimize => AnimationKind::Close,
+            TMCommand::WindowEvent(WindowEvent::EndMoveSize(_, MoveSizeResult::Moved(..)))
+            | TMCommand::Insert(..)
+            | TMCommand::Move(..)
+            | TMCommand::Invert
+            | TMCommand::Amplify => AnimationKind::Swap,
+            TMCommand::WindowEvent(WindowEvent::EndMoveSize(_, MoveSizeResult::Resized(..)))
+            | TMCommand::Resize(..)
+            | TMCommand::Peek(..) => AnimationKind::Resize,
+            TMCommand::FocusWorkspace { .. } | TMCommand::MoveToWorkspace { .. } => AnimationKind::WorkspaceSwitch,
+            TMCommand::Focalize | TMCommand::HalfFocalize | TMCommand::CycleFocalized(..) => AnimationKind::Focalize,
+            _ => AnimationKind::Default,
+        }
+    }
+
+    /// Returns true if the command is a window event that can be coalesced with the other events
+    /// of the same window
+    pub fn is_coalescable(&self) -> bool {
+        matches!(
+            self,
+            TMCommand::WindowEvent(
+                WindowEvent::Opened(..)
+                    | WindowEvent::Closed(..)
+                    | WindowEvent::Minimized(..)
+                    | WindowEvent::Restored(..)
+                    | WindowEvent::Focused(..)
+                    | WindowEvent::Repositioned(..)
+            )
+        )
+    }
+}
+
+/// Coalesces a burst of commands. For each window, only the last position event, the last focus
+/// event and the last minimize/restore event are kept. The windows opened and then closed within
+/// the burst are dropped, along with their events in between, while the windows minimized and then
+/// restored only lose that pair of events.
+pub fn coalesce_commands(commands: Vec<TMCommand>) -> Vec<TMCommand> {
+    let events: Vec<Option<WindowEvent>> = commands
+        .iter()
+        .map(|c| match c {
+            TMCommand::WindowEvent(e) if c.is_coalescable() => Some(*e),
+            _ => None,
+        })
+        .collect();
+    let mut keep = vec![true; commands.len()];
+
+    let mut opened_at = HashMap::new();
+    let mut minimized_at = HashMap::new();
+    for (i, event) in events.iter().enumerate() {
+        match event {
+            Some(WindowEvent::Opened(w)) => {
+                opened_at.insert(*w, i);
+            }
+            Some(WindowEvent::Minimized(w)) => {
+                minimized_at.insert(*w, i);
+            }
+            Some(WindowEvent::Restored(w)) => {
+                // INFO: the window ends up as it was before the burst, so the pair is dropped together
+                if let Some(start) = minimized_at.remove(w) {
+                    keep[start] = false;
+                    keep[i] = false;
+                }
+            }
+            Some(WindowEvent::Closed(w)) => {
+                if let Some(start) = opened_at.remove(w) {
+                    (start..=i)
+                        .filter(|j| events[*j].is_some_and(|e| e.get_window_ref() == *w))
+                        .for_each(|j| keep[j] = false);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // INFO: the events are visited from the last one, so that only the last of each kind is kept
+    let mut seen = HashSet::new();
+    for (i, event) in events.iter().enumerate().rev() {
+        let kind = match event {
+            _ if !keep[i] => continue,
+            Some(WindowEvent::Repositioned(w)) => (*w, 0),
+            Some(WindowEvent::Focused(w)) => (*w, 1),
+            Some(WindowEvent::Minimized(w) | WindowEvent::Restored(w)) => (*w, 2),
+            _ => continue,
+        };
+        keep[i] = seen.insert(kind);
+    }
+
+    commands
+        .into_iter()
+        .zip(keep)
+        .filter(|(_, k)| *k)
+        .map(|(c, _)| c)
+        .collect()
+}
+
 impl From<WindowEvent> for TMCommand {
     fn from(event: WindowEvent) -> Self {
         TMCommand::WindowEvent(event)
@@ -123,6 +231,8 @@ impl TryFrom<&MondrianMessage> for TMCommand {
             MondrianMessage::Close => Ok(TMCommand::Close),
             MondrianMessage::Topmost => Ok(TMCommand::Topmost),
             MondrianMessage::RefreshConfig
+            | MondrianMessage::ConfigChanged(_)
+            | MondrianMessage::SetProfile(_)
             | MondrianMessage::OpenConfig
             | MondrianMessage::Retile
             | MondrianMessage::Configure
@@ -133,6 +243,7 @@ impl TryFrom<&MondrianMessage> for TMCommand {
             | MondrianMessage::CoreUpdateError
             | MondrianMessage::CoreUpdateComplete
             | MondrianMessage::QueryInfoResponse { .. }
+            | MondrianMessage::ExplainWindow(_)
             | MondrianMessage::OpenLogFolder
             | MondrianMessage::About
             | MondrianMessage::HealthCheckPing
@@ -141,3 +252,98 @@ impl TryFrom<&MondrianMessage> for TMCommand {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::coalesce_commands;
+    use super::TMCommand;
+    use crate::app::mondrian_message::WindowEvent;
+    use crate::win32::window::window_ref::WindowRef;
+
+    fn ev(event: fn(WindowRef) -> WindowEvent, win: isize) -> TMCommand {
+        TMCommand::WindowEvent(event(WindowRef::from(win)))
+    }
+
+    #[test]
+    fn test_coalesce_drops_open_close_pairs() {
+        let commands = vec![
+            ev(WindowEvent::Opened, 1),
+            ev(WindowEvent::Repositioned, 2),
+            ev(WindowEvent::Focused, 1),
+            ev(WindowEvent::Repositioned, 1),
+            TMCommand::Update(false),
+            ev(WindowEvent::Closed, 1),
+        ];
+
+        let expected = vec![ev(WindowEvent::Repositioned, 2), TMCommand::Update(false)];
+        assert_eq!(coalesce_commands(commands), expected);
+    }
+
+    #[test]
+    fn test_coalesce_keeps_last_of_kind_per_window() {
+        let commands = vec![
+            ev(WindowEvent::Repositioned, 1),
+            ev(WindowEvent::Focused, 1),
+            ev(WindowEvent::Repositioned, 2),
+            ev(WindowEvent::Repositioned, 1),
+            ev(WindowEvent::Focused, 2),
+            ev(WindowEvent::Focused, 1),
+        ];
+
+        let expected = vec![
+            ev(WindowEvent::Repositioned, 2),
+            ev(WindowEvent::Repositioned, 1),
+            ev(WindowEvent::Focused, 2),
+            ev(WindowEvent::Focused, 1),
+        ];
+        assert_eq!(coalesce_commands(commands), expected);
+    }
+
+    #[test]
+    fn test_coalesce_keeps_minimize_restore_order() {
+        let commands = vec![
+            ev(WindowEvent::Minimized, 1),
+            ev(WindowEvent::Restored, 2),
+            ev(WindowEvent::Minimized, 2),
+        ];
+
+        let expected = vec![ev(WindowEvent::Minimized, 1), ev(WindowEvent::Minimized, 2)];
+        assert_eq!(coalesce_commands(commands), expected);
+    }
+
+    #[test]
+    fn test_coalesce_minimize_then_restore() {
+        let commands = vec![
+            ev(WindowEvent::Minimized, 1),
+            ev(WindowEvent::Repositioned, 1),
+            ev(WindowEvent::Restored, 1),
+        ];
+
+        let expected = vec![ev(WindowEvent::Repositioned, 1)];
+        assert_eq!(coalesce_commands(commands), expected);
+    }
+
+    #[test]
+    fn test_coalesce_minimize_restore_minimize() {
+        let commands = vec![
+            ev(WindowEvent::Minimized, 1),
+            ev(WindowEvent::Restored, 1),
+            ev(WindowEvent::Focused, 2),
+            ev(WindowEvent::Minimized, 1),
+        ];
+
+        let expected = vec![ev(WindowEvent::Focused, 2), ev(WindowEvent::Minimized, 1)];
+        assert_eq!(coalesce_commands(commands), expected);
+    }
+
+    #[test]
+    fn test_coalesce_keeps_unmatched_close() {
+        let commands = vec![
+            ev(WindowEvent::Repositioned, 1),
+            ev(WindowEvent::Closed, 1),
+            ev(WindowEvent::Opened, 1),
+        ];
+
+        assert_eq!(coalesce_commands(commands.clone()), commands);
+    }
+}
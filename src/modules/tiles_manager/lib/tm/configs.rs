@@ -6,8 +6,11 @@ use crate::app::configs::AppConfig;
 use crate::app::configs::LayoutConfig;
 use crate::app::configs::MonitorConfig;
 use crate::app::configs::WorkspaceConfig;
+use crate::app::structs::monitor_selector::resolve_monitor;
 use crate::app::structs::paddings::Paddings;
+use crate::app::structs::reserved_area::ReservedArea;
 use crate::app::structs::win_matcher::WinMatcher;
+use crate::win32::api::monitor::Monitor;
 use std::collections::HashMap;
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -17,10 +20,17 @@ pub struct TilesManagerConfig {
     focalized_padding: Paddings,
     half_focalized_borders_pad: Paddings,
     half_focalized_tiles_pad: i16,
+    scale_with_dpi: bool,
     layout_strategy: LayoutStrategyEnum,
     monitors_configs: HashMap<String, MonitorConfig>,
     workspaces_configs: HashMap<String, WorkspaceConfig>,
+    pub monitor_aliases: HashMap<String, String>,
+    /// Monitor names used in the settings, resolved to the ids of the connected monitors
+    resolved_monitors: HashMap<String, String>,
+    /// Scale factors of the connected monitors, by monitor id
+    monitors_scale: HashMap<String, f32>,
     default_workspace: String,
+    pub fallback_monitor: String,
     pub focus_on_empty_monitor: bool,
     pub focus_follows_cursor: bool,
     pub ignore_filter: WinMatcher,
@@ -41,38 +51,43 @@ impl TilesManagerConfig {
     }
 
     pub fn get_focalized_padding(&self, monitor_name: &str, workspace: &str) -> Paddings {
-        self.extract_config(
+        let pad = self.extract_config(
             monitor_name,
             workspace,
             |c| c.focalized_padding,
             &self.focalized_padding,
-        )
+        );
+        pad.scaled(self.get_scale_factor(monitor_name, workspace))
     }
 
     pub fn get_borders_padding(&self, monitor_name: &str, workspace: &str) -> Paddings {
-        self.extract_config(monitor_name, workspace, |c| c.borders_padding, &self.borders_padding)
+        let pad = self.extract_config(monitor_name, workspace, |c| c.borders_padding, &self.borders_padding);
+        pad.scaled(self.get_scale_factor(monitor_name, workspace))
     }
 
     pub fn get_tiles_padding(&self, monitor_name: &str, workspace: &str) -> i16 {
-        self.extract_config(monitor_name, workspace, |c| c.tiles_padding as i16, &self.tiles_padding)
+        let pad = self.extract_config(monitor_name, workspace, |c| c.tiles_padding as i16, &self.tiles_padding);
+        scale_padding(pad, self.get_scale_factor(monitor_name, workspace))
     }
 
     pub fn get_half_focalized_borders_pad(&self, monitor_name: &str, workspace: &str) -> Paddings {
-        self.extract_config(
+        let pad = self.extract_config(
             monitor_name,
             workspace,
             |c| c.half_focalized_borders_pad,
             &self.half_focalized_borders_pad,
-        )
+        );
+        pad.scaled(self.get_scale_factor(monitor_name, workspace))
     }
 
     pub fn get_half_focalized_tiles_pad(&self, monitor_name: &str, workspace: &str) -> i16 {
-        self.extract_config(
+        let pad = self.extract_config(
             monitor_name,
             workspace,
             |c| c.half_focalized_tiles_pad as i16,
             &self.half_focalized_tiles_pad,
-        )
+        );
+        scale_padding(pad, self.get_scale_factor(monitor_name, workspace))
     }
 
     pub fn get_half_focalized_tiles_pad_xy(&self, monitor_name: &str, workspace: &str) -> (i16, i16) {
@@ -85,19 +100,71 @@ impl TilesManagerConfig {
         (tiles_padding, tiles_padding)
     }
 
+    /// Returns the id of the monitor the workspace is bound to, `None` if the workspace is not bound
+    /// or if the monitor is not connected
     pub fn get_bounded_monitor(&self, workspace: &str) -> Option<String> {
-        self.workspaces_configs
-            .get(workspace)
-            .and_then(|c| c.bind_to_monitor.clone())
+        let monitor = self.workspaces_configs.get(workspace)?.bind_to_monitor.as_ref()?;
+        let resolved = self.resolved_monitors.get(monitor).cloned();
+        if resolved.is_none() {
+            log::warn!("Monitor '{monitor}' of workspace '{workspace}' not found, the workspace is not bound");
+        }
+        resolved
     }
 
     pub fn get_default_workspace(&self, monitor_name: &str) -> String {
-        self.monitors_configs
-            .get(monitor_name)
+        self.get_monitor_entry(&self.monitors_configs, monitor_name)
             .map(|c| c.default_workspace.clone())
             .unwrap_or(self.default_workspace.clone())
     }
 
+    pub fn get_reserved_area(&self, monitor_name: &str) -> ReservedArea {
+        self.get_monitor_entry(&self.monitors_configs, monitor_name)
+            .map(|c| c.reserved)
+            .unwrap_or_default()
+    }
+
+    /// Returns the given padding scaled like the other paddings of the monitor and workspace
+    pub fn scale_padding(&self, monitor_name: &str, workspace: &str, padding: u8) -> i16 {
+        scale_padding(padding.into(), self.get_scale_factor(monitor_name, workspace))
+    }
+
+    /// Resolves the monitor names used in the settings (aliases or positional selectors) against
+    /// the connected monitors
+    pub fn resolve_monitors(&mut self, monitors: &[Monitor]) {
+        let workspaces = self.workspaces_configs.values();
+        let names = self
+            .monitors_configs
+            .keys()
+            .chain(workspaces.flat_map(|w| w.monitors.keys().chain(w.bind_to_monitor.iter())));
+
+        self.resolved_monitors = names
+            .filter_map(|n| resolve_monitor(n, &self.monitor_aliases, monitors).map(|id| (n.clone(), id)))
+            .collect();
+        self.monitors_scale = monitors.iter().map(|m| (m.id.clone(), m.get_scale_factor())).collect();
+    }
+
+    /// Returns the factor the paddings of the monitor are scaled by (i.e. 1.0 if `scale_with_dpi`
+    /// is disabled)
+    fn get_scale_factor(&self, monitor_name: &str, workspace: &str) -> f32 {
+        match self.extract_config(monitor_name, workspace, |c| c.scale_with_dpi, &self.scale_with_dpi) {
+            true => self.monitors_scale.get(monitor_name).copied().unwrap_or(1.0),
+            false => 1.0,
+        }
+    }
+
+    /// Returns the entry of the monitor with the given id. The entries with the monitor id take
+    /// precedence over the ones with an alias or a positional selector.
+    fn get_monitor_entry<'a, V>(&self, entries: &'a HashMap<String, V>, monitor_id: &str) -> Option<&'a V> {
+        entries.get(monitor_id).or_else(|| {
+            let mut names: Vec<&String> = entries
+                .keys()
+                .filter(|n| self.resolved_monitors.get(*n).is_some_and(|id| id == monitor_id))
+                .collect();
+            names.sort();
+            names.first().and_then(|n| entries.get(*n))
+        })
+    }
+
     fn extract_config<V: Clone>(
         &self,
         monitor_name: &str,
@@ -107,10 +174,13 @@ impl TilesManagerConfig {
     ) -> V {
         self.workspaces_configs
             .get(workspace)
-            .and_then(|w| w.monitors.get(monitor_name).or(Some(&w.layout)).map(&config_extractor))
+            .and_then(|w| {
+                self.get_monitor_entry(&w.monitors, monitor_name)
+                    .or(Some(&w.layout))
+                    .map(&config_extractor)
+            })
             .unwrap_or(
-                self.monitors_configs
-                    .get(monitor_name)
+                self.get_monitor_entry(&self.monitors_configs, monitor_name)
                     .map(|c| config_extractor(&c.layout))
                     .unwrap_or(default.clone()),
             )
@@ -125,10 +195,15 @@ impl From<&AppConfig> for TilesManagerConfig {
             focalized_padding: config.focalized_pads,
             half_focalized_borders_pad: config.half_focalized_borders_pads,
             half_focalized_tiles_pad: config.half_focalized_tiles_pad as i16,
+            scale_with_dpi: config.scale_with_dpi,
             layout_strategy: config.layout_strategy.clone(),
             monitors_configs: config.monitors_config.clone(),
             workspaces_configs: config.workspaces_config.clone(),
+            monitor_aliases: config.monitor_aliases.clone(),
+            resolved_monitors: HashMap::new(),
+            monitors_scale: HashMap::new(),
             default_workspace: config.default_workspace.clone(),
+            fallback_monitor: config.fallback_monitor.clone(),
             focus_on_empty_monitor: config.allow_focus_on_empty_monitor,
             focus_follows_cursor: config.move_cursor_on_focus,
             ignore_filter: config.ignore_filter.clone(),
@@ -139,3 +214,7 @@ impl From<&AppConfig> for TilesManagerConfig {
         }
     }
 }
+
+fn scale_padding(padding: i16, factor: f32) -> i16 {
+    (padding as f32 * factor).round() as i16
+}
@@ -4,6 +4,9 @@ use super::TilesManager;
 use crate::app::mondrian_message::WindowTileState;
 use crate::app::structs::direction::Direction;
 use crate::app::structs::orientation::Orientation;
+use crate::modules::tiles_manager::lib::containers::keys::ContainerKey;
+use crate::modules::tiles_manager::lib::containers::map::ActiveContainersMap;
+use crate::modules::tiles_manager::lib::containers::Containers;
 use crate::modules::tiles_manager::lib::structs::virtual_desktop::VirtualDesktop;
 use crate::modules::tiles_manager::lib::utils::find_nearest_candidate;
 use crate::win32::api::cursor::set_cursor_pos;
@@ -12,6 +15,8 @@ use crate::win32::window::window_obj::WindowObjInfo;
 use crate::win32::window::window_ref::WindowRef;
 use std::collections::HashMap;
 use utils::*;
+use windows::Win32::UI::WindowsAndMessaging::SWP_NOACTIVATE;
+use windows::Win32::UI::WindowsAndMessaging::SWP_NOZORDER;
 use winvd::is_window_on_desktop;
 
 type Success = TilesManagerSuccess;
@@ -26,6 +31,16 @@ pub trait TilesManagerFloating {
     fn insert(&mut self, window: WindowRef, direction: Direction, center: bool) -> TMResult;
     fn resize(&mut self, window: WindowRef, axis: Orientation, increment: i16) -> TMResult;
     fn change_focus(&mut self, window: WindowRef, direction: Direction) -> TMResult;
+
+    /// Moves the window to the given workspace. If `silent` is true and the workspace is not active,
+    /// the window is hidden until the workspace is focused.
+    fn move_to_workspace(
+        &mut self,
+        window: WindowRef,
+        workspace: &str,
+        monitor: Option<&str>,
+        silent: bool,
+    ) -> TMResult;
 }
 
 impl TilesManagerFloating for TilesManager {
@@ -77,6 +92,10 @@ impl TilesManagerFloating for TilesManager {
         let monitor_area = monitor.get_workspace();
         let area = fit_area(&area, &monitor_area);
 
+        if let Ok(e) = self.containers.find_near(monitor_area.get_center()) {
+            self.floating_wins.set_workspace(&window, e.key.into());
+        }
+
         if center {
             let area = area.with_center(monitor_area.get_center());
             return Ok(Success::queue(window, area, None));
@@ -144,19 +163,80 @@ impl TilesManagerFloating for TilesManager {
 
         Ok(Success::NoChange)
     }
+
+    fn move_to_workspace(
+        &mut self,
+        window: WindowRef,
+        workspace: &str,
+        monitor: Option<&str>,
+        silent: bool,
+    ) -> TMResult {
+        if !matches!(self.get_window_state(window)?, WindowTileState::Floating) {
+            return Ok(Success::NoChange);
+        }
+
+        // INFO: global windows don't belong to any workspace
+        let Some(src_k) = self.floating_wins.get_workspace(&window) else {
+            return Ok(Success::NoChange);
+        };
+        let trg_monitor = match monitor {
+            Some(m) => self.resolve_monitor(m).ok_or(Error::MonitorNotFound(m.to_string()))?,
+            None => src_k.monitor.clone(),
+        };
+        if src_k.workspace == workspace && src_k.monitor == trg_monitor {
+            return Ok(Success::NoChange);
+        }
+
+        let trg_k = ContainerKey::new(self.current_vd.get_id(), &trg_monitor, workspace);
+        self.floating_wins.set_workspace(&window, trg_k.clone());
+
+        let area = window.get_area().ok_or(Error::NoWindowsInfo)?;
+        let area = match self.managed_monitors.get(&trg_monitor) {
+            Some(m) if src_k.monitor != trg_monitor => {
+                let monitor_area = m.info.get_workspace();
+                fit_area(&area, &monitor_area).with_center(monitor_area.get_center())
+            }
+            _ => area,
+        };
+
+        let is_active = self
+            .containers
+            .get_key_by_monitor(&trg_monitor)
+            .is_ok_and(|k| k.workspace == workspace);
+        if !silent || is_active {
+            return Ok(Success::queue(window, area, None));
+        }
+
+        // INFO: the window is moved without animation, since it is hidden right after
+        self.animation_player.dequeue(window);
+        let flags = SWP_NOACTIVATE | SWP_NOZORDER;
+        window
+            .resize_and_move(area.get_origin(), area.get_size(), false, flags)
+            .ok();
+        self.floating_wins.hide_workspace(&trg_k).iter().for_each(|w| {
+            w.minimize(true);
+        });
+        Ok(Success::LayoutChanged)
+    }
 }
 
 #[derive(Debug)]
 pub struct FloatingProperties {
     pub minimized: bool,
     pub locked: bool,
+    /// Workspace the window belongs to, `None` if it is visible on all the workspaces
+    pub workspace: Option<ContainerKey>,
+    /// True if the window is minimized because its workspace is not active
+    pub hidden: bool,
 }
 
 impl FloatingProperties {
-    pub fn new() -> Self {
+    pub fn new(workspace: Option<ContainerKey>) -> Self {
         FloatingProperties {
             minimized: false,
             locked: false,
+            workspace,
+            hidden: false,
         }
     }
 }
@@ -169,12 +249,21 @@ pub trait FloatingWindows {
     fn set_locked(&mut self, window: &WindowRef, locked: bool);
     fn set_minimized(&mut self, window: &WindowRef, minimized: bool);
     fn can_be_closed(&self, window: &WindowRef) -> bool;
+    fn get_workspace(&self, window: &WindowRef) -> Option<ContainerKey>;
+    fn set_workspace(&mut self, window: &WindowRef, workspace: ContainerKey);
+    /// Marks the visible windows of the workspace as hidden and returns them
+    fn hide_workspace(&mut self, workspace: &ContainerKey) -> Vec<WindowRef>;
+    /// Marks the hidden windows of the workspace as visible again and returns them
+    fn show_workspace(&mut self, workspace: &ContainerKey) -> Vec<WindowRef>;
+    /// Moves the windows to the workspaces returned by `rekey`, the ones for which it returns `None` are left as they are
+    fn rekey_workspaces(&mut self, rekey: impl FnMut(&WindowRef, &FloatingProperties) -> Option<ContainerKey>);
 }
 
 impl FloatingWindows for HashMap<WindowRef, FloatingProperties> {
     fn enabled_keys(&self, vd: &VirtualDesktop) -> impl Iterator<Item = WindowRef> {
         self.iter()
-            .filter(|(w, props)| !props.minimized && is_window_on_desktop(vd.get_desktop(), w.hwnd).unwrap_or(true))
+            .filter(|(_, props)| !props.minimized && !props.hidden)
+            .filter(|(w, _)| is_window_on_desktop(vd.get_desktop(), w.hwnd).unwrap_or(true))
             .map(|(key, _)| *key)
     }
 
@@ -200,6 +289,8 @@ impl FloatingWindows for HashMap<WindowRef, FloatingProperties> {
         if let Some(props) = self.get_mut(window) {
             props.minimized = minimized;
             props.locked = locked;
+            // INFO: a hidden window restored by the user is visible again, even if its workspace is not active
+            props.hidden &= minimized;
         }
     }
 
@@ -212,6 +303,44 @@ impl FloatingWindows for HashMap<WindowRef, FloatingProperties> {
     fn locked(&self, window: &WindowRef) -> Option<bool> {
         self.get(window).map(|props| props.locked)
     }
+
+    fn get_workspace(&self, window: &WindowRef) -> Option<ContainerKey> {
+        self.get(window).and_then(|props| props.workspace.clone())
+    }
+
+    fn set_workspace(&mut self, window: &WindowRef, workspace: ContainerKey) {
+        if let Some(props) = self.get_mut(window).filter(|props| props.workspace.is_some()) {
+            props.workspace = Some(workspace);
+        }
+    }
+
+    fn hide_workspace(&mut self, workspace: &ContainerKey) -> Vec<WindowRef> {
+        self.iter_mut()
+            .filter(|(_, props)| !props.minimized && !props.hidden && props.workspace.as_ref() == Some(workspace))
+            .map(|(w, props)| {
+                props.hidden = true;
+                *w
+            })
+            .collect()
+    }
+
+    fn show_workspace(&mut self, workspace: &ContainerKey) -> Vec<WindowRef> {
+        self.iter_mut()
+            .filter(|(_, props)| props.hidden && props.workspace.as_ref() == Some(workspace))
+            .map(|(w, props)| {
+                props.hidden = false;
+                *w
+            })
+            .collect()
+    }
+
+    fn rekey_workspaces(&mut self, mut rekey: impl FnMut(&WindowRef, &FloatingProperties) -> Option<ContainerKey>) {
+        for (w, props) in self.iter_mut().filter(|(_, props)| props.workspace.is_some()) {
+            if let Some(workspace) = rekey(w, props) {
+                props.workspace = Some(workspace);
+            }
+        }
+    }
 }
 
 mod utils {
@@ -283,3 +412,78 @@ mod utils {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FloatingProperties;
+    use super::FloatingWindows;
+    use crate::modules::tiles_manager::lib::containers::keys::ContainerKey;
+    use crate::win32::window::window_ref::WindowRef;
+    use std::collections::HashMap;
+
+    fn key(monitor: &str, workspace: &str) -> ContainerKey {
+        ContainerKey::new(1, monitor, workspace)
+    }
+
+    fn floating_wins(wins: &[(isize, Option<ContainerKey>)]) -> HashMap<WindowRef, FloatingProperties> {
+        wins.iter()
+            .map(|(w, k)| (WindowRef::from(*w), FloatingProperties::new(k.clone())))
+            .collect()
+    }
+
+    #[test]
+    fn test_hide_show_workspace() {
+        let mut wins = floating_wins(&[
+            (1, Some(key("M1", "1"))),
+            (2, Some(key("M1", "1"))),
+            (3, Some(key("M1", "2"))),
+            (4, Some(key("M2", "1"))),
+            (5, None),
+        ]);
+        wins.set_minimized(&WindowRef::from(2isize), true);
+
+        assert_eq!(wins.hide_workspace(&key("M1", "1")), vec![WindowRef::from(1isize)]);
+        assert!(wins.hide_workspace(&key("M1", "1")).is_empty());
+        assert!(wins[&WindowRef::from(1isize)].hidden);
+        assert!(!wins[&WindowRef::from(4isize)].hidden && !wins[&WindowRef::from(5isize)].hidden);
+
+        assert!(wins.show_workspace(&key("M1", "2")).is_empty());
+        assert_eq!(wins.show_workspace(&key("M1", "1")), vec![WindowRef::from(1isize)]);
+        assert!(wins.values().all(|p| !p.hidden));
+    }
+
+    #[test]
+    fn test_restore_hidden() {
+        let (w1, w2) = (WindowRef::from(1isize), WindowRef::from(2isize));
+        let mut wins = floating_wins(&[(1, Some(key("M1", "1"))), (2, Some(key("M1", "1")))]);
+        wins.hide_workspace(&key("M1", "1"));
+
+        // INFO: the minimize event of a hidden window doesn't make it visible
+        wins.set_properties(&w1, true, false);
+        wins.set_properties(&w2, false, false);
+        assert!(wins[&w1].hidden && !wins[&w2].hidden);
+        assert_eq!(wins.show_workspace(&key("M1", "1")), vec![w1]);
+    }
+
+    #[test]
+    fn test_rekey_workspaces() {
+        let mut wins = floating_wins(&[
+            (1, Some(key("M1", "1"))),
+            (2, Some(key("M1", "2"))),
+            (3, Some(key("M2", "1"))),
+            (4, None),
+        ]);
+        wins.hide_workspace(&key("M1", "2"));
+
+        wins.rekey_workspaces(|_, props| {
+            let k = props.workspace.as_ref().filter(|k| k.monitor == "M1")?;
+            Some(key("M3", &k.workspace))
+        });
+
+        assert_eq!(wins.get_workspace(&WindowRef::from(1isize)), Some(key("M3", "1")));
+        assert_eq!(wins.get_workspace(&WindowRef::from(3isize)), Some(key("M2", "1")));
+        assert_eq!(wins.get_workspace(&WindowRef::from(4isize)), None);
+        assert!(wins.show_workspace(&key("M1", "2")).is_empty());
+        assert_eq!(wins.show_workspace(&key("M3", "2")), vec![WindowRef::from(2isize)]);
+    }
+}
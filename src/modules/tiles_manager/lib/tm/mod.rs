@@ -1,6 +1,8 @@
+pub mod batch;
 pub mod command;
 pub mod configs;
 pub mod floating;
+pub mod monitors_remap;
 pub mod operations;
 pub mod public;
 pub mod result;
@@ -11,31 +13,44 @@ use super::containers::keys::ContainerKeyTrait;
 use super::containers::map::ContainersMap;
 use super::structs::focus_history::FocusHistory;
 use super::structs::managed_monitor::ManagedMonitor;
+use super::structs::rules::AddOptions;
+use super::structs::rules::WindowOptions;
 use super::structs::virtual_desktop::VirtualDesktop;
 use crate::app::area_tree::leaf::AreaLeaf;
 use crate::app::area_tree::tree::WinTree;
+use crate::app::configs::external::general::WorkspaceTransition;
 use crate::app::mondrian_message::WindowTileState;
 use crate::app::structs::area::Area;
+use crate::app::structs::monitor_selector::resolve_fallback_monitor;
+use crate::app::structs::monitor_selector::resolve_monitor;
 use crate::modules::tiles_manager::lib::containers::keys::ActiveContainerKey;
 use crate::modules::tiles_manager::lib::containers::keys::ContainerKey;
 use crate::modules::tiles_manager::lib::containers::map::ActiveContainersMap;
 use crate::modules::tiles_manager::lib::containers::Containers;
+use crate::modules::tiles_manager::lib::window_animation_player::AnimationKind;
 use crate::modules::tiles_manager::lib::window_animation_player::WindowAnimationPlayer;
+use crate::modules::tiles_manager::lib::window_animation_player::WindowTransition;
 use crate::win32::api::monitor::enum_display_monitors;
+use crate::win32::api::monitor::Monitor;
 use crate::win32::api::window::enum_user_manageable_windows;
 use crate::win32::window::window_obj::WindowObjHandler;
 use crate::win32::window::window_obj::WindowObjInfo;
 use crate::win32::window::window_ref::WindowRef;
+use batch::LayoutBatch;
 use configs::TilesManagerConfig;
 use floating::FloatingProperties;
 use floating::FloatingWindows;
+use monitors_remap::fallback_key;
+use monitors_remap::monitor_identity;
+use monitors_remap::KeyRemap;
+use monitors_remap::MonitorsRemap;
 use operations::TilesManagerOperations;
 use result::TilesManagerError;
 use result::TilesManagerSuccess;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
-use std::time::Duration;
 use winvd::get_current_desktop;
 use winvd::Desktop;
 
@@ -48,6 +63,8 @@ pub struct TilesManager {
     maximized_wins: HashSet<WindowRef>,
     peeked_containers: HashMap<ContainerKey, Area>,
     pause_updates: bool,
+    batch: LayoutBatch,
+    animation_kind: AnimationKind,
     animation_player: WindowAnimationPlayer,
     focus_history: FocusHistory,
     managed_monitors: HashMap<String, ManagedMonitor>,
@@ -55,6 +72,17 @@ pub struct TilesManager {
     last_workspaces: HashMap<(u128, String), String>,
     config: TilesManagerConfig,
     current_vd: VirtualDesktop,
+    detached_containers: HashMap<String, Vec<DetachedContainer>>,
+    windows_options: HashMap<WindowRef, WindowOptions>,
+    rules_outcomes: HashMap<WindowRef, Option<AddOptions>>,
+    pending_add_options: HashMap<WindowRef, AddOptions>,
+}
+
+/// Container of a disconnected monitor, restored when the monitor is connected again
+struct DetachedContainer {
+    key: ContainerKey,
+    container: Container,
+    active: bool,
 }
 
 impl TilesManager {
@@ -72,7 +100,6 @@ impl TilesManager {
     {
         let config = config.unwrap_or_default();
         let animation_player = WindowAnimationPlayer::new(
-            Duration::from_millis(config.animation.duration.into()),
             config.animation.framerate,
             on_update_start,
             on_update_error,
@@ -82,6 +109,8 @@ impl TilesManager {
         let current_vd = get_current_desktop().map_err(Error::VDError)?;
         let mut tm = TilesManager {
             pause_updates: false,
+            batch: LayoutBatch::default(),
+            animation_kind: AnimationKind::Default,
             floating_wins: HashMap::new(),
             maximized_wins: HashSet::new(),
             inactive_containers: HashMap::new(),
@@ -94,12 +123,15 @@ impl TilesManager {
             last_focused_monitor: None,
             last_workspaces: HashMap::new(),
             current_vd: current_vd.try_into()?,
+            detached_containers: HashMap::new(),
+            windows_options: HashMap::new(),
+            rules_outcomes: HashMap::new(),
+            pending_add_options: HashMap::new(),
         };
 
-        tm.managed_monitors = enum_display_monitors()
-            .iter()
-            .map(|m| (m.id.clone(), m.clone().into()))
-            .collect();
+        let monitors = enum_display_monitors();
+        tm.config.resolve_monitors(&monitors);
+        tm.managed_monitors = tm.create_managed_monitors(&monitors);
 
         tm.activate_vd(current_vd)?;
 
@@ -117,7 +149,8 @@ impl TilesManager {
                 .iter()
                 .any(|(_, c)| c.get_tree(ContainerLayer::Normal).has(*w))
         });
-        let mut wins: Vec<WindowRef> = wins.filter(|w| !filter.matches(*w)).collect();
+        let monitors = self.get_monitors();
+        let mut wins: Vec<WindowRef> = wins.filter(|w| !filter.matches_on(*w, &monitors)).collect();
 
         // INFO: bigger windows first
         wins.sort_by(|a, b| {
@@ -154,11 +187,23 @@ impl TilesManager {
         tiled
     }
 
+    /// Returns false if the overlay of the window is disabled by the rules
+    pub fn has_overlay(&self, window: WindowRef) -> bool {
+        self.windows_options
+            .get(&window)
+            .is_none_or(|o| o.overlay.unwrap_or(true))
+    }
+
     /// Cancel the ongoing animation
     pub fn cancel_animation(&mut self) {
         self.animation_player.cancel();
     }
 
+    /// Sets the kind of change animated by the next layout updates
+    pub fn set_animation_kind(&mut self, kind: AnimationKind) {
+        self.animation_kind = kind;
+    }
+
     /// Updates the tiles layout of all active containers.
     /// If `win_in_focus` is `Some`, focus will be moved to that window.
     pub fn update_layout(&mut self, animate: bool, win_in_focus: Option<WindowRef>) -> Result<(), Error> {
@@ -166,6 +211,10 @@ impl TilesManager {
             return Ok(());
         }
 
+        if self.batch.postpone(animate, win_in_focus, self.animation_kind) {
+            return Ok(());
+        }
+
         let anim_player = &mut self.animation_player;
         self.containers.iter_mut().for_each(|(k, c)| {
             let tile_pad = match c.current() {
@@ -173,10 +222,25 @@ impl TilesManager {
                 ContainerLayer::HalfFocalized => self.config.get_half_focalized_tiles_pad_xy(&k.monitor, &k.workspace),
                 ContainerLayer::Normal => self.config.get_tiles_padding_xy(&k.monitor, &k.workspace),
             };
+
+            // INFO: the paddings set by the rules don't apply to the focalized windows
+            let wins_pad: HashMap<WindowRef, (i16, i16)> = match c.current() {
+                ContainerLayer::Focalized => HashMap::new(),
+                _ => self
+                    .windows_options
+                    .iter()
+                    .filter_map(|(w, o)| o.padding.map(|p| (*w, p)))
+                    .map(|(w, p)| {
+                        let pad = self.config.scale_padding(&k.monitor, &k.workspace, p);
+                        (w, (pad, pad))
+                    })
+                    .collect(),
+            };
             let _ = update_from_tree(
                 c.tree_mut(),
                 (-tile_pad.0, -tile_pad.1),
                 tile_pad,
+                &wins_pad,
                 anim_player,
                 &self.maximized_wins,
             );
@@ -185,7 +249,10 @@ impl TilesManager {
         // INFO: set maximized windows to the front when animation is complete
         let maximized = self.maximized_wins.clone();
         anim_player.play(
-            self.config.animation.animation_type.filter(|_| animate),
+            self.config
+                .animation
+                .get_settings(self.animation_kind)
+                .filter(|_| animate),
             Some(Arc::new(move || {
                 if let Some(w) = win_in_focus {
                     w.focus()
@@ -200,10 +267,27 @@ impl TilesManager {
     pub fn reposition_window(&mut self, window: WindowRef) -> Result<(), Error> {
         let leaf = self.containers.find_leaf(window)?;
         self.animation_player.queue(window, leaf.viewbox, None);
-        self.animation_player.play(self.config.animation.animation_type, None);
+        let settings = self.config.animation.get_settings(AnimationKind::Open);
+        self.animation_player.play(settings, None);
         Ok(())
     }
 
+    /// Starts a batch of commands: the layout updates are postponed until [`TilesManager::end_batch`] is called
+    pub fn begin_batch(&mut self) {
+        self.batch.begin();
+    }
+
+    /// Ends the current batch, updating the layout once for all the changes made during it
+    pub fn end_batch(&mut self) -> Result<(), Error> {
+        match self.batch.end() {
+            Some(update) => {
+                self.animation_kind = update.animation_kind;
+                self.update_layout(update.animate, update.win_in_focus)
+            }
+            None => Ok(()),
+        }
+    }
+
     /// Pause the updates of the tiles manager (i.e. prevents `update_layout` from executing)
     pub fn pause_updates(&mut self, pause: bool) {
         self.pause_updates = pause;
@@ -294,7 +378,7 @@ impl TilesManager {
             .ok_or(Error::Generic)?;
         let new_tree = self.inactive_containers.remove(&new_key).ok_or(Error::Generic)?;
         let wins_leaves = new_tree.tree().leaves(None);
-        let old_tree = self.containers.replace(new_key.into(), new_tree);
+        let old_tree = self.containers.replace(new_key.clone().into(), new_tree);
         let old_tree = old_tree.ok_or(Error::container_not_found())?;
 
         // INFO: most recently focused window otherwise top left window
@@ -317,8 +401,21 @@ impl TilesManager {
                     self.last_focused_monitor = Some(monitor_name.to_string());
                 }
             }
-            old_tree.tree().leaves(None).iter().for_each(|l| {
-                l.id.minimize(false);
+            let leaving: Vec<WindowRef> = old_tree.tree().leaves(None).iter().map(|l| l.id).collect();
+            let entering: Vec<WindowRef> = wins_leaves.iter().map(|l| l.id).collect();
+            if !self.set_workspace_transition(monitor_name, &prev_k.workspace, workspace, &leaving, &entering) {
+                leaving.iter().for_each(|w| {
+                    w.minimize(false);
+                });
+            }
+
+            // INFO: the floating windows don't take part in the transition
+            let prev_ck: ContainerKey = prev_k.clone().into();
+            self.floating_wins.hide_workspace(&prev_ck).iter().for_each(|w| {
+                w.minimize(false);
+            });
+            self.floating_wins.show_workspace(&new_key).iter().for_each(|w| {
+                w.restore(false);
             });
         }
 
@@ -326,6 +423,82 @@ impl TilesManager {
         Ok(TilesManagerSuccess::UpdateAndFocus { window: win_to_focus })
     }
 
+    /// Sets the transitions of the windows leaving and entering the monitor when switching workspace.
+    /// The slide transitions fall back to the fade one when a neighbouring monitor is on the way.
+    /// Returns false if there is no transition to play.
+    fn set_workspace_transition(
+        &mut self,
+        monitor_name: &str,
+        prev_workspace: &str,
+        next_workspace: &str,
+        leaving: &[WindowRef],
+        entering: &[WindowRef],
+    ) -> bool {
+        let transition = self.config.animation.workspace_transition;
+        let animated = self
+            .config
+            .animation
+            .get_settings(AnimationKind::WorkspaceSwitch)
+            .is_some();
+        let monitor_area = self.managed_monitors.get(monitor_name).map(|m| m.info.monitor_area);
+        let Some(monitor_area) = monitor_area.filter(|_| animated) else {
+            return false;
+        };
+
+        // INFO: the following workspaces enter from the right (or the bottom), the previous ones from the left (or the top)
+        let sign = match compare_workspaces(prev_workspace, next_workspace) {
+            Ordering::Greater => -1,
+            _ => 1,
+        };
+        // INFO: the windows slide through the areas next to both sides of the monitor
+        let blocked = |dx: i32, dy: i32| {
+            [1, -1].into_iter().any(|s| {
+                let (x, y) = (monitor_area.x + s * dx, monitor_area.y + s * dy);
+                let (w, h) = (i32::from(monitor_area.width), i32::from(monitor_area.height));
+                self.managed_monitors
+                    .iter()
+                    .filter(|(name, _)| name.as_str() != monitor_name)
+                    .map(|(_, m)| m.info.monitor_area)
+                    .any(|a| a.x < x + w && x < a.get_right_edge() && a.y < y + h && y < a.get_bottom_edge())
+            })
+        };
+        let transition = match transition {
+            WorkspaceTransition::SlideHorizontal if blocked(i32::from(monitor_area.width), 0) => {
+                WorkspaceTransition::Fade
+            }
+            WorkspaceTransition::SlideVertical if blocked(0, i32::from(monitor_area.height)) => {
+                WorkspaceTransition::Fade
+            }
+            t => t,
+        };
+        let (dx, dy, leave, enter) = match transition {
+            WorkspaceTransition::None => return false,
+            WorkspaceTransition::SlideHorizontal => {
+                let dx = sign * i32::from(monitor_area.width);
+                (dx, 0, WindowTransition::SlideOut, WindowTransition::SlideIn(dx, 0))
+            }
+            WorkspaceTransition::SlideVertical => {
+                let dy = sign * i32::from(monitor_area.height);
+                (0, dy, WindowTransition::SlideOut, WindowTransition::SlideIn(0, dy))
+            }
+            WorkspaceTransition::Fade => (0, 0, WindowTransition::FadeOut, WindowTransition::FadeIn),
+        };
+
+        for w in leaving {
+            let Some(area) = w.get_area() else {
+                w.minimize(false);
+                continue;
+            };
+            let target = Area::new(area.x - dx, area.y - dy, area.width, area.height);
+            self.animation_player.queue(*w, target, None);
+            self.animation_player.set_transition(*w, leave);
+        }
+        entering
+            .iter()
+            .for_each(|w| self.animation_player.set_transition(*w, enter));
+        true
+    }
+
     fn create_inactive_vd_containers(&mut self, vd: Desktop) -> Result<(), Error> {
         let vd_id = vd.get_id().map_err(Error::VDError)?.to_u128();
         if self.inactive_containers.has_vd(vd_id) {
@@ -389,6 +562,259 @@ impl TilesManager {
         Ok(Container::new(t1, t2, t3))
     }
 
+    /// Returns the managed monitors
+    fn get_monitors(&self) -> Vec<Monitor> {
+        self.managed_monitors.values().map(|m| m.info.clone()).collect()
+    }
+
+    /// Returns the id of the managed monitor with the given name (i.e. monitor id, alias or
+    /// positional selector)
+    fn resolve_monitor(&self, monitor_name: &str) -> Option<String> {
+        resolve_monitor(monitor_name, &self.config.monitor_aliases, &self.get_monitors())
+    }
+
+    /// Moves the windows of the containers of a disconnected monitor to the fallback monitor and
+    /// keeps the containers, so that they can be restored if the monitor is connected again.
+    fn detach_containers(&mut self, identity: &str, detached: Vec<DetachedContainer>, fallback: Option<&str>) {
+        for d in detached.iter() {
+            let Some(fallback) = fallback else { break };
+            let fallback_ws = self.containers.get_key_by_monitor(fallback).ok().map(|k| k.workspace);
+            let Some(key) = fallback_key(&d.key, d.active, fallback, fallback_ws.as_deref()) else {
+                continue;
+            };
+
+            let is_active = key.vd == self.current_vd.get_id() && self.containers.has(key.vd, fallback, &key.workspace);
+            if !is_active && !self.inactive_containers.contains_key(&key) {
+                let Ok(container) = self.create_container(fallback, &key.workspace) else {
+                    continue;
+                };
+                self.inactive_containers.insert(key.clone(), container);
+            }
+
+            let target = match is_active {
+                true => self.containers.get_mut(&ActiveContainerKey::from(key)),
+                false => self.inactive_containers.get_mut(&key),
+            };
+            let Some(target) = target else { continue };
+
+            if target.current().is_focalized_or_half() {
+                target.tree_mut().clear();
+                target.set_current(ContainerLayer::Normal);
+            }
+
+            let tree = target.get_tree_mut(ContainerLayer::Normal);
+            let wins = d.container.get_tree(ContainerLayer::Normal).get_ids();
+            wins.into_iter().filter(|w| !tree.has(*w)).for_each(|w| tree.insert(w));
+        }
+
+        self.detached_containers.insert(identity.to_string(), detached);
+    }
+
+    /// Creates the containers of a newly connected monitor, restoring the ones it had before being
+    /// disconnected (if any). The windows of the restored containers are removed from the
+    /// containers they were moved to.
+    fn attach_monitor(&mut self, monitor: &Monitor) {
+        let vd_id = self.current_vd.get_id();
+        let area = self.managed_monitors.get(&monitor.id).map(|m| m.info.get_workspace());
+        let area = area.unwrap_or(monitor.get_workspace());
+
+        for mut d in self
+            .detached_containers
+            .remove(monitor_identity(monitor))
+            .unwrap_or_default()
+        {
+            d.key.monitor = monitor.id.clone();
+            let active = d.active && d.key.vd == vd_id && self.containers.get_key_by_monitor(&monitor.id).is_err();
+
+            // INFO: windows that are no longer tiled (e.g. closed) are dropped, the others are moved back
+            for w in d.container.get_tree(ContainerLayer::Normal).get_ids() {
+                let mut others = self
+                    .containers
+                    .values_mut()
+                    .chain(self.inactive_containers.values_mut());
+                match others.find(|c| c.get_tree(ContainerLayer::Normal).has(w)) {
+                    Some(c) => c.iter_mut().for_each(|(_, t)| t.remove(w)),
+                    None => {
+                        d.container.iter_mut().for_each(|(_, t)| t.remove(w));
+                        continue;
+                    }
+                }
+                if !active && w.is_visible() {
+                    w.minimize(false);
+                }
+            }
+
+            d.container.iter_mut().for_each(|(_, t)| t.set_base_area(area));
+            if active {
+                self.containers.insert(d.key.into(), d.container);
+            } else {
+                self.inactive_containers.insert(d.key, d.container);
+            }
+        }
+
+        let mut vds: HashSet<u128> = self.inactive_containers.keys().map(|k| k.vd).collect();
+        vds.insert(vd_id);
+        for vd in vds {
+            let ws = self.config.get_default_workspace(&monitor.id);
+            let key = ContainerKey::new(vd, &monitor.id, &ws);
+            let has_container = match vd == vd_id {
+                true => self.containers.get_key_by_monitor(&monitor.id).is_ok(),
+                false => self
+                    .inactive_containers
+                    .keys()
+                    .any(|k| k.vd == vd && k.monitor == monitor.id),
+            };
+
+            if !has_container {
+                let container = self
+                    .inactive_containers
+                    .remove(&key)
+                    .or_else(|| self.create_container(&monitor.id, &ws).ok());
+                match (container, vd == vd_id) {
+                    (Some(c), true) => self.containers.insert(key.clone().into(), c),
+                    (Some(c), false) => self.inactive_containers.insert(key.clone(), c),
+                    (None, _) => None,
+                };
+            }
+
+            // INFO: the workspace to activate is required when switching to a VD with multiple workspaces
+            let last_ws = (vd, monitor.id.clone());
+            if vd != vd_id && !self.last_workspaces.contains_key(&last_ws) {
+                let ws = match self.inactive_containers.contains_key(&key) {
+                    true => Some(ws),
+                    false => self
+                        .inactive_containers
+                        .keys()
+                        .find(|k| k.vd == vd && k.monitor == monitor.id)
+                        .map(|k| k.workspace.clone()),
+                };
+                if let Some(ws) = ws {
+                    self.last_workspaces.insert(last_ws, ws);
+                }
+            }
+        }
+    }
+
+    /// Updates the managed monitors. The containers of the disconnected monitors are detached and
+    /// their windows moved to the fallback monitor, while the containers of the surviving monitors
+    /// are assigned to their new ids.
+    fn update_monitors(&mut self, monitors: Vec<Monitor>) {
+        let prev_monitors = self.get_monitors();
+        let remap = MonitorsRemap::new(&prev_monitors, &monitors);
+
+        let active: Vec<(ContainerKey, Container, bool)> =
+            self.containers.drain().map(|(k, c)| (k.into(), c, true)).collect();
+        let inactive: Vec<(ContainerKey, Container, bool)> =
+            self.inactive_containers.drain().map(|(k, c)| (k, c, false)).collect();
+
+        let mut detached: HashMap<String, Vec<DetachedContainer>> = HashMap::new();
+        for (key, container, active) in active.into_iter().chain(inactive) {
+            match remap.remap(key) {
+                KeyRemap::Moved(key) => {
+                    match active {
+                        true => self.containers.insert(key.into(), container),
+                        false => self.inactive_containers.insert(key, container),
+                    };
+                }
+                KeyRemap::Detached(identity, key) => {
+                    let entry = detached.entry(identity).or_default();
+                    entry.push(DetachedContainer { key, container, active });
+                }
+                KeyRemap::Unknown(key) => log::warn!("Container of unknown monitor '{}' dropped", key.monitor),
+            }
+        }
+
+        self.last_workspaces = self
+            .last_workspaces
+            .drain()
+            .filter_map(|((vd, m), ws)| remap.new_id(&m).map(|m| ((vd, m), ws)))
+            .collect();
+
+        self.refresh_monitors_config(&monitors);
+        self.update_monitors_areas(&monitors);
+
+        let (fallback, aliases) = (&self.config.fallback_monitor, &self.config.monitor_aliases);
+        let fallback = resolve_fallback_monitor(fallback, aliases, &monitors);
+        for (identity, containers) in detached {
+            self.detach_containers(&identity, containers, fallback.as_deref());
+        }
+
+        // INFO: the floating windows follow the containers of their workspaces
+        let fallback_ws = fallback
+            .as_deref()
+            .and_then(|f| self.containers.get_key_by_monitor(f).ok())
+            .map(|k| k.workspace);
+        self.floating_wins
+            .rekey_workspaces(|_, props| match remap.remap(props.workspace.clone()?) {
+                KeyRemap::Moved(key) => Some(key),
+                KeyRemap::Detached(_, key) | KeyRemap::Unknown(key) => {
+                    fallback_key(&key, !props.hidden, fallback.as_deref()?, fallback_ws.as_deref())
+                }
+            });
+
+        for monitor in remap.added.iter() {
+            self.attach_monitor(monitor);
+        }
+        self.show_active_floating_wins();
+    }
+
+    /// Shows the hidden floating windows whose workspace is active
+    fn show_active_floating_wins(&mut self) {
+        let keys: Vec<ContainerKey> = self.containers.keys().map(|k| k.clone().into()).collect();
+        for key in keys {
+            self.floating_wins.show_workspace(&key).iter().for_each(|w| {
+                w.restore(false);
+            });
+        }
+    }
+
+    /// Creates the managed monitors, excluding the reserved areas from their workspace area
+    fn create_managed_monitors(&self, monitors: &[Monitor]) -> HashMap<String, ManagedMonitor> {
+        monitors
+            .iter()
+            .map(|m| {
+                let mut monitor = m.clone();
+                monitor.workspace_area = self.config.get_reserved_area(&m.id).apply(m.workspace_area);
+                (m.id.clone(), monitor.into())
+            })
+            .collect()
+    }
+
+    /// Updates the managed monitors and the base areas of all the layers of their containers
+    fn update_monitors_areas(&mut self, monitors: &[Monitor]) {
+        self.managed_monitors = self.create_managed_monitors(monitors);
+        for m in self.managed_monitors.values() {
+            let area = m.info.get_workspace();
+            self.containers
+                .iter_mut()
+                .filter(|(k, _)| k.monitor == m.info.id)
+                .for_each(|(_, c)| c.iter_mut().for_each(|(_, t)| t.set_base_area(area)));
+
+            self.inactive_containers
+                .iter_mut()
+                .filter(|(k, _)| k.monitor == m.info.id)
+                .for_each(|(_, c)| c.iter_mut().for_each(|(_, t)| t.set_base_area(area)));
+        }
+    }
+
+    /// Resolves the monitors of the configuration against the given ones, updating the paddings of
+    /// the containers if they changed (e.g. the DPI of a monitor changed)
+    fn refresh_monitors_config(&mut self, monitors: &[Monitor]) {
+        let prev = self.config.clone();
+        self.config.resolve_monitors(monitors);
+        if prev == self.config {
+            return;
+        }
+
+        let config = &self.config;
+        self.containers
+            .iter_mut()
+            .for_each(|(k, c)| reconfigure_container(c, config, &k.monitor, &k.workspace));
+        self.inactive_containers
+            .iter_mut()
+            .for_each(|(k, c)| reconfigure_container(c, config, &k.monitor, &k.workspace));
+    }
+
     fn restore_monitor(&mut self, key: &ActiveContainerKey) -> Result<(), Error> {
         let is_focalized_or_half = self.containers.get(key).map(|c| c.current());
         let is_focalized_or_half = is_focalized_or_half.is_some_and(|ct| ct.is_focalized_or_half());
@@ -424,10 +850,27 @@ impl TilesManager {
     }
 }
 
+/// Updates the paddings and the layout strategy of each layer of the container, the layers are
+/// rebuilt only if their strategy changed.
+fn reconfigure_container(container: &mut Container, config: &TilesManagerConfig, monitor: &str, workspace: &str) {
+    let strategy = config.get_layout_strategy(monitor, workspace);
+
+    for (layer, tree) in container.iter_mut() {
+        let paddings = match layer {
+            ContainerLayer::Normal => config.get_borders_padding(monitor, workspace),
+            ContainerLayer::Focalized => config.get_focalized_padding(monitor, workspace),
+            ContainerLayer::HalfFocalized => config.get_half_focalized_borders_pad(monitor, workspace),
+        };
+        tree.set_paddings(paddings);
+        tree.set_strategy(strategy.clone());
+    }
+}
+
 fn update_from_tree(
     tree: &mut WinTree,
     border_pad: (i16, i16),
     tile_pad: (i16, i16),
+    wins_pad: &HashMap<WindowRef, (i16, i16)>,
     animation_player: &mut WindowAnimationPlayer,
     ignored_wins: &HashSet<WindowRef>,
 ) -> Result<(), Error> {
@@ -436,9 +879,9 @@ fn update_from_tree(
     for leaf in &leaves {
         if !leaf.id.is_visible() {
             tree.remove(leaf.id);
-            return update_from_tree(tree, border_pad, tile_pad, animation_player, ignored_wins);
+            return update_from_tree(tree, border_pad, tile_pad, wins_pad, animation_player, ignored_wins);
         };
-        let area = leaf.viewbox.pad_xy(tile_pad);
+        let area = leaf.viewbox.pad_xy(wins_pad.get(&leaf.id).copied().unwrap_or(tile_pad));
         leaf.id.restore(false);
         let borders = leaf.id.get_borders().unwrap_or((0, 0, 0, 0));
         let borders = (
@@ -452,3 +895,11 @@ fn update_from_tree(
     }
     Ok(())
 }
+
+/// Compares two workspaces by their ids, numerically if both are numbers
+fn compare_workspaces(a: &str, b: &str) -> Ordering {
+    match (a.parse::<i64>(), b.parse::<i64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
@@ -0,0 +1,149 @@
+use crate::modules::tiles_manager::lib::containers::keys::ContainerKey;
+use crate::win32::api::monitor::Monitor;
+use std::collections::HashMap;
+
+/// Returns what identifies the monitor across reconnections: its hardware id or, if it is unknown,
+/// its id
+pub fn monitor_identity(monitor: &Monitor) -> &str {
+    match monitor.hw_id.is_empty() {
+        true => &monitor.id,
+        false => &monitor.hw_id,
+    }
+}
+
+/// Destination of a container after the monitors changed
+#[derive(Debug, PartialEq)]
+pub enum KeyRemap {
+    /// The monitor is still connected, possibly with a new id
+    Moved(ContainerKey),
+    /// The monitor has been disconnected, the container is detached under the monitor identity
+    Detached(String, ContainerKey),
+    /// The monitor was not managed
+    Unknown(ContainerKey),
+}
+
+/// Changes of the monitors between two enumerations. The monitor ids depend on the connected
+/// monitors, so the monitors are matched by identity (see [`monitor_identity`]).
+pub struct MonitorsRemap {
+    /// New id of each previous monitor, `None` if it has been disconnected
+    ids: HashMap<String, Option<String>>,
+    /// Identity of each previous monitor
+    identities: HashMap<String, String>,
+    /// Newly connected monitors
+    pub added: Vec<Monitor>,
+}
+
+impl MonitorsRemap {
+    pub fn new(prev: &[Monitor], current: &[Monitor]) -> MonitorsRemap {
+        let find = |p: &Monitor| current.iter().find(|m| monitor_identity(m) == monitor_identity(p));
+        MonitorsRemap {
+            ids: prev
+                .iter()
+                .map(|p| (p.id.clone(), find(p).map(|m| m.id.clone())))
+                .collect(),
+            identities: prev
+                .iter()
+                .map(|p| (p.id.clone(), monitor_identity(p).to_string()))
+                .collect(),
+            added: current
+                .iter()
+                .filter(|m| !prev.iter().any(|p| monitor_identity(p) == monitor_identity(m)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Returns the new id of a previous monitor, `None` if it has been disconnected or was unknown
+    pub fn new_id(&self, prev_id: &str) -> Option<String> {
+        self.ids.get(prev_id).cloned().flatten()
+    }
+
+    pub fn remap(&self, mut key: ContainerKey) -> KeyRemap {
+        match self.ids.get(&key.monitor) {
+            Some(Some(id)) => {
+                key.monitor = id.clone();
+                KeyRemap::Moved(key)
+            }
+            Some(None) => KeyRemap::Detached(self.identities[&key.monitor].clone(), key),
+            None => KeyRemap::Unknown(key),
+        }
+    }
+}
+
+/// Returns the key of the container of the fallback monitor receiving the windows of a detached
+/// container: the active one receives the windows of the active container (if any), while the
+/// inactive ones keep their workspace.
+pub fn fallback_key(
+    detached: &ContainerKey,
+    active: bool,
+    fallback: &str,
+    fallback_workspace: Option<&str>,
+) -> Option<ContainerKey> {
+    let workspace = match active {
+        true => fallback_workspace?,
+        false => detached.workspace.as_str(),
+    };
+    Some(ContainerKey::new(detached.vd, fallback, workspace))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fallback_key, KeyRemap, MonitorsRemap};
+    use crate::app::test_utils;
+    use crate::modules::tiles_manager::lib::containers::keys::ContainerKey;
+    use crate::win32::api::monitor::Monitor;
+
+    fn monitor(id: &str, hw_id: &str) -> Monitor {
+        Monitor {
+            hw_id: hw_id.to_string(),
+            ..test_utils::monitor(id, 0, false)
+        }
+    }
+
+    #[test]
+    fn test_remap_by_hw_id() {
+        let prev = [monitor("MONITOR1", "A"), monitor("MONITOR2", "B")];
+        let remap = MonitorsRemap::new(&prev, &[monitor("MONITOR1", "B"), monitor("MONITOR2", "C")]);
+
+        let key = |m: &str| ContainerKey::new(1, m, "1");
+        assert_eq!(remap.remap(key("MONITOR2")), KeyRemap::Moved(key("MONITOR1")));
+        assert_eq!(
+            remap.remap(key("MONITOR1")),
+            KeyRemap::Detached("A".to_string(), key("MONITOR1"))
+        );
+        assert_eq!(remap.remap(key("MONITOR3")), KeyRemap::Unknown(key("MONITOR3")));
+        assert_eq!(remap.new_id("MONITOR2"), Some("MONITOR1".to_string()));
+        assert_eq!(remap.new_id("MONITOR1"), None);
+
+        let added: Vec<&str> = remap.added.iter().map(|m| m.hw_id.as_str()).collect();
+        assert_eq!(added, ["C"]);
+    }
+
+    #[test]
+    fn test_remap_empty_hw_id() {
+        let prev = [monitor("MONITOR1", ""), monitor("MONITOR2", "")];
+        let remap = MonitorsRemap::new(&prev, &[monitor("MONITOR1", "")]);
+
+        let key = |m: &str| ContainerKey::new(1, m, "1");
+        assert_eq!(remap.remap(key("MONITOR1")), KeyRemap::Moved(key("MONITOR1")));
+        assert_eq!(
+            remap.remap(key("MONITOR2")),
+            KeyRemap::Detached("MONITOR2".to_string(), key("MONITOR2"))
+        );
+        assert!(remap.added.is_empty());
+    }
+
+    #[test]
+    fn test_fallback_key() {
+        let detached = ContainerKey::new(1, "MONITOR2", "3");
+        assert_eq!(
+            fallback_key(&detached, true, "MONITOR1", Some("1")),
+            Some(ContainerKey::new(1, "MONITOR1", "1"))
+        );
+        assert_eq!(
+            fallback_key(&detached, false, "MONITOR1", Some("1")),
+            Some(ContainerKey::new(1, "MONITOR1", "3"))
+        );
+        assert_eq!(fallback_key(&detached, true, "MONITOR1", None), None);
+    }
+}
@@ -9,11 +9,13 @@ use crate::app::mondrian_message::WindowTileState;
 use crate::app::structs::direction::Direction;
 use crate::app::structs::orientation::Orientation;
 use crate::modules::tiles_manager::lib::containers::container::ContainerLayer;
+use crate::modules::tiles_manager::lib::containers::keys::ContainerKey;
 use crate::modules::tiles_manager::lib::containers::map::ActiveContainersMap;
 use crate::modules::tiles_manager::lib::containers::map::ContainersMap;
 use crate::modules::tiles_manager::lib::containers::Containers;
 use crate::modules::tiles_manager::lib::containers::ContainersMut;
-use crate::modules::tiles_manager::lib::structs::rules::Rules;
+use crate::modules::tiles_manager::lib::structs::rules::get_managed_outcome;
+use crate::modules::tiles_manager::lib::structs::rules::AddOptions;
 use crate::modules::tiles_manager::lib::utils::get_floating_win_area;
 use crate::modules::tiles_manager::lib::utils::leaves_limited_by_edge;
 use crate::win32::window::window_obj::WindowObjHandler;
@@ -75,6 +77,20 @@ impl TilesManagerOperations for TilesManager {
         check_rules: bool,
         prevent_workspace_switch: bool,
     ) -> TMResult {
+        // INFO: if the floating window belongs to an inactive workspace, activate it
+        let vd_id = self.current_vd.get_id();
+        let is_inactive = |k: &ContainerKey| {
+            self.containers
+                .get_key_by_monitor(&k.monitor)
+                .is_ok_and(|a| a.workspace != k.workspace)
+        };
+        let floating_k = self.floating_wins.get_workspace(&win);
+        let floating_k = floating_k.filter(|k| k.vd == vd_id && !win.is_iconic() && is_inactive(k));
+        if let Some(k) = floating_k {
+            self.activate_workspace(&k.monitor, &k.workspace, false, false)?;
+            return Ok(Success::LayoutChanged);
+        }
+
         let tile_state = self.get_window_state(win);
         if tile_state
             .as_ref()
@@ -97,14 +113,39 @@ impl TilesManagerOperations for TilesManager {
             return Ok(Success::LayoutChanged);
         }
 
-        let add_opt = self.config.rules.get_add_options(win);
+        // INFO: the outcome of the rules is recorded even when they are not applied, so that the
+        // window is added again only if it changes
+        let (filter, rules) = (&self.config.ignore_filter, &self.config.rules);
+        let outcome = get_managed_outcome(filter, rules, win, &self.get_monitors());
+        self.rules_outcomes.insert(win, outcome.clone());
+
+        // INFO: the options of a window started minimized are applied once it is restored
+        let add_opt = match self.pending_add_options.remove(&win) {
+            Some(opt) => Some(opt),
+            None if check_rules => outcome,
+            None => None,
+        };
+        if let Some(opt) = add_opt.as_ref() {
+            self.windows_options.insert(win, opt.window_options);
+
+            // INFO: the window is added to the layout once it is restored
+            if opt.start_minimized {
+                win.minimize(false);
+                let opt = AddOptions {
+                    start_minimized: false,
+                    ..opt.clone()
+                };
+                self.pending_add_options.insert(win, opt);
+                return Ok(Success::NoChange);
+            }
+        }
 
         let center = prefer_position.or_else(|| win.get_area().map(|a| a.get_center()));
         let center = center.ok_or(Error::NoWindow)?;
         let center = add_opt
             .as_ref()
             .and_then(|opt| opt.monitor.clone())
-            .filter(|_| check_rules)
+            .and_then(|m| self.resolve_monitor(&m))
             .and_then(|m| {
                 self.managed_monitors
                     .get(&m)
@@ -114,13 +155,10 @@ impl TilesManagerOperations for TilesManager {
 
         let k = self.containers.find_near(center).map(|e| e.key)?;
         let prev_workspace = k.workspace.clone();
-        let trg_workspace_opt = match check_rules {
-            true => add_opt
-                .as_ref()
-                .and_then(|opt| opt.workspace_options.clone())
-                .filter(|o| o.workspace != prev_workspace),
-            false => None,
-        };
+        let trg_workspace_opt = add_opt
+            .as_ref()
+            .and_then(|opt| opt.workspace_options.clone())
+            .filter(|o| o.workspace != prev_workspace);
 
         if let Some(opt) = trg_workspace_opt.as_ref() {
             let _ = self.activate_workspace(
@@ -144,18 +182,28 @@ impl TilesManagerOperations for TilesManager {
         }
 
         let container = self.containers.get_mut(&k).ok_or(C_ERR)?;
-        win.set_topmost(false).ok();
+        let topmost = self.windows_options.get(&win).is_some_and(|o| o.topmost);
+        win.set_topmost(topmost).ok();
         container.tree_mut().insert(win);
+        if let Some(ratio) = add_opt.as_ref().and_then(|opt| opt.ratio) {
+            container.tree_mut().set_leaf_ratio(win, ratio);
+        }
 
         // INFO: if the monitor has a maximized window, restore it
         if trg_workspace_opt.is_none() {
             self.restore_maximized(&k)?;
         }
 
-        if check_rules {
-            if let Some(config) = add_opt.and_then(|opt| opt.floating_config) {
+        if let Some(opt) = add_opt.as_ref() {
+            if let Some(config) = opt.floating_config {
                 return self.release(win, Some(true), Some(config));
             }
+
+            if opt.focalize {
+                self.focalize(win, Some(true))?;
+            } else if opt.half_focalize {
+                self.half_focalize(win, Some(true))?;
+            }
         }
 
         if trg_workspace_opt.is_some_and(|opt| opt.silent || prevent_workspace_switch) {
@@ -198,9 +246,11 @@ impl TilesManagerOperations for TilesManager {
 
         let config = &config.unwrap_or(self.config.floating_wins);
         if release.unwrap_or(!matches!(tile_state, TileState::Floating)) {
-            let monitor_area = self.containers.find(window)?.value.tree().get_area();
+            let e = self.containers.find(window)?;
+            let monitor_area = e.value.tree().get_area();
+            let workspace = Some(e.key.into()).filter(|_| !config.global);
             self.remove(window)?;
-            self.floating_wins.insert(window, FloatingProperties::new());
+            self.floating_wins.insert(window, FloatingProperties::new(workspace));
             let area = get_floating_win_area(&monitor_area, &window, config)?;
             let is_topmost = config.topmost;
             let _ = window.set_topmost(is_topmost);
@@ -482,7 +532,11 @@ impl TilesManagerOperations for TilesManager {
         }
 
         let win_container_k = self.containers.find(win)?.key;
-        let trg_monitor = monitor.unwrap_or(&win_container_k.monitor);
+        let trg_monitor = match monitor {
+            Some(m) => self.resolve_monitor(m).ok_or(Error::MonitorNotFound(m.to_string()))?,
+            None => win_container_k.monitor.clone(),
+        };
+        let trg_monitor = trg_monitor.as_str();
         if win_container_k.workspace == workspace && win_container_k.monitor == trg_monitor {
             return Ok(Success::NoChange);
         }
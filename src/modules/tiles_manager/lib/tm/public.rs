@@ -1,27 +1,33 @@
+use super::configs::TilesManagerConfig;
 use super::floating::FloatingWindows;
 use super::floating::TilesManagerFloating;
 use super::operations::MonitorSearchStrategy;
 use super::operations::TilesManagerOperations;
+use super::reconfigure_container;
 use super::result::TilesManagerError;
 use super::result::TilesManagerSuccess;
 use super::TilesManager;
 use crate::app::area_tree::leaf::AreaLeaf;
+use crate::app::configs::external::general::WorkspaceTransition;
 use crate::app::mondrian_message::IntermonitorMoveOp;
 use crate::app::mondrian_message::IntramonitorMoveOp;
 use crate::app::mondrian_message::WindowTileState;
 use crate::app::structs::direction::Direction;
 use crate::app::structs::point::Point;
 use crate::modules::tiles_manager::lib::containers::container::ContainerLayer;
+use crate::modules::tiles_manager::lib::containers::keys::ContainerKey;
 use crate::modules::tiles_manager::lib::containers::keys::ContainerKeyTrait;
 use crate::modules::tiles_manager::lib::containers::map::ActiveContainersMap;
 use crate::modules::tiles_manager::lib::containers::Containers;
 use crate::modules::tiles_manager::lib::containers::ContainersMut;
+use crate::modules::tiles_manager::lib::structs::rules::get_managed_outcome;
 use crate::modules::tiles_manager::lib::utils::get_foreground;
 use crate::modules::tiles_manager::lib::utils::is_on_current_vd;
 use crate::modules::tiles_manager::lib::utils::leaves_limited_by_edge;
 use crate::win32::api::cursor::get_cursor_pos;
 use crate::win32::api::cursor::set_cursor_pos;
 use crate::win32::api::monitor::enum_display_monitors;
+use crate::win32::api::monitor::Monitor;
 use crate::win32::window::window_obj::WindowObjHandler;
 use crate::win32::window::window_obj::WindowObjInfo;
 use crate::win32::window::window_ref::WindowRef;
@@ -55,6 +61,18 @@ pub trait TilesManagerEvents {
     fn on_vd_destroyed(&mut self, destroyed: Desktop, fallback: Desktop) -> Result<(), Error>;
     fn on_vd_changed(&mut self, previous: Desktop, current: Desktop) -> Result<(), Error>;
     fn on_workarea_changed(&mut self) -> Result<(), Error>;
+
+    /// Applies the rules to the window again after its title changed, floating, moving or
+    /// removing it according to the rules it matches now.
+    fn on_title_changed(&mut self, window: WindowRef) -> Result<(), Error>;
+
+    /// Moves the containers of the disconnected monitors to the fallback monitor and restores the
+    /// ones of the reconnected monitors.
+    fn on_monitors_changed(&mut self) -> Result<(), Error>;
+
+    /// Applies the new configuration to the existing containers, preserving their windows and
+    /// focalized layers.
+    fn on_config_changed(&mut self, config: TilesManagerConfig) -> Result<(), Error>;
 }
 
 pub trait TilesManagerCommands {
@@ -113,6 +131,9 @@ impl TilesManagerEvents for TilesManager {
             self.floating_wins.set_locked(&win, false);
         }
 
+        self.windows_options.remove(&win);
+        self.rules_outcomes.remove(&win);
+        self.pending_add_options.remove(&win);
         let s = TMOperations::remove(self, win)?;
         self.success_handler(s, true, None)
     }
@@ -142,7 +163,15 @@ impl TilesManagerEvents for TilesManager {
         inter_op: InterOp,
     ) -> Result<(), Error> {
         let tile_state = self.get_window_state(win)?;
-        if matches!(tile_state, WindowTileState::Floating | WindowTileState::Maximized) {
+        if matches!(tile_state, WindowTileState::Floating) {
+            // INFO: the window now belongs to the active workspace of the monitor it was moved to
+            if let Ok(e) = self.containers.find_near(target) {
+                self.floating_wins.set_workspace(&win, e.key.into());
+            }
+            return Ok(());
+        }
+
+        if matches!(tile_state, WindowTileState::Maximized) {
             return Ok(());
         }
 
@@ -236,24 +265,87 @@ impl TilesManagerEvents for TilesManager {
             self.floating_wins.insert(*w, p);
         });
 
+        // INFO: the floating windows visible on all the VDs move to the workspaces of the new one
+        let vd_id = self.current_vd.get_id();
+        let containers = &self.containers;
+        self.floating_wins.rekey_workspaces(|w, props| {
+            let key = props.workspace.as_ref().filter(|k| k.vd != vd_id)?;
+            if !is_on_current_vd(w).unwrap_or(false) {
+                return None;
+            }
+            match props.hidden {
+                true => Some(ContainerKey::new(vd_id, &key.monitor, &key.workspace)),
+                false => containers.get_key_by_monitor(&key.monitor).ok().map(|k| k.into()),
+            }
+        });
+        self.show_active_floating_wins();
+
         self.update_layout(true, None)
     }
 
     fn on_workarea_changed(&mut self) -> Result<(), Error> {
         let monitors = enum_display_monitors();
         self.peeked_containers.clear();
-        monitors.iter().for_each(|m| {
-            self.containers
-                .iter_mut()
-                .filter(|(k, _)| k.monitor == m.id)
-                .for_each(|(_, c)| c.tree_mut().set_base_area(m.get_workspace()));
-
-            self.inactive_containers
-                .iter_mut()
-                .filter(|(k, _)| k.monitor == m.id)
-                .for_each(|(_, c)| c.tree_mut().set_base_area(m.get_workspace()));
-        });
-        self.managed_monitors = monitors.iter().map(|m| (m.id.clone(), m.clone().into())).collect();
+        self.refresh_monitors_config(&monitors);
+        self.update_monitors_areas(&monitors);
+        self.update_layout(true, None)
+    }
+
+    fn on_title_changed(&mut self, win: WindowRef) -> Result<(), Error> {
+        match self.reevaluate_rules(win)? {
+            Some(s) => self.success_handler(s, true, Some(win)),
+            None => Ok(()),
+        }
+    }
+
+    fn on_monitors_changed(&mut self) -> Result<(), Error> {
+        self.peeked_containers.clear();
+        self.last_focused_monitor = None;
+        self.update_monitors(enum_display_monitors());
+
+        // INFO: windows left without a container are managed again
+        self.add_open_windows().ok();
+        self.update_layout(true, None)
+    }
+
+    fn on_config_changed(&mut self, mut config: TilesManagerConfig) -> Result<(), Error> {
+        let monitors = self.get_monitors();
+        config.resolve_monitors(&monitors);
+        let prev = std::mem::replace(&mut self.config, config);
+        if prev.animation.framerate != self.config.animation.framerate {
+            self.animation_player.set_framerate(self.config.animation.framerate);
+        }
+
+        self.peeked_containers.clear();
+        let config = &self.config;
+        self.containers
+            .iter_mut()
+            .for_each(|(k, c)| reconfigure_container(c, config, &k.monitor, &k.workspace));
+        self.inactive_containers
+            .iter_mut()
+            .for_each(|(k, c)| reconfigure_container(c, config, &k.monitor, &k.workspace));
+
+        let reserved_changed = |m: &Monitor| prev.get_reserved_area(&m.id) != self.config.get_reserved_area(&m.id);
+        if monitors.iter().any(reserved_changed) {
+            self.update_monitors_areas(&enum_display_monitors());
+        }
+
+        if prev.ignore_filter != self.config.ignore_filter || prev.rules != self.config.rules {
+            // INFO: the rules are applied again to the managed windows, the ones that are no longer ignored are added
+            let managed: Vec<WindowRef> = self
+                .containers
+                .values()
+                .flat_map(|c| c.get_tree(ContainerLayer::Normal).get_ids())
+                .chain(self.floating_wins.enabled_keys(&self.current_vd))
+                .collect();
+            for w in managed {
+                if let Some(Success::Queue { window, area, topmost }) = self.reevaluate_rules(w)? {
+                    self.animation_player.queue(window, area, topmost);
+                }
+            }
+            self.add_open_windows()?;
+        }
+
         self.update_layout(true, None)
     }
 }
@@ -450,7 +542,7 @@ impl TilesManagerCommands for TilesManager {
         let monitor_name = if bounded.is_some() {
             bounded.clone()
         } else if let Some(monitor_name) = monitor_name {
-            Some(monitor_name.to_uppercase())
+            self.resolve_monitor(monitor_name)
         } else if self.last_focused_monitor.is_some() {
             self.last_focused_monitor.clone()
         } else if let Some(c) = get_foreground().and_then(|w| w.get_area().map(|a| a.get_center())) {
@@ -472,7 +564,8 @@ impl TilesManagerCommands for TilesManager {
                     .filter(|_| self.config.focus_follows_cursor)
                     .and_then(|w| self.containers.find_leaf(w).ok())
                     .inspect(|w| self.cursor_on_leaf(w));
-                self.update_layout(false, window)
+                let transition = self.config.animation.workspace_transition != WorkspaceTransition::None;
+                self.update_layout(transition, window)
             }
             s => self.success_handler(s, false, None),
         }
@@ -486,10 +579,21 @@ impl TilesManagerCommands for TilesManager {
     ) -> Result<(), Error> {
         let curr = get_foreground().ok_or(Error::NoWindow)?;
         let tile_state = self.get_window_state(curr)?;
-        if matches!(tile_state, WindowTileState::Floating | WindowTileState::Maximized) {
+        if matches!(tile_state, WindowTileState::Maximized) {
             return Ok(());
         }
 
+        if matches!(tile_state, WindowTileState::Floating) {
+            return match TMFloating::move_to_workspace(self, curr, workspace_id, monitor_name, !focus_workspace)? {
+                Success::NoChange => Ok(()),
+                s if focus_workspace => {
+                    self.focus_workspace(workspace_id, monitor_name)?;
+                    self.success_handler(s, true, Some(curr))
+                }
+                s => self.success_handler(s, true, None),
+            };
+        }
+
         match self.insert_window_to_workspace(curr, workspace_id, monitor_name)? {
             Success::LayoutChanged if !focus_workspace => {
                 curr.minimize(true);
@@ -689,6 +793,42 @@ impl TilesManagerCommands for TilesManager {
 }
 
 impl TilesManager {
+    /// Applies the rules to the window again and, if their outcome changed, re-adds it with the new options
+    /// Returns `None` if the window was left untouched
+    fn reevaluate_rules(&mut self, win: WindowRef) -> Result<Option<Success>, Error> {
+        // INFO: minimized and maximized windows are left untouched
+        if win.is_iconic() {
+            return Ok(None);
+        }
+
+        // INFO: the window is left untouched if the outcome of the rules did not change
+        let (filter, rules) = (&self.config.ignore_filter, &self.config.rules);
+        let outcome = get_managed_outcome(filter, rules, win, &self.get_monitors());
+        if self.rules_outcomes.get(&win) == Some(&outcome) {
+            return Ok(None);
+        }
+
+        match self.get_window_state(win) {
+            Ok(WindowTileState::Maximized) => return Ok(None),
+            Ok(WindowTileState::Floating) => {
+                self.floating_wins.remove(&win);
+                let _ = win.set_topmost(false);
+            }
+            Ok(_) => {
+                TMOperations::remove(self, win)?;
+            }
+            Err(_) => {}
+        }
+
+        self.windows_options.remove(&win);
+        if outcome.is_none() {
+            self.rules_outcomes.insert(win, None);
+            return Ok(Some(Success::LayoutChanged));
+        }
+
+        TMOperations::add(self, win, None, true, true).map(Some)
+    }
+
     fn success_handler(
         &mut self,
         success: Success,
@@ -1,39 +1,85 @@
+use super::frame_sink::FrameSink;
+use super::frame_sink::Win32FrameSink;
 use crate::app::structs::area::Area;
-use crate::win32::window::window_obj::WindowObjHandler;
-use crate::win32::window::window_obj::WindowObjInfo;
 use crate::win32::window::window_ref::WindowRef;
+use schemars::json_schema;
+use schemars::JsonSchema;
+use schemars::Schema;
+use schemars::SchemaGenerator;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::f32::consts::PI;
+use std::fmt::Display;
+use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
-use std::time::SystemTime;
-use windows::Win32::UI::WindowsAndMessaging::SET_WINDOW_POS_FLAGS;
-use windows::Win32::UI::WindowsAndMessaging::SWP_NOACTIVATE;
-use windows::Win32::UI::WindowsAndMessaging::SWP_NOSENDCHANGING;
-use windows::Win32::UI::WindowsAndMessaging::SWP_NOZORDER;
-use windows::Win32::UI::WindowsAndMessaging::SWP_SHOWWINDOW;
+
+type Callback = Arc<dyn Fn() + Send + Sync + 'static>;
+
+/// Kind of change of the layout, each one can have its own animation settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AnimationKind {
+    #[default]
+    Default,
+    Open,
+    Close,
+    Swap,
+    Resize,
+    WorkspaceSwitch,
+    Focalize,
+}
+
+/// Transition of a window entering or leaving the screen, played along with its animation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowTransition {
+    /// The window slides to its target area, starting from it shifted by the given offset
+    SlideIn(i32, i32),
+    /// The window slides to its target area, then it's minimized
+    SlideOut,
+    /// The window fades in while moving to its target area
+    FadeIn,
+    /// The window fades out while moving to its target area, then it's minimized
+    FadeOut,
+}
+
+impl WindowTransition {
+    fn is_fade(&self) -> bool {
+        matches!(self, WindowTransition::FadeIn | WindowTransition::FadeOut)
+    }
+}
+
+/// Type and duration of an animation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationSettings {
+    pub animation: WindowAnimation,
+    pub duration: Duration,
+}
 
 pub struct WindowAnimationPlayer {
     windows: HashMap<WindowRef, WindowAnimationQueueInfo>,
+    transitions: HashMap<WindowRef, WindowTransition>,
+    sink: Arc<dyn FrameSink>,
+    state: Arc<Mutex<AnimationState>>,
     running: Arc<AtomicBool>,
+    cancelled: Arc<AtomicBool>,
     animation_thread: Option<std::thread::JoinHandle<()>>,
-    animation_duration: Duration,
     framerate: u8,
     on_start: Arc<dyn Fn(HashSet<WindowRef>) + Send + Sync + 'static>,
-    on_error: Arc<dyn Fn() + Send + Sync + 'static>,
-    on_complete: Arc<dyn Fn() + Send + Sync + 'static>,
+    on_error: Callback,
+    on_complete: Callback,
 }
 
 #[derive(Clone)]
 pub struct WindowAnimationQueueInfo {
     target_area: Area,
     topmost: Option<bool>,
+    transition: Option<WindowTransition>,
 }
 
 impl WindowAnimationQueueInfo {
@@ -41,24 +87,86 @@ impl WindowAnimationQueueInfo {
         WindowAnimationQueueInfo {
             target_area: new_area,
             topmost,
+            transition: None,
         }
     }
 }
 
+/// Windows being animated, shared with the animation thread
+#[derive(Default)]
+struct AnimationState {
+    windows: HashMap<WindowRef, AnimatedWindow>,
+    cb_done: Vec<Callback>,
+}
+
+struct AnimatedWindow {
+    src_area: Area,
+    target: WindowAnimationQueueInfo,
+    started_at: Instant,
+    settings: AnimationSettings,
+}
+
+impl AnimatedWindow {
+    fn progress(&self, now: Instant) -> f32 {
+        let elapsed = now.saturating_duration_since(self.started_at).as_secs_f32();
+        (elapsed / self.settings.duration.as_secs_f32()).clamp(0.0, 1.0)
+    }
+
+    /// Returns the interpolated area of the window at the given time
+    fn area_at(&self, now: Instant) -> Area {
+        let (animation, t) = (self.settings.animation, self.progress(now));
+        let ((x1, y1), (w1, h1)) = (self.src_area.get_origin(), self.src_area.get_size());
+        let ((x2, y2), (w2, h2)) = (self.target.target_area.get_origin(), self.target.target_area.get_size());
+        Area::new(
+            animation.get_next_frame(x1 as f32, x2 as f32, t) as i32,
+            animation.get_next_frame(y1 as f32, y2 as f32, t) as i32,
+            animation.get_next_frame(w1 as f32, w2 as f32, t).max(0.0) as u16,
+            animation.get_next_frame(h1 as f32, h2 as f32, t).max(0.0) as u16,
+        )
+    }
+
+    /// Returns the opacity of the window at the given time, if it's fading
+    fn opacity_at(&self, now: Instant) -> Option<u8> {
+        let (animation, t) = (self.settings.animation, self.progress(now));
+        let alpha = match self.target.transition {
+            Some(WindowTransition::FadeIn) => animation.get_next_frame(0.0, 255.0, t),
+            Some(WindowTransition::FadeOut) => animation.get_next_frame(255.0, 0.0, t),
+            _ => return None,
+        };
+        Some(alpha.clamp(0.0, 255.0) as u8)
+    }
+
+    fn is_fading(&self) -> bool {
+        self.target.transition.is_some_and(|t| t.is_fade())
+    }
+}
+
 impl WindowAnimationPlayer {
-    pub fn new<S, E, C>(animation_duration: Duration, framerate: u8, on_start: S, on_error: E, on_complete: C) -> Self
+    pub fn new<S, E, C>(framerate: u8, on_start: S, on_error: E, on_complete: C) -> Self
+    where
+        S: Fn(HashSet<WindowRef>) + Sync + Send + 'static,
+        E: Fn() + Sync + Send + 'static,
+        C: Fn() + Sync + Send + 'static,
+    {
+        Self::with_sink(Arc::new(Win32FrameSink), framerate, on_start, on_error, on_complete)
+    }
+
+    /// Creates a player emitting its frames through the given sink
+    pub fn with_sink<S, E, C>(sink: Arc<dyn FrameSink>, framerate: u8, on_start: S, on_error: E, on_complete: C) -> Self
     where
         S: Fn(HashSet<WindowRef>) + Sync + Send + 'static,
         E: Fn() + Sync + Send + 'static,
         C: Fn() + Sync + Send + 'static,
     {
-        assert!(animation_duration.as_millis() > 0);
         assert!(framerate > 0);
         WindowAnimationPlayer {
             windows: HashMap::new(),
+            transitions: HashMap::new(),
+            sink,
+            state: Arc::new(Mutex::new(AnimationState::default())),
             running: Arc::new(AtomicBool::new(false)),
+            cancelled: Arc::new(AtomicBool::new(false)),
             animation_thread: None,
-            animation_duration,
             framerate,
             on_start: Arc::new(on_start),
             on_error: Arc::new(on_error),
@@ -66,167 +174,270 @@ impl WindowAnimationPlayer {
         }
     }
 
-    pub fn queue(&mut self, window: WindowRef, new_area: Area, topmost: Option<bool>) {
-        if self.running.load(Ordering::Acquire) {
-            self.clear();
-        }
+    pub fn set_framerate(&mut self, framerate: u8) {
+        assert!(framerate > 0);
+        self.framerate = framerate;
+    }
 
+    pub fn queue(&mut self, window: WindowRef, new_area: Area, topmost: Option<bool>) {
         self.windows
             .insert(window, WindowAnimationQueueInfo::new(new_area, topmost));
     }
 
+    /// Sets the transition played by the window along with its next animation. The windows that
+    /// are already layered can't fade, so they just slide.
+    pub fn set_transition(&mut self, window: WindowRef, transition: WindowTransition) {
+        let fading = self
+            .state
+            .lock()
+            .unwrap()
+            .windows
+            .get(&window)
+            .is_some_and(|a| a.is_fading());
+        let can_fade = fading || !self.sink.is_layered(window);
+        let transition = match transition {
+            WindowTransition::FadeIn if !can_fade => return,
+            WindowTransition::FadeOut if !can_fade => WindowTransition::SlideOut,
+            // INFO: the window is hidden before being restored, so that it doesn't flash
+            WindowTransition::FadeIn => {
+                self.sink.set_opacity(window, Some(0));
+                transition
+            }
+            _ => transition,
+        };
+        self.transitions.insert(window, transition);
+    }
+
+    /// Removes the window from the queue and stops its animation, if any
     pub(crate) fn dequeue(&mut self, window: WindowRef) {
         self.windows.remove(&window);
+        if self.transitions.remove(&window).is_some_and(|t| t.is_fade()) {
+            self.sink.set_opacity(window, None);
+        }
+        let animated = self.state.lock().unwrap().windows.remove(&window);
+        if animated.is_some_and(|a| a.is_fading()) {
+            self.sink.set_opacity(window, None);
+        }
     }
 
     pub fn clear(&mut self) {
-        self.running.store(false, Ordering::Release);
+        self.cancel();
         self.windows.clear();
-        if let Some(t) = self.animation_thread.take() {
-            t.join().unwrap();
+        self.apply_transitions();
+    }
+
+    /// Moves the pending transitions to the queued windows, discarding the ones of the windows
+    /// that are not queued
+    fn apply_transitions(&mut self) {
+        for (win, transition) in self.transitions.drain() {
+            match self.windows.get_mut(&win) {
+                Some(info) => info.transition = Some(transition),
+                None if transition.is_fade() => self.sink.set_opacity(win, None),
+                None => {}
+            }
         }
     }
 
-    pub fn play(&mut self, animation: Option<WindowAnimation>, cb_done: Option<Arc<dyn Fn() + Send + Sync + 'static>>) {
-        self.cancel();
+    /// Animates the queued windows. The windows already being animated are retargeted from their
+    /// current (interpolated) area, while the other running animations go on undisturbed. The
+    /// `cb_done` callback runs once all the running animations end, even if they are cancelled.
+    pub fn play(&mut self, animation: Option<AnimationSettings>, cb_done: Option<Callback>) {
+        self.apply_transitions();
+        let Some(settings) = animation else {
+            self.cancel();
+            let wins_to_move: HashSet<WindowRef> = self
+                .windows
+                .iter()
+                .filter(|(w, i)| self.sink.get_area(**w).is_some_and(|a| a != i.target_area))
+                .map(|(w, _)| *w)
+                .collect();
+            (self.on_start)(wins_to_move);
+            Self::complete_windows(self.sink.as_ref(), &self.windows);
+            (self.on_complete)();
+            cb_done.inspect(|f| f());
+            self.windows.clear();
+            return;
+        };
 
-        let wins = self.windows.clone();
-        let wins_info: Vec<(WindowRef, Area, Area)> = wins
-            .clone()
-            .into_iter()
-            .filter_map(|(w, i)| {
-                let src_area: Area = w.get_area()?;
-                if src_area == i.target_area {
-                    if let Some(topmost) = i.topmost {
-                        w.set_topmost(topmost).ok();
-                    }
-                    return None;
-                };
-                Some((w, src_area, i.target_area))
-            })
-            .collect();
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let mut wins_to_animate = HashSet::new();
+        for (win, info) in self.windows.drain() {
+            let prev = state.windows.remove(&win);
+            if prev.as_ref().is_some_and(|a| a.is_fading()) && !info.transition.is_some_and(|t| t.is_fade()) {
+                self.sink.set_opacity(win, None);
+            }
 
-        let wins_to_animate: HashSet<WindowRef> = wins_info.iter().map(|(w, _, _)| *w).collect();
-        if animation.is_none() {
+            let src_area = match (&prev, info.transition) {
+                (Some(animated), _) => Some(animated.area_at(now)),
+                (None, Some(WindowTransition::SlideIn(dx, dy))) => {
+                    let area = info.target_area;
+                    Some(Area::new(area.x + dx, area.y + dy, area.width, area.height))
+                }
+                (None, _) => self.sink.get_area(win),
+            };
+            let Some(src_area) = src_area else {
+                continue;
+            };
+
+            if src_area == info.target_area && info.transition.is_none() {
+                if let Some(topmost) = info.topmost {
+                    self.sink.set_topmost(win, topmost);
+                }
+                continue;
+            }
+
+            let animated = AnimatedWindow {
+                src_area,
+                target: info,
+                started_at: now,
+                settings,
+            };
+            state.windows.insert(win, animated);
+            wins_to_animate.insert(win);
+        }
+
+        // INFO: the callbacks of the retargeted animations are kept, all of them run once the animations end
+        state.cb_done.extend(cb_done);
+
+        if state.windows.is_empty() {
+            let cb_done = std::mem::take(&mut state.cb_done);
+            drop(state);
             (self.on_start)(wins_to_animate);
-            Self::move_windows(&self.windows);
-            Self::set_windows_topmost(&self.windows);
             (self.on_complete)();
-            cb_done.inspect(|f| f());
-            self.clear();
+            cb_done.iter().for_each(|f| f());
             return;
         }
 
-        let animation = animation.unwrap();
-        let running = self.running.clone();
-        let duration = self.animation_duration.as_millis();
-        let framerate = self.framerate;
-        let frame_duration = Duration::from_millis(1000 / u64::from(framerate));
+        // INFO: the thread ends (under the lock) as soon as there are no windows left to animate
+        if !self.running.load(Ordering::Acquire) {
+            if let Some(t) = self.animation_thread.take() {
+                t.join().unwrap();
+            }
+            self.running.store(true, Ordering::Release);
+            self.animation_thread = Some(self.spawn_animation_thread());
+        }
+        drop(state);
 
-        let on_start = self.on_start.clone();
-        let on_error = self.on_error.clone();
-        let on_complete = self.on_complete.clone();
-        let cb_done = cb_done;
+        (self.on_start)(wins_to_animate);
+    }
 
-        self.running.store(true, Ordering::Release);
+    /// Stops the running animations, moving the windows to their target areas
+    pub fn cancel(&mut self) {
+        if let Some(t) = self.animation_thread.take() {
+            self.cancelled.store(true, Ordering::Release);
+            t.join().unwrap();
+            self.cancelled.store(false, Ordering::Release);
+        }
+    }
 
-        (on_start)(wins_to_animate);
-        self.animation_thread = Some(std::thread::spawn(move || {
-            let mut is_running = running.load(Ordering::Acquire);
+    fn spawn_animation_thread(&self) -> std::thread::JoinHandle<()> {
+        let sink = self.sink.clone();
+        let state = self.state.clone();
+        let running = self.running.clone();
+        let cancelled = self.cancelled.clone();
+        let frame_duration = Duration::from_millis(1000 / u64::from(self.framerate));
+        let on_error = self.on_error.clone();
+        let on_complete = self.on_complete.clone();
 
-            let set_pos_flags = SWP_NOSENDCHANGING | SWP_NOACTIVATE | SWP_NOZORDER;
-            let start_time = SystemTime::now();
-            while is_running {
-                let passed = start_time.elapsed().unwrap().as_millis();
-                is_running = running.load(Ordering::Acquire) && passed <= duration;
-                if !is_running {
-                    break;
+        std::thread::spawn(move || {
+            // INFO: the loop ends holding the lock, so that no window can be added before the thread stops
+            let mut guard = loop {
+                let frame_start = Instant::now();
+                let mut guard = state.lock().unwrap();
+                if cancelled.load(Ordering::Acquire) || guard.windows.is_empty() {
+                    break guard;
                 }
-                let complete_frac = (passed as f32 / duration as f32).clamp(0.0, 1.0);
-                let res = Self::animate_frame(&wins_info, &animation, complete_frac, frame_duration, &set_pos_flags);
-                if let Err(win) = res {
+
+                if let Err(win) = Self::animate_frame(sink.as_ref(), &mut guard.windows) {
                     log::warn!("Failed to animate window {:?}", win);
+                    guard
+                        .windows
+                        .iter()
+                        .filter(|(_, a)| a.is_fading())
+                        .for_each(|(w, _)| sink.set_opacity(*w, None));
+                    guard.windows.clear();
+                    guard.cb_done.clear();
+                    running.store(false, Ordering::Release);
+                    drop(guard);
                     (on_error)();
                     return;
                 }
-            }
 
-            Self::move_windows(&wins);
-            Self::set_windows_topmost(&wins);
-            running.store(false, Ordering::Release);
-            (on_complete)();
-            cb_done.inspect(|f| f());
-        }));
+                drop(guard);
+                if frame_start.elapsed() < frame_duration {
+                    std::thread::sleep(frame_duration.saturating_sub(frame_start.elapsed()));
+                }
+            };
 
-        self.windows.clear();
-    }
+            let remaining: HashMap<WindowRef, WindowAnimationQueueInfo> =
+                guard.windows.drain().map(|(w, a)| (w, a.target)).collect();
+            Self::complete_windows(sink.as_ref(), &remaining);
+            let cb_done = std::mem::take(&mut guard.cb_done);
+            running.store(false, Ordering::Release);
+            drop(guard);
 
-    pub fn cancel(&mut self) {
-        if let Some(t) = self.animation_thread.take() {
-            self.running.store(false, Ordering::Release);
-            t.join().unwrap();
-        }
+            (on_complete)();
+            cb_done.iter().for_each(|f| f());
+        })
     }
 
-    fn animate_frame(
-        wins: &[(WindowRef, Area, Area)],
-        animation: &WindowAnimation,
-        complete_frac: f32,
-        frame_duration: Duration,
-        set_pos_flags: &SET_WINDOW_POS_FLAGS,
-    ) -> Result<(), WindowRef> {
-        let frame_start = Instant::now();
-        for (win, src_area, trg_area) in wins.iter() {
-            if src_area == trg_area {
+    /// Moves each window to its interpolated area, completing the animations whose time is over
+    fn animate_frame(sink: &dyn FrameSink, wins: &mut HashMap<WindowRef, AnimatedWindow>) -> Result<(), WindowRef> {
+        let now = Instant::now();
+        let mut completed = HashMap::new();
+        for (win, animated) in wins.iter() {
+            if animated.progress(now) >= 1.0 {
+                completed.insert(*win, animated.target.clone());
                 continue;
             }
 
-            let ((x1, y1), (w1, h1)) = (src_area.get_origin(), src_area.get_size());
-            let ((x2, y2), (w2, h2)) = (trg_area.get_origin(), trg_area.get_size());
-            let new_area = Area::new(
-                animation.get_next_frame(x1 as f32, x2 as f32, complete_frac) as i32,
-                animation.get_next_frame(y1 as f32, y2 as f32, complete_frac) as i32,
-                animation.get_next_frame(w1 as f32, w2 as f32, complete_frac) as u16,
-                animation.get_next_frame(h1 as f32, h2 as f32, complete_frac) as u16,
-            );
-
-            let (pos, size) = (new_area.get_origin(), new_area.get_size());
-            if win.resize_and_move(pos, size, false, *set_pos_flags).is_err() {
+            if sink.draw_frame(*win, animated.area_at(now)).is_err() {
                 return Err(*win);
             }
+            if let Some(alpha) = animated.opacity_at(now) {
+                sink.set_opacity(*win, Some(alpha));
+            }
         }
 
-        if frame_start.elapsed() < frame_duration {
-            let sleep_time = frame_duration.saturating_sub(frame_start.elapsed());
-            std::thread::sleep(sleep_time);
-        }
-
+        wins.retain(|w, _| !completed.contains_key(w));
+        Self::complete_windows(sink, &completed);
         Ok(())
     }
 
-    fn move_windows(windows: &HashMap<WindowRef, WindowAnimationQueueInfo>) {
-        let flags = SWP_SHOWWINDOW | SWP_NOSENDCHANGING | SWP_NOACTIVATE | SWP_NOZORDER;
+    /// Moves the windows to their target areas and ends their transitions
+    fn complete_windows(sink: &dyn FrameSink, windows: &HashMap<WindowRef, WindowAnimationQueueInfo>) {
+        Self::move_windows(sink, windows);
+        Self::set_windows_topmost(sink, windows);
+        windows.iter().for_each(|(win, info)| match info.transition {
+            Some(WindowTransition::SlideOut) => sink.minimize(*win),
+            // INFO: the window is minimized before being opaque again, so that it doesn't flash
+            Some(WindowTransition::FadeOut) => {
+                sink.minimize(*win);
+                sink.set_opacity(*win, None);
+            }
+            Some(WindowTransition::FadeIn) => sink.set_opacity(*win, None),
+            Some(WindowTransition::SlideIn(..)) | None => {}
+        });
+    }
+
+    fn move_windows(sink: &dyn FrameSink, windows: &HashMap<WindowRef, WindowAnimationQueueInfo>) {
         windows
             .iter()
-            .filter(|(win, info)| win.get_area().is_some_and(|a| a != info.target_area))
-            .for_each(|(win, info)| {
-                let (pos, size) = (info.target_area.get_origin(), info.target_area.get_size());
-                win.resize_and_move(pos, size, false, flags).ok();
-            });
+            .filter(|(win, info)| sink.get_area(**win).is_some_and(|a| a != info.target_area))
+            .for_each(|(win, info)| sink.place(*win, info.target_area));
     }
 
-    fn set_windows_topmost(windows: &HashMap<WindowRef, WindowAnimationQueueInfo>) {
+    fn set_windows_topmost(sink: &dyn FrameSink, windows: &HashMap<WindowRef, WindowAnimationQueueInfo>) {
         windows
             .iter()
             .filter_map(|(win, info)| info.topmost.map(|topmost| (win, topmost)))
-            .for_each(|(win, topmost)| {
-                win.set_topmost(topmost).ok();
-            });
+            .for_each(|(win, topmost)| sink.set_topmost(*win, topmost));
     }
 }
 
 #[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
-#[serde(rename_all = "snake_case")]
+#[serde(try_from = "String", into = "String")]
 pub enum WindowAnimation {
     Linear,
     EaseIn,
@@ -262,14 +473,147 @@ pub enum WindowAnimation {
     EaseInOutBack,
     EaseInOutElastic,
     EaseInOutBounce,
+    /// Cubic Bézier curve with the control points (x1, y1) and (x2, y2), as in CSS
+    CubicBezier(f32, f32, f32, f32),
+    /// Damped spring, the duration of the animation being the time it takes to settle
+    Spring {
+        stiffness: f32,
+        damping: f32,
+    },
 }
 
+const NAMED_ANIMATIONS: [(&str, WindowAnimation); 34] = [
+    ("linear", WindowAnimation::Linear),
+    ("ease_in", WindowAnimation::EaseIn),
+    ("ease_in_sine", WindowAnimation::EaseInSine),
+    ("ease_in_quad", WindowAnimation::EaseInQuad),
+    ("ease_in_cubic", WindowAnimation::EaseInCubic),
+    ("ease_in_quart", WindowAnimation::EaseInQuart),
+    ("ease_in_quint", WindowAnimation::EaseInQuint),
+    ("ease_in_expo", WindowAnimation::EaseInExpo),
+    ("ease_in_circ", WindowAnimation::EaseInCirc),
+    ("ease_in_back", WindowAnimation::EaseInBack),
+    ("ease_in_elastic", WindowAnimation::EaseInElastic),
+    ("ease_in_bounce", WindowAnimation::EaseInBounce),
+    ("ease_out", WindowAnimation::EaseOut),
+    ("ease_out_sine", WindowAnimation::EaseOutSine),
+    ("ease_out_quad", WindowAnimation::EaseOutQuad),
+    ("ease_out_cubic", WindowAnimation::EaseOutCubic),
+    ("ease_out_quart", WindowAnimation::EaseOutQuart),
+    ("ease_out_quint", WindowAnimation::EaseOutQuint),
+    ("ease_out_expo", WindowAnimation::EaseOutExpo),
+    ("ease_out_circ", WindowAnimation::EaseOutCirc),
+    ("ease_out_back", WindowAnimation::EaseOutBack),
+    ("ease_out_elastic", WindowAnimation::EaseOutElastic),
+    ("ease_out_bounce", WindowAnimation::EaseOutBounce),
+    ("ease_in_out", WindowAnimation::EaseInOut),
+    ("ease_in_out_sine", WindowAnimation::EaseInOutSine),
+    ("ease_in_out_quad", WindowAnimation::EaseInOutQuad),
+    ("ease_in_out_cubic", WindowAnimation::EaseInOutCubic),
+    ("ease_in_out_quart", WindowAnimation::EaseInOutQuart),
+    ("ease_in_out_quint", WindowAnimation::EaseInOutQuint),
+    ("ease_in_out_expo", WindowAnimation::EaseInOutExpo),
+    ("ease_in_out_circ", WindowAnimation::EaseInOutCirc),
+    ("ease_in_out_back", WindowAnimation::EaseInOutBack),
+    ("ease_in_out_elastic", WindowAnimation::EaseInOutElastic),
+    ("ease_in_out_bounce", WindowAnimation::EaseInOutBounce),
+];
+
+const DEFAULT_SPRING: WindowAnimation = WindowAnimation::Spring {
+    stiffness: 170.0,
+    damping: 26.0,
+};
+
 impl Default for WindowAnimation {
     fn default() -> Self {
         Self::Linear
     }
 }
 
+impl Display for WindowAnimation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowAnimation::CubicBezier(x1, y1, x2, y2) => write!(f, "cubic_bezier({x1}, {y1}, {x2}, {y2})"),
+            WindowAnimation::Spring { stiffness, damping } => write!(f, "spring({stiffness}, {damping})"),
+            named => {
+                let name = NAMED_ANIMATIONS.iter().find(|(_, a)| a == named).map(|(n, _)| *n);
+                write!(f, "{}", name.unwrap_or_default())
+            }
+        }
+    }
+}
+
+impl FromStr for WindowAnimation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((_, animation)) = NAMED_ANIMATIONS.iter().find(|(n, _)| *n == s) {
+            return Ok(*animation);
+        }
+
+        if s == "spring" {
+            return Ok(DEFAULT_SPRING);
+        }
+
+        let (name, params) = s
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .filter(|(name, _)| ["cubic_bezier", "spring"].contains(name))
+            .ok_or(format!("Unknown animation type: {s}"))?;
+        let params = params
+            .split(',')
+            .map(|p| p.trim().parse::<f32>())
+            .collect::<Result<Vec<f32>, _>>()
+            .map_err(|_| format!("Invalid parameters in '{s}'"))?;
+
+        match (name, params.as_slice()) {
+            ("cubic_bezier", [x1, y1, x2, y2]) if (0.0..=1.0).contains(x1) && (0.0..=1.0).contains(x2) => {
+                Ok(WindowAnimation::CubicBezier(*x1, *y1, *x2, *y2))
+            }
+            ("cubic_bezier", [_, _, _, _]) => Err("The x coordinates of a cubic Bézier must be between 0 and 1".into()),
+            ("spring", [stiffness, damping]) if *stiffness > 0.0 && *damping > 0.0 => Ok(WindowAnimation::Spring {
+                stiffness: *stiffness,
+                damping: *damping,
+            }),
+            ("spring", [_, _]) => Err("The stiffness and the damping of a spring must be positive".into()),
+            (name, _) => Err(format!("Wrong number of parameters for '{name}'")),
+        }
+    }
+}
+
+impl TryFrom<String> for WindowAnimation {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<WindowAnimation> for String {
+    fn from(value: WindowAnimation) -> Self {
+        value.to_string()
+    }
+}
+
+impl JsonSchema for WindowAnimation {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "WindowAnimation".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        const NUMBER: &str = r"\s*-?[0-9]+(\.[0-9]+)?\s*";
+        let names: Vec<&str> = NAMED_ANIMATIONS.iter().map(|(n, _)| *n).collect();
+        json_schema!({
+            "type": "string",
+            "anyOf": [
+                { "enum": names },
+                { "pattern": format!("^cubic_bezier\\({NUMBER},{NUMBER},{NUMBER},{NUMBER}\\)$") },
+                { "pattern": format!("^spring(\\({NUMBER},{NUMBER}\\))?$") },
+            ]
+        })
+    }
+}
+
 impl WindowAnimation {
     fn get_next_frame(&self, src: f32, trg: f32, percent: f32) -> f32 {
         let t = percent;
@@ -323,6 +667,8 @@ impl WindowAnimation {
                 true => (1.0 - Self::ease_out_bounce(1.0 - 2.0 * t)) / 2.0,
                 false => (1.0 + Self::ease_out_bounce(2.0 * t - 1.0)) / 2.0,
             },
+            Self::CubicBezier(x1, y1, x2, y2) => Self::cubic_bezier(t, (*x1, *y1), (*x2, *y2)),
+            Self::Spring { stiffness, damping } => Self::spring(t, *stiffness, *damping),
         };
         Self::lerp(src, trg, t)
     }
@@ -331,6 +677,54 @@ impl WindowAnimation {
         a + (b - a) * t
     }
 
+    fn cubic_bezier(t: f32, p1: (f32, f32), p2: (f32, f32)) -> f32 {
+        let bezier =
+            |u: f32, c1: f32, c2: f32| 3.0 * (1.0 - u).powi(2) * u * c1 + 3.0 * (1.0 - u) * u.powi(2) * c2 + u.powi(3);
+
+        // INFO: x is monotonic (the x coordinates are between 0 and 1), so the curve parameter is
+        // found by bisection
+        let (mut low, mut high, mut u) = (0.0, 1.0, t);
+        for _ in 0..24 {
+            u = (low + high) / 2.0;
+            match bezier(u, p1.0, p2.0) < t {
+                true => low = u,
+                false => high = u,
+            }
+        }
+        bezier(u, p1.1, p2.1)
+    }
+
+    fn spring(t: f32, stiffness: f32, damping: f32) -> f32 {
+        if t >= 1.0 {
+            return 1.0;
+        }
+
+        let omega = stiffness.sqrt();
+        let zeta = damping / (2.0 * omega);
+        let position = |s: f32| match zeta {
+            z if (z - 1.0).abs() < 1e-3 => 1.0 - (-omega * s).exp() * (1.0 + omega * s),
+            z if z < 1.0 => {
+                let omega_d = omega * (1.0 - z * z).sqrt();
+                let decay = (-z * omega * s).exp();
+                1.0 - decay * ((omega_d * s).cos() + (z * omega / omega_d) * (omega_d * s).sin())
+            }
+            z => {
+                // INFO: overdamped, the sum of a slow and a fast exponential decay
+                let (r1, r2) = (-omega * (z - (z * z - 1.0).sqrt()), -omega * (z + (z * z - 1.0).sqrt()));
+                1.0 - (r2 * (r1 * s).exp() - r1 * (r2 * s).exp()) / (r2 - r1)
+            }
+        };
+
+        // INFO: the animation lasts until the displacement of the slowest decay is below 0.1% of
+        // the distance, and what is left of it is spread over the animation to end on the target
+        let rate = match zeta < 1.0 {
+            true => zeta * omega,
+            false => omega * (zeta - (zeta * zeta - 1.0).sqrt()),
+        };
+        let end = 1000f32.ln() / rate;
+        position(t * end) + (1.0 - position(end)) * t
+    }
+
     fn ease_in_back(t: f32) -> f32 {
         const C1: f32 = 1.70158;
         const C3: f32 = C1 + 1.0;
@@ -405,3 +799,307 @@ impl WindowAnimation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AnimationSettings;
+    use super::WindowAnimation;
+    use super::WindowAnimationPlayer;
+    use super::WindowTransition;
+    use super::NAMED_ANIMATIONS;
+    use crate::app::structs::area::Area;
+    use crate::modules::tiles_manager::lib::frame_sink::FrameSink;
+    use crate::modules::tiles_manager::lib::frame_sink::RecordingFrameSink;
+    use crate::modules::tiles_manager::lib::frame_sink::SinkEvent;
+    use crate::win32::window::window_ref::WindowRef;
+    use std::sync::mpsc;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const FRAMERATE: u8 = 50;
+
+    fn player(sink: &Arc<RecordingFrameSink>) -> WindowAnimationPlayer {
+        WindowAnimationPlayer::with_sink(sink.clone(), FRAMERATE, |_| {}, || {}, || {})
+    }
+
+    fn settings(animation: WindowAnimation, duration: u64) -> Option<AnimationSettings> {
+        Some(AnimationSettings {
+            animation,
+            duration: Duration::from_millis(duration),
+        })
+    }
+
+    fn play_and_wait(player: &mut WindowAnimationPlayer, animation: Option<AnimationSettings>) {
+        let (tx, rx) = mpsc::channel();
+        player.play(animation, Some(Arc::new(move || tx.send(()).unwrap())));
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    #[test]
+    fn test_frames() {
+        let (win, src, trg) = (
+            WindowRef::from(1isize),
+            Area::new(0, 0, 100, 100),
+            Area::new(500, 0, 300, 100),
+        );
+        let sink = Arc::new(RecordingFrameSink::new([(win, src)]));
+        let mut player = player(&sink);
+
+        player.queue(win, trg, None);
+        play_and_wait(&mut player, settings(WindowAnimation::Linear, 200));
+
+        // INFO: the frames are never more than the ones fitting in the duration
+        let frames = sink.frames(win);
+        let max_frames = 200 / (1000 / usize::from(FRAMERATE)) + 1;
+        assert!(!frames.is_empty() && frames.len() <= max_frames);
+        assert!(frames.windows(2).all(|f| f[0].x <= f[1].x && f[0].width <= f[1].width));
+        assert!(frames.iter().all(|f| f.x < trg.x && f.y == trg.y));
+        assert_eq!(sink.events().last(), Some(&SinkEvent::Place(win, trg)));
+        assert_eq!(sink.get_area(win), Some(trg));
+    }
+
+    #[test]
+    fn test_no_animation() {
+        let (win, src, trg) = (
+            WindowRef::from(1isize),
+            Area::new(0, 0, 100, 100),
+            Area::new(500, 0, 100, 100),
+        );
+        let sink = Arc::new(RecordingFrameSink::new([(win, src)]));
+        let mut player = player(&sink);
+
+        player.queue(win, trg, Some(true));
+        play_and_wait(&mut player, None);
+
+        assert_eq!(
+            sink.events(),
+            vec![SinkEvent::Place(win, trg), SinkEvent::Topmost(win, true)]
+        );
+    }
+
+    #[test]
+    fn test_cancel() {
+        let wins = [
+            (
+                WindowRef::from(1isize),
+                Area::new(0, 0, 100, 100),
+                Area::new(500, 0, 100, 100),
+            ),
+            (
+                WindowRef::from(2isize),
+                Area::new(100, 0, 100, 100),
+                Area::new(0, 300, 200, 200),
+            ),
+        ];
+        let sink = Arc::new(RecordingFrameSink::new(wins.iter().map(|(w, src, _)| (*w, *src))));
+        let mut player = player(&sink);
+
+        wins.iter().for_each(|(w, _, trg)| player.queue(*w, *trg, None));
+        player.play(settings(WindowAnimation::EaseOutBack, 10000), None);
+        std::thread::sleep(Duration::from_millis(100));
+        player.cancel();
+
+        for (w, _, trg) in wins {
+            assert_eq!(sink.get_area(w), Some(trg));
+        }
+    }
+
+    #[test]
+    fn test_retarget() {
+        let (win, src) = (WindowRef::from(1isize), Area::new(0, 0, 100, 100));
+        let (trg1, trg2) = (Area::new(1000, 0, 100, 100), Area::new(0, 500, 100, 100));
+        let sink = Arc::new(RecordingFrameSink::new([(win, src)]));
+        let mut player = player(&sink);
+
+        player.queue(win, trg1, None);
+        player.play(settings(WindowAnimation::Linear, 1000), None);
+        std::thread::sleep(Duration::from_millis(200));
+        player.queue(win, trg2, None);
+        play_and_wait(&mut player, settings(WindowAnimation::Linear, 200));
+
+        // INFO: the window goes back from where it was, rather than restarting from the source
+        let frames = sink.frames(win);
+        let turn = frames.iter().position(|f| f.y > 0).unwrap();
+        assert!(turn > 0, "the window was retargeted before its first frame");
+        assert!(frames[turn].x > 0 && frames[turn].x <= frames[turn - 1].x);
+        assert_eq!(sink.get_area(win), Some(trg2));
+    }
+
+    #[test]
+    fn test_retarget_callbacks() {
+        let (win, src) = (WindowRef::from(1isize), Area::new(0, 0, 100, 100));
+        let (trg1, trg2) = (Area::new(1000, 0, 100, 100), Area::new(0, 500, 100, 100));
+        let sink = Arc::new(RecordingFrameSink::new([(win, src)]));
+        let mut player = player(&sink);
+        let (tx, rx) = mpsc::channel();
+
+        player.queue(win, trg1, None);
+        let tx1 = tx.clone();
+        player.play(
+            settings(WindowAnimation::Linear, 1000),
+            Some(Arc::new(move || tx1.send(1).unwrap())),
+        );
+        std::thread::sleep(Duration::from_millis(100));
+        player.queue(win, trg2, None);
+        player.play(
+            settings(WindowAnimation::Linear, 200),
+            Some(Arc::new(move || tx.send(2).unwrap())),
+        );
+
+        let mut done: Vec<i32> = (0..2)
+            .map(|_| rx.recv_timeout(Duration::from_secs(5)).unwrap())
+            .collect();
+        done.sort();
+        assert_eq!(done, vec![1, 2]);
+        assert_eq!(sink.get_area(win), Some(trg2));
+    }
+
+    #[test]
+    fn test_cancel_callbacks() {
+        let (win, src, trg) = (
+            WindowRef::from(1isize),
+            Area::new(0, 0, 100, 100),
+            Area::new(500, 0, 100, 100),
+        );
+        let sink = Arc::new(RecordingFrameSink::new([(win, src)]));
+        let mut player = player(&sink);
+        let (tx, rx) = mpsc::channel();
+
+        player.queue(win, trg, None);
+        player.play(
+            settings(WindowAnimation::Linear, 10000),
+            Some(Arc::new(move || tx.send(()).unwrap())),
+        );
+        std::thread::sleep(Duration::from_millis(100));
+        player.cancel();
+
+        assert!(rx.try_recv().is_ok());
+        assert_eq!(sink.get_area(win), Some(trg));
+    }
+
+    #[test]
+    fn test_fade_out() {
+        let (win, area) = (WindowRef::from(1isize), Area::new(0, 0, 100, 100));
+        let sink = Arc::new(RecordingFrameSink::new([(win, area)]));
+        let mut player = player(&sink);
+
+        player.queue(win, area, None);
+        player.set_transition(win, WindowTransition::FadeOut);
+        play_and_wait(&mut player, settings(WindowAnimation::Linear, 100));
+
+        // INFO: the window is minimized before being opaque again
+        let events = sink.events();
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SinkEvent::Opacity(w, Some(alpha)) if *w == win && *alpha < 255)));
+        assert_eq!(
+            events[events.len() - 2..],
+            [SinkEvent::Minimize(win), SinkEvent::Opacity(win, None)]
+        );
+    }
+
+    #[test]
+    fn test_slide_in() {
+        let (win, src, trg) = (
+            WindowRef::from(1isize),
+            Area::new(0, 0, 100, 100),
+            Area::new(200, 0, 100, 100),
+        );
+        let sink = Arc::new(RecordingFrameSink::new([(win, src)]));
+        let mut player = player(&sink);
+
+        player.queue(win, trg, None);
+        player.set_transition(win, WindowTransition::SlideIn(1000, 0));
+        play_and_wait(&mut player, settings(WindowAnimation::Linear, 200));
+
+        // INFO: the window starts from the offset area rather than from its current one
+        let frames = sink.frames(win);
+        assert!(!frames.is_empty());
+        assert!(frames
+            .iter()
+            .all(|f| f.x >= trg.x && f.x <= trg.x + 1000 && f.y == trg.y));
+        assert!(frames.windows(2).all(|f| f[0].x >= f[1].x));
+        assert_eq!(sink.get_area(win), Some(trg));
+    }
+
+    #[test]
+    fn test_easings_endpoints() {
+        let custom = [WindowAnimation::CubicBezier(0.3, -0.5, 0.7, 1.5), super::DEFAULT_SPRING];
+        for animation in NAMED_ANIMATIONS.iter().map(|(_, a)| *a).chain(custom) {
+            assert!(animation.get_next_frame(0.0, 1000.0, 0.0).abs() < 1.0, "{animation}");
+            assert!(
+                (animation.get_next_frame(0.0, 1000.0, 1.0) - 1000.0).abs() < 1.0,
+                "{animation}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_easings_monotonic() {
+        let overshooting = ["back", "elastic", "bounce"];
+        let custom = [
+            WindowAnimation::CubicBezier(0.25, 0.1, 0.25, 1.0),
+            WindowAnimation::Spring {
+                stiffness: 100.0,
+                damping: 40.0,
+            },
+        ];
+        let animations = NAMED_ANIMATIONS
+            .iter()
+            .filter(|(n, _)| !overshooting.iter().any(|o| n.ends_with(o)))
+            .map(|(_, a)| *a)
+            .chain(custom);
+
+        for animation in animations {
+            let values: Vec<f32> = (0..=100)
+                .map(|t| animation.get_next_frame(0.0, 1000.0, t as f32 / 100.0))
+                .collect();
+            assert!(values.windows(2).all(|v| v[1] >= v[0] - 0.01), "{animation}");
+        }
+    }
+
+    #[test]
+    fn test_springs_end_on_target() {
+        let springs = [
+            (170.0, 26.0),
+            (300.0, 10.0),
+            (100.0, 20.0),
+            (100.0, 40.0),
+            (50.0, 100.0),
+        ];
+        for (stiffness, damping) in springs {
+            let spring = WindowAnimation::Spring { stiffness, damping };
+            let last = spring.get_next_frame(0.0, 1000.0, 0.99);
+            assert!((last - 1000.0).abs() < 5.0, "{spring}: {last}");
+            assert_eq!(spring.get_next_frame(0.0, 1000.0, 1.0), 1000.0, "{spring}");
+        }
+
+        // INFO: an overdamped spring keeps moving for the whole animation
+        let overdamped = WindowAnimation::Spring {
+            stiffness: 50.0,
+            damping: 100.0,
+        };
+        assert!(overdamped.get_next_frame(0.0, 1000.0, 0.5) < 990.0);
+    }
+
+    #[test]
+    fn test_parse_easings() {
+        assert_eq!("ease_out_back".parse(), Ok(WindowAnimation::EaseOutBack));
+        assert_eq!(
+            "cubic_bezier(0.1, -1, 0.5,2)".parse(),
+            Ok(WindowAnimation::CubicBezier(0.1, -1.0, 0.5, 2.0))
+        );
+        assert_eq!("spring".parse(), Ok(super::DEFAULT_SPRING));
+        assert!("cubic_bezier(1.5, 0, 0, 1)".parse::<WindowAnimation>().is_err());
+        assert!("spring(0, 10)".parse::<WindowAnimation>().is_err());
+        assert!("spring(1, 2, 3)".parse::<WindowAnimation>().is_err());
+        assert!("spring(1, 2".parse::<WindowAnimation>().is_err());
+        assert!("bounce(1, 2)".parse::<WindowAnimation>().is_err());
+
+        let spring = WindowAnimation::Spring {
+            stiffness: 200.0,
+            damping: 20.5,
+        };
+        assert_eq!(spring.to_string().parse(), Ok(spring));
+    }
+}
@@ -1,4 +1,6 @@
 use super::configs::CoreModuleConfigs;
+use super::lib::structs::rules::explain_rules;
+use super::lib::tm::command::coalesce_commands;
 use super::lib::tm::command::TMCommand;
 use super::lib::tm::public::TilesManagerCommands;
 use super::lib::tm::public::TilesManagerEvents;
@@ -17,19 +19,28 @@ use crate::modules::utils;
 use crate::modules::ConfigurableModule;
 use crate::modules::Module;
 use crate::win32::api::monitor::enum_display_monitors;
+use crate::win32::api::window::get_foreground_window;
 use crate::win32::window::window_obj::WindowObjInfo;
+use crate::win32::window::window_ref::WindowRef;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
+use std::collections::VecDeque;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU32;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
+use std::time::Instant;
 
 pub struct TilesManagerModule {
     tm_command_tx: Option<Sender<TMCommand>>,
     tiles_manager_thread: Option<thread::JoinHandle<()>>,
-    configs: CoreModuleConfigs,
+    configs: Box<CoreModuleConfigs>,
     running: Arc<AtomicBool>,
+    animations_enabled: Arc<AtomicBool>,
+    events_coalescing: Arc<AtomicU32>,
+    max_batch_latency: Arc<AtomicU32>,
     enabled: bool,
     bus_tx: Sender<MondrianMessage>,
 }
@@ -37,10 +48,13 @@ pub struct TilesManagerModule {
 impl TilesManagerModule {
     pub fn new(bus_tx: Sender<MondrianMessage>) -> Self {
         TilesManagerModule {
-            configs: CoreModuleConfigs::default(),
+            configs: Box::default(),
             tm_command_tx: None,
             tiles_manager_thread: None,
             running: Arc::new(AtomicBool::new(false)),
+            animations_enabled: Arc::new(AtomicBool::new(false)),
+            events_coalescing: Arc::new(AtomicU32::new(0)),
+            max_batch_latency: Arc::new(AtomicU32::new(0)),
             enabled: true,
             bus_tx,
         }
@@ -59,11 +73,10 @@ impl TilesManagerModule {
         let tm_configs = self.configs.tm_configs.clone();
 
         let app_tx = self.bus_tx.clone();
-        let animations_enabled = tm_configs.animation.animation_type.is_some();
+        let animations_enabled = self.animations_enabled.clone();
         let on_update_start = move |wins| {
-            app_tx
-                .send(MondrianMessage::CoreUpdateStart(wins, animations_enabled))
-                .unwrap();
+            let animated = animations_enabled.load(Ordering::SeqCst);
+            app_tx.send(MondrianMessage::CoreUpdateStart(wins, animated)).unwrap();
         };
 
         let app_tx = self.bus_tx.clone();
@@ -92,18 +105,62 @@ impl TilesManagerModule {
         tm.update_layout(true, None).ok();
 
         let tx = self.bus_tx.clone();
+        let events_coalescing = self.events_coalescing.clone();
+        let max_batch_latency = self.max_batch_latency.clone();
         self.tiles_manager_thread = Some(thread::spawn(move || loop {
-            match event_receiver.recv() {
-                Ok(app_event) => {
-                    if !handle_tm(&mut tm, &tx, app_event) {
-                        log::trace!("TilesManager exit!");
-                        break;
-                    }
-                }
+            let app_event = match event_receiver.recv() {
+                Ok(app_event) => app_event,
                 Err(error) => {
                     log::error!("Error: {:?}", error);
                     break;
                 }
+            };
+
+            let coalescing = Duration::from_millis(events_coalescing.load(Ordering::SeqCst).into());
+            let max_latency = Duration::from_millis(max_batch_latency.load(Ordering::SeqCst).into());
+            let started_at = Instant::now();
+            let mut commands = VecDeque::from(receive_burst(app_event, &event_receiver, coalescing));
+
+            // INFO: the commands already pending are handled in the same batch, with a single layout update
+            let prev_wins = tm.get_visible_managed_windows();
+            let mut last_change = None;
+            tm.begin_batch();
+            let mut running = true;
+            while let Some(command) = commands.pop_front() {
+                if command.can_change_layout() {
+                    last_change = Some(command.clone());
+                }
+                if !handle_tm(&mut tm, &tx, command) {
+                    running = false;
+                    break;
+                }
+
+                let expired = !max_latency.is_zero() && started_at.elapsed() >= max_latency;
+                if commands.is_empty() && !expired {
+                    if let Ok(next) = event_receiver.try_recv() {
+                        commands.extend(receive_burst(next, &event_receiver, coalescing));
+                    }
+                }
+            }
+
+            if !running {
+                log::trace!("TilesManager exit!");
+                break;
+            }
+
+            tm.end_batch()
+                .inspect_err(|e| log::warn!("Layout update error: {e:?}"))
+                .ok();
+
+            // INFO: the managed windows are sent once per batch, if they changed
+            let Some(event) = last_change else {
+                continue;
+            };
+            let mut windows = tm.get_visible_managed_windows();
+            if windows != prev_wins {
+                // INFO: the windows whose overlay is disabled by the rules are left out
+                windows.retain(|w, _| tm.has_overlay(*w));
+                tx.send(MondrianMessage::UpdatedWindows(windows, event)).unwrap();
             }
         }));
     }
@@ -170,15 +227,31 @@ impl ModuleImpl for TilesManagerModule {
                 self.configure(app_configs.into());
             }
             MondrianMessage::Retile => Module::restart(self),
-            MondrianMessage::RefreshConfig => {
-                let config_changed = self.configs != app_configs.into();
+            MondrianMessage::ConfigChanged(_) => {
+                let config_changed = *self.configs != app_configs.into();
                 self.configure(app_configs.into());
                 if config_changed {
-                    Module::restart(self);
+                    let tm_configs = Box::new(self.configs.tm_configs.clone());
+                    self.send_to_tm(TMCommand::ConfigChanged(tm_configs));
                 }
             }
-            MondrianMessage::SystemEvent(evt) if *evt == SystemEvent::MonitorsLayoutChanged => {
-                Module::restart(self);
+            MondrianMessage::ExplainWindow(window) => {
+                let Some(window) = window.or_else(|| get_foreground_window().map(WindowRef::from)) else {
+                    log::warn!("No window to explain the rules of");
+                    return;
+                };
+                let infos = explain_rules(
+                    &app_configs.ignore_filter,
+                    &app_configs.delayed_filter,
+                    &app_configs.rules,
+                    window.snapshot(),
+                    &enum_display_monitors(),
+                );
+                let name = "Window Rules Explainer".to_string();
+                let icon = InfoEntryIcon::Window;
+                self.bus_tx
+                    .send(MondrianMessage::QueryInfoResponse { name, icon, infos })
+                    .ok();
             }
             MondrianMessage::HealthCheckPing => utils::send_pong(&Module::name(self), &self.bus_tx),
             MondrianMessage::Quit => Module::stop(self),
@@ -198,16 +271,46 @@ impl ModuleImpl for TilesManagerModule {
 impl ConfigurableModule for TilesManagerModule {
     type Config = CoreModuleConfigs;
     fn configure(&mut self, config: Self::Config) {
-        self.configs = config;
+        let animations_enabled = config.tm_configs.animation.animation_type.is_some();
+        self.animations_enabled.store(animations_enabled, Ordering::SeqCst);
+        self.events_coalescing.store(config.events_coalescing, Ordering::SeqCst);
+        self.max_batch_latency.store(config.max_batch_latency, Ordering::SeqCst);
+        *self.configs = config;
     }
 }
 
+/// Time without new commands after which a burst is considered over
+const BURST_IDLE_TIMEOUT: Duration = Duration::from_millis(4);
+
+/// Collects the commands received within the coalescing window, stopping at the first one that
+/// can't be coalesced or as soon as the channel stays idle for `BURST_IDLE_TIMEOUT`
+fn receive_burst(first: TMCommand, receiver: &Receiver<TMCommand>, window: Duration) -> Vec<TMCommand> {
+    if window.is_zero() || !first.is_coalescable() {
+        return vec![first];
+    }
+
+    let deadline = Instant::now() + window;
+    let mut commands = vec![first];
+    while let Ok(command) = receiver.recv_deadline(deadline.min(Instant::now() + BURST_IDLE_TIMEOUT)) {
+        let coalescable = command.is_coalescable();
+        commands.push(command);
+        if !coalescable {
+            break;
+        }
+    }
+
+    let count = commands.len();
+    let commands = coalesce_commands(commands);
+    log::trace!("Coalesced {count} commands into {}", commands.len());
+    commands
+}
+
 fn handle_tm(tm: &mut TilesManager, tx: &Sender<MondrianMessage>, event: TMCommand) -> bool {
-    let prev_wins = tm.get_visible_managed_windows();
     tm.check_for_vd_changes()
         .inspect_err(|m| log::trace!("VD changes check error: {m:?}"))
         .ok();
 
+    tm.set_animation_kind(event.animation_kind());
     let res = match event.clone() {
         TMCommand::WindowEvent(window_event) => match window_event {
             WindowEvent::Maximized(winref) => {
@@ -235,15 +338,18 @@ fn handle_tm(tm: &mut TilesManager, tx: &Sender<MondrianMessage>, event: TMComma
                 }
             }
             WindowEvent::Repositioned(w) => tm.reposition_window(w),
+            WindowEvent::TitleChanged(winref) => tm.on_title_changed(winref),
             WindowEvent::Focused(winref) => tm.on_focus(winref),
         },
         TMCommand::SystemEvent(evt) => match evt {
             SystemEvent::VirtualDesktopRemoved { destroyed, fallback } => tm.on_vd_destroyed(destroyed, fallback),
             SystemEvent::VirtualDesktopChanged { old, new } => tm.on_vd_changed(old, new),
             SystemEvent::WorkareaChanged => tm.on_workarea_changed(),
+            SystemEvent::MonitorsLayoutChanged => tm.on_monitors_changed(),
             SystemEvent::DesktopFocused { at } => tm.on_desktop_focus(at),
             _ => Ok(()),
         },
+        TMCommand::ConfigChanged(config) => tm.on_config_changed(*config),
         TMCommand::Focus(direction) => tm.change_focus(direction),
         TMCommand::FocusMonitor(direction) => tm.change_focus_monitor(direction),
         TMCommand::FocusWorkspace { id, monitor } => tm.focus_workspace(&id, monitor.as_deref()),
@@ -268,7 +374,8 @@ fn handle_tm(tm: &mut TilesManager, tx: &Sender<MondrianMessage>, event: TMComma
         TMCommand::Amplify => tm.amplify_focused(),
         TMCommand::CycleFocalized(next) => tm.cycle_focalized_wins(next, None),
         TMCommand::ListManagedWindows => {
-            let windows = tm.get_visible_managed_windows();
+            let mut windows = tm.get_visible_managed_windows();
+            windows.retain(|w, _| tm.has_overlay(*w));
             tx.send(MondrianMessage::UpdatedWindows(windows, event.clone())).ok();
             Ok(())
         }
@@ -294,13 +401,6 @@ fn handle_tm(tm: &mut TilesManager, tx: &Sender<MondrianMessage>, event: TMComma
         Ok(_) => {}
     }
 
-    if event.can_change_layout() {
-        let windows = tm.get_visible_managed_windows();
-        if windows != prev_wins {
-            tx.send(MondrianMessage::UpdatedWindows(windows, event)).unwrap();
-        }
-    }
-
     true
 }
 
@@ -5,6 +5,11 @@ use windows::Win32::Graphics::Gdi::EnumDisplayMonitors;
 use windows::Win32::Graphics::Gdi::GetMonitorInfoW;
 use windows::Win32::Graphics::Gdi::HMONITOR;
 use windows::Win32::Graphics::Gdi::MONITORINFOEXW;
+use windows::Win32::UI::HiDpi::GetDpiForMonitor;
+use windows::Win32::UI::HiDpi::MDT_EFFECTIVE_DPI;
+
+/// DPI of a monitor with a 100% scaling
+pub const DEFAULT_DPI: u32 = 96;
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -18,12 +23,26 @@ pub(crate) struct Monitor {
     pub monitor_area: Area,
     pub workspace_area: Area,
     pub offset: (i32, i32),
+    pub dpi: u32,
 }
 
 impl Monitor {
     pub fn get_workspace(&self) -> Area {
         self.workspace_area
     }
+
+    /// Returns the scaling of the monitor (e.g. 1.5 for a 150% scaling)
+    pub fn get_scale_factor(&self) -> f32 {
+        self.dpi as f32 / DEFAULT_DPI as f32
+    }
+}
+
+pub fn get_dpi_for_monitor(monitor: HMONITOR) -> u32 {
+    let (mut dpi_x, mut dpi_y) = (0, 0);
+    match unsafe { GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) } {
+        Ok(_) if dpi_x > 0 => dpi_x,
+        _ => DEFAULT_DPI,
+    }
 }
 
 pub fn get_monitor_info(monitor: HMONITOR) -> MONITORINFOEXW {
@@ -35,6 +54,11 @@ pub fn get_monitor_info(monitor: HMONITOR) -> MONITORINFOEXW {
     info
 }
 
+/// Returns the id (e.g. "MONITOR1") of the monitor with the given handle, among the given monitors
+pub fn get_monitor_id(handle: isize, monitors: &[Monitor]) -> Option<String> {
+    monitors.iter().find(|m| m.handle == handle).map(|m| m.id.clone())
+}
+
 pub fn enum_display_monitors() -> Vec<Monitor> {
     let mut monitors: Vec<Monitor> = Vec::new();
 
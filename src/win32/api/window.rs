@@ -6,7 +6,7 @@ use std::ffi::{OsStr, OsString};
 use std::mem::size_of;
 use std::os::windows::ffi::{OsStrExt, OsStringExt};
 use windows::core::PCWSTR;
-use windows::Win32::Foundation::{CloseHandle, BOOL, HANDLE, HMODULE, HWND, LPARAM, MAX_PATH, RECT, WPARAM};
+use windows::Win32::Foundation::{CloseHandle, BOOL, COLORREF, HANDLE, HMODULE, HWND, LPARAM, MAX_PATH, RECT, WPARAM};
 use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_CLOAKED, DWMWA_EXTENDED_FRAME_BOUNDS};
 use windows::Win32::Graphics::Gdi::{MonitorFromWindow, MONITOR_DEFAULTTONEAREST};
 use windows::Win32::System::LibraryLoader::GetModuleHandleExW;
@@ -20,12 +20,13 @@ use windows::Win32::UI::Input::KeyboardAndMouse::{
     SendInput, SetFocus, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP, VK_NONAME,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    CreateWindowExW, DestroyWindow, EnumWindows, GetDesktopWindow, GetForegroundWindow, GetTitleBarInfo, GetWindow,
-    GetWindowLongW, GetWindowPlacement, GetWindowRect, GetWindowTextW, GetWindowThreadProcessId, IsIconic,
-    IsWindowVisible, RealGetWindowClassW, RegisterClassExW, SendMessageW, SetForegroundWindow, ShowWindow, CS_HREDRAW,
-    CS_VREDRAW, GWL_EXSTYLE, GWL_STYLE, GW_OWNER, MINMAXINFO, SHOW_WINDOW_CMD, SW_MAXIMIZE, TITLEBARINFO,
-    WINDOWPLACEMENT, WINDOW_EX_STYLE, WINDOW_STYLE, WM_GETMINMAXINFO, WNDCLASSEXW, WNDPROC, WS_CHILD, WS_CHILDWINDOW,
-    WS_EX_TOPMOST, WS_POPUP,
+    CreateWindowExW, DestroyWindow, EnumWindows, GetAncestor, GetDesktopWindow, GetForegroundWindow, GetTitleBarInfo,
+    GetWindow, GetWindowLongW, GetWindowPlacement, GetWindowRect, GetWindowTextW, GetWindowThreadProcessId, IsIconic,
+    IsWindowVisible, RealGetWindowClassW, RegisterClassExW, SendMessageW, SetForegroundWindow,
+    SetLayeredWindowAttributes, SetWindowLongW, ShowWindow, CS_HREDRAW, CS_VREDRAW, GA_PARENT, GWL_EXSTYLE, GWL_STYLE,
+    GW_OWNER, LWA_ALPHA, MINMAXINFO, SHOW_WINDOW_CMD, SW_MAXIMIZE, TITLEBARINFO, WINDOWPLACEMENT, WINDOW_EX_STYLE,
+    WINDOW_STYLE, WM_GETMINMAXINFO, WNDCLASSEXW, WNDPROC, WS_CHILD, WS_CHILDWINDOW, WS_EX_LAYERED, WS_EX_TOPMOST,
+    WS_POPUP,
 };
 
 lazy_static!(
@@ -88,6 +89,20 @@ pub fn get_window_exstyle(hwnd: HWND) -> u32 {
     unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) as u32 }
 }
 
+/// Sets the opacity of the window, making it layered. With `None` the window is no longer layered.
+pub fn set_window_opacity(hwnd: HWND, alpha: Option<u8>) -> bool {
+    let exstyle = get_window_exstyle(hwnd);
+    let Some(alpha) = alpha else {
+        unsafe { SetWindowLongW(hwnd, GWL_EXSTYLE, (exstyle & !WS_EX_LAYERED.0) as i32) };
+        return true;
+    };
+
+    if exstyle & WS_EX_LAYERED.0 == 0 {
+        unsafe { SetWindowLongW(hwnd, GWL_EXSTYLE, (exstyle | WS_EX_LAYERED.0) as i32) };
+    }
+    unsafe { SetLayeredWindowAttributes(hwnd, COLORREF(0), alpha, LWA_ALPHA).is_ok() }
+}
+
 fn get_window_thread_process_id(hwnd: HWND) -> Option<u32> {
     let mut pid = 0;
     unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid)) };
@@ -219,6 +234,17 @@ pub fn is_owner_window(hwnd: HWND) -> bool {
     unsafe { GetWindow(hwnd, GW_OWNER).is_err() }
 }
 
+/// Returns true if the parent of the window is not the desktop window
+pub fn has_parent_window(hwnd: HWND) -> bool {
+    let parent = unsafe { GetAncestor(hwnd, GA_PARENT) };
+    !parent.is_invalid() && get_desktop_window().is_none_or(|d| d != parent)
+}
+
+/// Returns the handle of the monitor the window is (mostly) on
+pub fn get_window_monitor(hwnd: HWND) -> isize {
+    unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) }.0 as isize
+}
+
 pub fn is_window_cloaked(hwnd: HWND) -> bool {
     let cloaked: BOOL = false.into();
     match unsafe {
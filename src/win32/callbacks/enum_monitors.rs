@@ -1,6 +1,6 @@
 use crate::{
     app::structs::area::Area,
-    win32::api::monitor::{get_monitor_info, Monitor},
+    win32::api::monitor::{get_dpi_for_monitor, get_monitor_info, Monitor},
 };
 use widestring::U16CString;
 use windows::{
@@ -63,6 +63,7 @@ pub(crate) unsafe extern "system" fn enum_monitors_callback(
         workspace,
         workspace_area,
         offset: origin,
+        dpi: get_dpi_for_monitor(monitor),
     });
     true.into()
 }
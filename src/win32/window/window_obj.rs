@@ -1,10 +1,12 @@
 use windows::Win32::UI::WindowsAndMessaging::SET_WINDOW_POS_FLAGS;
 
 use crate::app::structs::area::Area;
+use crate::win32::api::monitor::Monitor;
 
 pub trait WindowObjInfo {
     fn get_title(&self) -> Option<String>;
     fn get_exe_name(&self) -> Option<String>;
+    fn get_exe_path(&self) -> Option<String>;
     fn get_class_name(&self) -> Option<String>;
     fn get_area(&self) -> Option<Area>;
     fn get_visible_area(&self) -> Option<Area>;
@@ -16,6 +18,10 @@ pub trait WindowObjInfo {
     fn is_fullscreen(&self) -> bool;
     fn is_maximized(&self) -> bool;
     fn get_window_style(&self) -> u32;
+    fn get_window_exstyle(&self) -> u32;
+    fn has_owner(&self) -> bool;
+    fn has_parent(&self) -> bool;
+    fn get_monitor_id(&self, monitors: &[Monitor]) -> Option<String>;
 }
 
 pub trait WindowObjHandler {
@@ -31,6 +37,7 @@ pub trait WindowObjHandler {
     fn restore(&self, activate: bool) -> bool;
     fn close(&self);
     fn set_topmost(&self, topmost: bool) -> Result<(), ()>;
+    fn set_opacity(&self, alpha: Option<u8>) -> Result<(), ()>;
     fn set_normal(&self);
     fn to_front(&self);
 }
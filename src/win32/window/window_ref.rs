@@ -20,7 +20,11 @@ use crate::{
     app::area_tree::leaf::AreaLeaf,
     win32::api::{
         misc::post_empty_message,
-        window::{get_dwmwa_extended_frame_bounds, is_fullscreen, is_maximized, is_window_topmost},
+        monitor::{get_monitor_id, Monitor},
+        window::{
+            get_dwmwa_extended_frame_bounds, get_executable_path, get_window_exstyle, get_window_monitor,
+            has_parent_window, is_fullscreen, is_maximized, is_owner_window, is_window_topmost, set_window_opacity,
+        },
     },
 };
 use crate::{
@@ -111,12 +115,17 @@ impl WindowRef {
     }
 
     pub fn snapshot(&self) -> WindowSnapshot {
+        let exe_path = self.get_exe_path();
         WindowSnapshot {
             hwnd: self.hwnd,
             title: self.get_title(),
-            exe_name: self.get_exe_name(),
+            exe_name: exe_path
+                .as_ref()
+                .and_then(|p| p.split('\\').next_back().map(|s| s.to_string())),
+            exe_path,
             class_name: self.get_class_name(),
             style: self.get_window_style(),
+            exstyle: self.get_window_exstyle(),
             iconic: self.is_iconic(),
             visible: self.is_visible(),
             area: self.get_area(),
@@ -126,6 +135,9 @@ impl WindowRef {
             topmost: self.is_topmost(),
             fullscreen: self.is_fullscreen(),
             maximized: self.is_maximized(),
+            owned: self.has_owner(),
+            parented: self.has_parent(),
+            monitor: get_window_monitor(self.hwnd),
         }
     }
 }
@@ -139,6 +151,10 @@ impl WindowObjInfo for WindowRef {
         get_executable_name(self.hwnd)
     }
 
+    fn get_exe_path(&self) -> Option<String> {
+        get_executable_path(self.hwnd)
+    }
+
     fn get_class_name(&self) -> Option<String> {
         Some(get_class_name(self.hwnd))
     }
@@ -202,6 +218,22 @@ impl WindowObjInfo for WindowRef {
     fn get_window_style(&self) -> u32 {
         get_window_style(self.hwnd)
     }
+
+    fn get_window_exstyle(&self) -> u32 {
+        get_window_exstyle(self.hwnd)
+    }
+
+    fn has_owner(&self) -> bool {
+        !is_owner_window(self.hwnd)
+    }
+
+    fn has_parent(&self) -> bool {
+        has_parent_window(self.hwnd)
+    }
+
+    fn get_monitor_id(&self, monitors: &[Monitor]) -> Option<String> {
+        get_monitor_id(get_window_monitor(self.hwnd), monitors)
+    }
 }
 
 impl WindowObjHandler for WindowRef {
@@ -259,6 +291,13 @@ impl WindowObjHandler for WindowRef {
         }
     }
 
+    fn set_opacity(&self, alpha: Option<u8>) -> Result<(), ()> {
+        match set_window_opacity(self.hwnd, alpha) {
+            true => Ok(()),
+            false => Err(()),
+        }
+    }
+
     fn to_front(&self) {
         unsafe { SetWindowPos(self.hwnd, HWND_TOP, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE).ok() };
     }
@@ -3,6 +3,8 @@ use std::fmt::{Debug, Display};
 use windows::Win32::Foundation::HWND;
 
 use crate::app::structs::area::Area;
+use crate::win32::api::monitor::get_monitor_id;
+use crate::win32::api::monitor::Monitor;
 
 use super::window_obj::WindowObjInfo;
 
@@ -11,8 +13,10 @@ pub struct WindowSnapshot {
     pub hwnd: HWND,
     pub(crate) title: Option<String>,
     pub(crate) exe_name: Option<String>,
+    pub(crate) exe_path: Option<String>,
     pub(crate) class_name: Option<String>,
     pub(crate) style: u32,
+    pub(crate) exstyle: u32,
     pub(crate) iconic: bool,
     pub(crate) visible: bool,
     pub(crate) area: Option<Area>,
@@ -22,6 +26,9 @@ pub struct WindowSnapshot {
     pub(crate) topmost: bool,
     pub(crate) fullscreen: bool,
     pub(crate) maximized: bool,
+    pub(crate) owned: bool,
+    pub(crate) parented: bool,
+    pub(crate) monitor: isize,
 }
 
 impl WindowObjInfo for WindowSnapshot {
@@ -33,6 +40,10 @@ impl WindowObjInfo for WindowSnapshot {
         self.exe_name.clone()
     }
 
+    fn get_exe_path(&self) -> Option<String> {
+        self.exe_path.clone()
+    }
+
     fn get_class_name(&self) -> Option<String> {
         self.class_name.clone()
     }
@@ -76,6 +87,22 @@ impl WindowObjInfo for WindowSnapshot {
     fn get_window_style(&self) -> u32 {
         self.style
     }
+
+    fn get_window_exstyle(&self) -> u32 {
+        self.exstyle
+    }
+
+    fn has_owner(&self) -> bool {
+        self.owned
+    }
+
+    fn has_parent(&self) -> bool {
+        self.parented
+    }
+
+    fn get_monitor_id(&self, monitors: &[Monitor]) -> Option<String> {
+        get_monitor_id(self.monitor, monitors)
+    }
 }
 
 impl Debug for WindowSnapshot {